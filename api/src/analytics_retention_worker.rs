@@ -0,0 +1,47 @@
+use backend_repo_pg::{
+    daily_salts::DailySaltRepo,
+    errors::PgRepoError,
+    identification_cookies::IdentificationCookieRepo,
+    page_views::PageViewRepo,
+    pg_util::{pg_transaction, DynRepo},
+    visits::VisitRepo,
+};
+use chrono::{Duration, Utc};
+
+const POLL_INTERVAL_SECS: u64 = 60 * 60;
+
+/// Keeps deleting old analytics data until the process stops. Page views are only
+/// rolled up when `retention_days` is set.
+pub async fn run(repo: DynRepo, retention_days: Option<i64>) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(POLL_INTERVAL_SECS));
+    loop {
+        interval.tick().await;
+        if let Err(err) = apply_retention(repo.clone(), retention_days).await {
+            tracing::error!("Analytics retention failed: {}", err);
+        }
+    }
+}
+
+/// Deletes expired identification cookies and past days' salts, then rolls page views
+/// older than `retention_days` up into daily totals. Returns how many views were rolled up.
+pub async fn apply_retention(
+    repo: DynRepo,
+    retention_days: Option<i64>,
+) -> Result<usize, PgRepoError> {
+    pg_transaction(repo, move |conn| {
+        let today = Utc::now().naive_utc().date();
+        IdentificationCookieRepo::new(conn).delete_expired()?;
+        DailySaltRepo::new(conn).delete_before(today)?;
+
+        let retention_days = match retention_days {
+            Some(days) => days,
+            None => return Ok(0),
+        };
+        // Whole days only, so none is rolled up twice
+        let before = (today - Duration::days(retention_days)).and_hms(0, 0, 0);
+        let archived = PageViewRepo::new(conn).archive_before(before)?;
+        VisitRepo::new(conn).delete_before(before)?;
+        Ok(archived)
+    })
+    .await
+}
@@ -1,10 +1,21 @@
-use std::net::SocketAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::{env, sync::Arc};
 
-use backend_repo_pg::pg_util::{get_pg_pool, PgRepo};
+use backend_repo_pg::pg_util::{get_pg_pool, DynRepo, PgRepo};
 use headers::HeaderValue;
 
-use crate::{emails::EmailSender as EmailSenderInner, routes};
+use crate::captcha::CaptchaProvider;
+use crate::emails::{EmailTransport, SmtpEmailTransport};
+use crate::errors::{CaptchaError, GeolocError};
+use crate::geolocation::{Location, MmdbGeolocation, NullGeolocation};
+use crate::live_views::LiveViews;
+use crate::privacy::AnalyticsSettings;
+use crate::{
+    analytics_retention_worker, digest_worker, email_worker,
+    emails::EmailSender as EmailSenderInner, geolocation_worker, newsletter_worker, routes,
+    search_refresh_worker,
+};
+use axum::async_trait;
 use jsonwebtoken::{DecodingKey, EncodingKey};
 use once_cell::sync::Lazy;
 
@@ -51,9 +62,14 @@ pub struct AppState {
     pub jwt_secret: String,
     pub jwt_duration: i64,
     pub captcha_secret: String,
+    pub captcha_provider: Option<CaptchaProvider>,
     pub static_file_dir: String,
     pub static_file_address: String,
     pub email_sender: EmailSenderInner,
+    pub email_transport: DynEmailTransport,
+    pub geolocation: DynGeolocation,
+    pub analytics: AnalyticsSettings,
+    pub live_views: LiveViews,
     pub origin: Vec<HeaderValue>,
 }
 
@@ -65,8 +81,19 @@ pub trait JwtDuration {
     fn jwt_duration(&self) -> i64;
 }
 
-pub trait CaptchaSecret {
-    fn captcha_secret(&self) -> &str;
+#[async_trait]
+pub trait CaptchaVerifier {
+    async fn verify(&self, token: &str) -> Result<(), CaptchaError>;
+}
+
+pub trait Geolocation {
+    /// Finds where an address is without waiting on anything outside the process.
+    fn lookup(&self, address: IpAddr) -> Option<Location>;
+
+    /// Loads the database again if it changed, returning whether it did.
+    fn reload(&self) -> Result<bool, GeolocError> {
+        Ok(false)
+    }
 }
 
 pub trait StaticFileDir {
@@ -97,14 +124,6 @@ impl JwtDuration for JwtDurationImpl {
     }
 }
 
-pub struct CaptchaSecretImpl(pub String);
-
-impl CaptchaSecret for CaptchaSecretImpl {
-    fn captcha_secret(&self) -> &str {
-        &self.0
-    }
-}
-
 pub struct StaticFileDirImpl(pub String);
 
 impl StaticFileDir for StaticFileDirImpl {
@@ -135,6 +154,12 @@ pub fn app_state() -> AppState {
         .parse()
         .expect("Failed to parse JWT_DURATION");
     let captcha_secret = env::var("CAPTCHA_SECRET").expect("CAPTCHA_SECRET must be set");
+    // `none` turns captcha checks off, meant for tests and local development
+    let captcha_provider = match env::var("CAPTCHA_PROVIDER") {
+        Ok(value) if value.trim().eq_ignore_ascii_case("none") => None,
+        Ok(value) => Some(value.parse().expect("Failed to parse CAPTCHA_PROVIDER")),
+        Err(_) => Some(CaptchaProvider::HCaptcha),
+    };
     let repo = get_pg_pool(database_url, 64);
     let origin = env::var("ORIGIN")
         .expect("ORIGIN must be set")
@@ -147,15 +172,29 @@ pub fn app_state() -> AppState {
     }
 
     let email_sender: EmailSenderInner = EmailSenderInner::new();
+    let email_transport = Arc::new(SmtpEmailTransport::new()) as DynEmailTransport;
+    // Page views are recorded without a location when no database is configured
+    let geolocation = match env::var("GEOIP_DATABASE_PATH") {
+        Ok(path) if !path.trim().is_empty() => {
+            Arc::new(MmdbGeolocation::open(path)) as DynGeolocation
+        }
+        _ => Arc::new(NullGeolocation) as DynGeolocation,
+    };
+    let analytics = AnalyticsSettings::from_env();
 
     AppState {
         repo,
         jwt_secret,
         jwt_duration,
         captcha_secret,
+        captcha_provider,
         static_file_dir,
         static_file_address,
         email_sender,
+        email_transport,
+        geolocation,
+        analytics,
+        live_views: LiveViews::new(),
         origin,
     }
 }
@@ -168,10 +207,12 @@ impl EmailSender for EmailSenderImpl {
 
 pub type DynJwtSecret = Arc<dyn JwtSecret + Send + Sync>;
 pub type DynJwtDuration = Arc<dyn JwtDuration + Send + Sync>;
-pub type DynCaptchaSecret = Arc<dyn CaptchaSecret + Send + Sync>;
+pub type DynCaptchaVerifier = Arc<dyn CaptchaVerifier + Send + Sync>;
 pub type DynStaticFileDir = Arc<dyn StaticFileDir + Send + Sync>;
 pub type DynStaticFileAddress = Arc<dyn StaticFileAddress + Send + Sync>;
 pub type DynEmailSender = Arc<dyn EmailSender + Send + Sync>;
+pub type DynEmailTransport = Arc<dyn EmailTransport + Send + Sync>;
+pub type DynGeolocation = Arc<dyn Geolocation + Send + Sync>;
 
 pub async fn start() {
     let bind_address: SocketAddr = env::var("BIND_ADDRESS")
@@ -186,6 +227,27 @@ pub async fn start() {
 
     println!("You can access the server at {}", bind_address);
 
+    tokio::spawn(email_worker::run(
+        Arc::new(app_state.repo.clone()) as DynRepo,
+        app_state.email_transport.clone(),
+    ));
+    tokio::spawn(digest_worker::run(
+        Arc::new(app_state.repo.clone()) as DynRepo,
+        app_state.email_sender.clone(),
+    ));
+    tokio::spawn(newsletter_worker::run(
+        Arc::new(app_state.repo.clone()) as DynRepo,
+        app_state.email_sender.clone(),
+    ));
+    tokio::spawn(search_refresh_worker::run(
+        Arc::new(app_state.repo.clone()) as DynRepo
+    ));
+    tokio::spawn(geolocation_worker::run(app_state.geolocation.clone()));
+    tokio::spawn(analytics_retention_worker::run(
+        Arc::new(app_state.repo.clone()) as DynRepo,
+        app_state.analytics.retention_days,
+    ));
+
     let app = routes::router(app_state).into_make_service_with_connect_info::<SocketAddr, _>();
     axum::Server::bind(&bind_address)
         .serve(app)
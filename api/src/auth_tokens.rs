@@ -1,19 +1,28 @@
-use backend_repo_pg::extra::UserRole;
-use chrono::{Duration, Utc};
+use backend_repo_pg::extra::{ApiTokenScope, UserRole};
+use backend_repo_pg::models::domain::NotificationKind;
+use chrono::{Duration, NaiveDateTime, Utc};
 use jsonwebtoken::errors::Result;
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use rand::{distributions::Alphanumeric, Rng};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha512};
 use uuid::Uuid;
 
 const TOKEN_PREFIX: &str = "Bearer ";
+pub const API_TOKEN_PREFIX: &str = "axd_";
 
 #[derive(Deserialize, Serialize, Debug, Clone, Eq, PartialEq)]
 pub enum AxmouthDotDevAudience {
     AxmouthDotDev,
     AdminDotAxmouthDotDev,
     Outside,
+    Unsubscribe,
 }
 
+/// Audiences of the tokens that authenticate a user. Decoders of these tokens check
+/// them so that an unsubscribe link can't be used as a session.
+pub const SESSION_AUDIENCES: [&str; 3] = ["AxmouthDotDev", "AdminDotAxmouthDotDev", "Outside"];
+
 #[derive(Deserialize, Serialize, Debug, Clone, Eq, PartialEq)]
 pub enum AxmouthDotDevIssuer {
     AxmouthDotDev,
@@ -32,6 +41,8 @@ pub struct Claims {
     nbf: i64,                   // time before which the token can't be used
     iss: AxmouthDotDevIssuer,   // token issuer
     jti: uuid::Uuid,            // token identifuer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    scopes: Option<Vec<ApiTokenScope>>, // only set when authenticated by api token
 }
 
 impl Claims {
@@ -46,6 +57,7 @@ impl Claims {
             nbf: Utc::now().timestamp(),
             iss: AxmouthDotDevIssuer::AxmouthDotDev,
             jti,
+            scopes: None,
         }
     }
     fn new_admin(
@@ -65,6 +77,28 @@ impl Claims {
             nbf: Utc::now().timestamp(),
             iss: AxmouthDotDevIssuer::AdminDotAxmouthDotDev,
             jti,
+            scopes: None,
+        }
+    }
+
+    pub fn new_api_token(
+        user_id: i32,
+        role: UserRole,
+        display_name: String,
+        scopes: Vec<ApiTokenScope>,
+        expires_at: Option<NaiveDateTime>,
+    ) -> Self {
+        Self {
+            sub: user_id,
+            role,
+            display_name,
+            aud: AxmouthDotDevAudience::Outside,
+            exp: expires_at.map_or(i64::MAX, |v| v.timestamp()),
+            iat: Utc::now().timestamp(),
+            nbf: Utc::now().timestamp(),
+            iss: AxmouthDotDevIssuer::AxmouthDotDev,
+            jti: Uuid::new_v4(),
+            scopes: Some(scopes),
         }
     }
 
@@ -92,6 +126,23 @@ impl Claims {
         self.role == UserRole::Admin
     }
 
+    /// Whether the user may change or remove something `owner_id` made.
+    pub fn can_manage(&self, owner_id: i32) -> bool {
+        self.sub == owner_id || self.is_admin()
+    }
+
+    pub fn is_api_token(&self) -> bool {
+        self.scopes.is_some()
+    }
+
+    /// Sessions have every scope, api tokens only the ones they were created with.
+    pub fn has_scope(&self, scope: ApiTokenScope) -> bool {
+        match &self.scopes {
+            Some(scopes) => scopes.contains(&scope),
+            None => true,
+        }
+    }
+
     pub fn is_for_admin_site(&self) -> bool {
         self.aud == AxmouthDotDevAudience::AdminDotAxmouthDotDev
     }
@@ -119,6 +170,7 @@ impl Claims {
             nbf: Utc::now().timestamp(),
             iss: self.iss.clone(),
             jti,
+            scopes: self.scopes.clone(),
         }
     }
 
@@ -168,6 +220,7 @@ pub fn decode_token(secret: &str, token: &str) -> Result<Claims> {
     let validation = &mut Validation::default();
     validation.validate_nbf = true;
     validation.validate_exp = false;
+    validation.set_audience(&SESSION_AUDIENCES);
     decode::<Claims>(
         token.trim_start_matches(TOKEN_PREFIX),
         &DecodingKey::from_secret(secret.as_ref()),
@@ -176,6 +229,52 @@ pub fn decode_token(secret: &str, token: &str) -> Result<Claims> {
     .map(|token_data| token_data.claims)
 }
 
+/// Claims of the signed links that turn off one kind of email. They do not expire,
+/// so a link in an old email keeps working, and their audience is checked by every
+/// decoder so they are never mistaken for sessions.
+#[derive(Deserialize, Serialize, Debug, Clone, Eq, PartialEq)]
+pub struct UnsubscribeClaims {
+    pub sub: i32,
+    pub kind: NotificationKind,
+    pub aud: AxmouthDotDevAudience,
+}
+
+pub fn encode_unsubscribe_token(key: &EncodingKey, user_id: i32, kind: NotificationKind) -> String {
+    encode(
+        &Header::default(),
+        &UnsubscribeClaims {
+            sub: user_id,
+            kind,
+            aud: AxmouthDotDevAudience::Unsubscribe,
+        },
+        key,
+    )
+    .unwrap()
+}
+
+pub fn decode_unsubscribe_token(key: &DecodingKey, token: &str) -> Result<UnsubscribeClaims> {
+    let mut validation = Validation::default();
+    validation.required_spec_claims.clear();
+    validation.validate_exp = false;
+    validation.set_audience(&["Unsubscribe"]);
+    decode::<UnsubscribeClaims>(token, key, &validation).map(|token_data| token_data.claims)
+}
+
+pub fn generate_api_token() -> String {
+    let secret = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .map(char::from)
+        .take(40)
+        .collect::<String>();
+    format!("{}{}", API_TOKEN_PREFIX, secret)
+}
+
+pub fn hash_api_token(token: &str) -> String {
+    let mut hasher = Sha512::new();
+    hasher.update(token);
+    format!("{:x}", hasher.finalize())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -203,4 +302,58 @@ mod tests {
 
         assert!(decoded.is_ok());
     }
+
+    #[test]
+    fn only_owners_and_admins_can_manage() {
+        let claims = |role| Claims::new(7, role, Uuid::new_v4(), "usahname".to_string(), 100);
+        assert!(claims(UserRole::User).can_manage(7));
+        assert!(!claims(UserRole::User).can_manage(8));
+        assert!(!claims(UserRole::Moderator).can_manage(8));
+        assert!(claims(UserRole::Admin).can_manage(7));
+        assert!(claims(UserRole::Admin).can_manage(8));
+    }
+
+    #[test]
+    fn encode_decode_unsubscribe_token() {
+        let token = encode_unsubscribe_token(
+            &EncodingKey::from_secret(b"sikureto"),
+            42,
+            NotificationKind::CommentReplies,
+        );
+        let claims =
+            decode_unsubscribe_token(&DecodingKey::from_secret(b"sikureto"), &token).unwrap();
+        assert_eq!(
+            claims,
+            UnsubscribeClaims {
+                sub: 42,
+                kind: NotificationKind::CommentReplies,
+                aud: AxmouthDotDevAudience::Unsubscribe,
+            }
+        );
+        assert!(decode_unsubscribe_token(&DecodingKey::from_secret(b"other"), &token).is_err());
+    }
+
+    #[test]
+    fn unsubscribe_and_session_tokens_are_not_interchangeable() {
+        let unsubscribe_token = encode_unsubscribe_token(
+            &EncodingKey::from_secret(b"sikureto"),
+            42,
+            NotificationKind::CommentReplies,
+        );
+        assert!(decode_token("sikureto", &unsubscribe_token).is_err());
+
+        let session_token = encode_token(
+            "sikureto",
+            42,
+            UserRole::User,
+            Uuid::new_v4(),
+            "usahname".to_string(),
+            100,
+        );
+        assert!(decode_token("sikureto", &session_token).is_ok());
+        assert!(
+            decode_unsubscribe_token(&DecodingKey::from_secret(b"sikureto"), &session_token)
+                .is_err()
+        );
+    }
 }
@@ -0,0 +1,126 @@
+/// Views from one visitor within a minute, past which they are taken to be a bot.
+pub const BOT_VIEWS_PER_MINUTE: i64 = 30;
+
+/// Lowercase parts of user agents sent by crawlers, link previewers, monitors and
+/// http libraries. Generic words like "bot" and "spider" catch most of the rest.
+/// Migration `105000_add_page_view_bots` classified older views with a copy of this
+/// list, which a test keeps in step.
+const BOT_USER_AGENTS: [&str; 44] = [
+    "bot",
+    "crawl",
+    "spider",
+    "slurp",
+    "archiver",
+    "scraper",
+    "headlesschrome",
+    "phantomjs",
+    "puppeteer",
+    "playwright",
+    "selenium",
+    "lighthouse",
+    "pagespeed",
+    "gtmetrix",
+    "pingdom",
+    "uptimerobot",
+    "statuscake",
+    "site24x7",
+    "monitor",
+    "facebookexternalhit",
+    "facebookcatalog",
+    "embedly",
+    "whatsapp",
+    "skypeuripreview",
+    "bitlypreview",
+    "preview",
+    "feedfetcher",
+    "feedly",
+    "inoreader",
+    "newsblur",
+    "curl/",
+    "wget/",
+    "httpie/",
+    "python-requests",
+    "python-urllib",
+    "aiohttp",
+    "go-http-client",
+    "okhttp",
+    "java/",
+    "apache-httpclient",
+    "libwww-perl",
+    "node-fetch",
+    "axios/",
+    "scrapy",
+];
+
+/// Whether a user agent belongs to a bot, going by the list above. Browsers always
+/// send one, so a missing or empty user agent counts as a bot too.
+pub fn is_bot_user_agent(user_agent: Option<&str>) -> bool {
+    let user_agent = match user_agent.map(str::trim) {
+        Some(value) if !value.is_empty() => value.to_lowercase(),
+        _ => return true,
+    };
+    BOT_USER_AGENTS
+        .iter()
+        .any(|pattern| user_agent.contains(pattern))
+}
+
+/// Whether a view is from a bot, by its user agent or by how many views the same
+/// visitor made in the last minute.
+pub fn is_bot(user_agent: Option<&str>, views_in_last_minute: i64) -> bool {
+    views_in_last_minute >= BOT_VIEWS_PER_MINUTE || is_bot_user_agent(user_agent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIREFOX: &str = "Mozilla/5.0 (X11; Linux x86_64; rv:109.0) Gecko/20100101 Firefox/115.0";
+
+    #[test]
+    fn browsers_are_not_bots() {
+        assert!(!is_bot_user_agent(Some(FIREFOX)));
+        assert!(!is_bot_user_agent(Some(
+            "Mozilla/5.0 (iPhone; CPU iPhone OS 16_5 like Mac OS X) AppleWebKit/605.1.15 \
+             (KHTML, like Gecko) Version/16.5 Mobile/15E148 Safari/604.1"
+        )));
+    }
+
+    #[test]
+    fn crawlers_and_tools_are_bots() {
+        assert!(is_bot_user_agent(Some(
+            "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)"
+        )));
+        assert!(is_bot_user_agent(Some(
+            "facebookexternalhit/1.1 (+http://www.facebook.com/externalhit_uatext.php)"
+        )));
+        assert!(is_bot_user_agent(Some("curl/8.1.2")));
+        assert!(is_bot_user_agent(Some(
+            "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) \
+             HeadlessChrome/114.0.0.0 Safari/537.36"
+        )));
+        assert!(is_bot_user_agent(None));
+        assert!(is_bot_user_agent(Some(" ")));
+    }
+
+    #[test]
+    fn migration_backfill_matches_list() {
+        let migration =
+            include_str!("../../repo_pg/migrations/2026-10-19-105000_add_page_view_bots/up.sql");
+        let start = migration.find("user_agent ~* '(").unwrap() + "user_agent ~* '(".len();
+        let end = start + migration[start..].find(")'").unwrap();
+        let patterns: Vec<&str> = migration[start..end].split('|').collect();
+        assert_eq!(patterns, BOT_USER_AGENTS.to_vec());
+        // Matched as a regex there and as plain text here
+        for pattern in BOT_USER_AGENTS.iter() {
+            assert!(pattern
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "/-".contains(c)));
+        }
+    }
+
+    #[test]
+    fn fast_visitors_are_bots() {
+        assert!(!is_bot(Some(FIREFOX), BOT_VIEWS_PER_MINUTE - 1));
+        assert!(is_bot(Some(FIREFOX), BOT_VIEWS_PER_MINUTE));
+    }
+}
@@ -0,0 +1,74 @@
+use crate::emails::EmailSender;
+use backend_repo_pg::{
+    blog_posts::BlogPostRepo,
+    errors::PgRepoError,
+    filters::GetAllBlogPostsFilter,
+    notification_preferences::NotificationPreferencesRepo,
+    options::{BlogPostSortType, PaginationOptions},
+    pg_util::{pg_transaction, DynRepo},
+};
+use chrono::{Duration, Utc};
+
+const POLL_INTERVAL_SECS: u64 = 60 * 60;
+const DIGEST_INTERVAL_DAYS: i64 = 7;
+
+/// Keeps sending the weekly new post digest until the process stops.
+pub async fn run(repo: DynRepo, email_sender: EmailSender) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(POLL_INTERVAL_SECS));
+    loop {
+        interval.tick().await;
+        if let Err(err) = send_due_digests(repo.clone(), &email_sender).await {
+            tracing::error!("New post digest failed: {}", err);
+        }
+    }
+}
+
+/// Queues a digest for every subscriber whose last one is older than a week and
+/// returns how many were queued. Subscribers with nothing new are skipped until
+/// the next week.
+pub async fn send_due_digests(
+    repo: DynRepo,
+    email_sender: &EmailSender,
+) -> Result<usize, PgRepoError> {
+    pg_transaction(repo, move |conn| {
+        let now = Utc::now().naive_utc();
+        let due_before = now - Duration::days(DIGEST_INTERVAL_DAYS);
+        let preferences_repository = NotificationPreferencesRepo::new(conn);
+        let blog_post_repository = BlogPostRepo::new(conn);
+        let mut sent = 0;
+        for (preferences, user) in preferences_repository.find_digest_due(due_before)? {
+            let filter = GetAllBlogPostsFilter {
+                author_id: None,
+                category_id: None,
+                category_name: None,
+                category_ids: None,
+                published: Some(true),
+                published_after: Some(preferences.last_digest_sent_at.unwrap_or(due_before)),
+            };
+            let (posts, _) = blog_post_repository.find(
+                filter,
+                Some(BlogPostSortType::CreatedAtDesc),
+                PaginationOptions {
+                    page: None,
+                    page_size: None,
+                },
+            )?;
+            if !posts.is_empty() {
+                match email_sender.send_new_posts_digest_email(conn, &user, &posts) {
+                    Ok(_) => sent += 1,
+                    Err(err) => {
+                        tracing::error!(
+                            "New post digest for user {} failed: {}",
+                            user.id,
+                            err.to_string()
+                        );
+                        continue;
+                    }
+                }
+            }
+            preferences_repository.mark_digest_sent(user.id, now)?;
+        }
+        Ok(sent)
+    })
+    .await
+}
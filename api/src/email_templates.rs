@@ -0,0 +1,253 @@
+use std::{collections::HashMap, error::Error, fmt, str::FromStr};
+
+use serde::Serialize;
+use tera::Context;
+
+use crate::app::TEMPLATES;
+use crate::errors::EmailError;
+
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Every transactional email. Each one lives in `templates/emails/<name>/` as
+/// `<name>_subject.txt`, `<name>_html.html` and `<name>_text.txt`, with optional
+/// translations of the same files in a `<locale>/` subdirectory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmailTemplate {
+    Contact,
+    ContactReply,
+    VerifyEmail,
+    ResetPassword,
+    ChangeEmailConfirmation,
+    ChangeEmailNotice,
+    CommentReply,
+    CommentModeration,
+    NewPostsDigest,
+    NewsletterConfirmation,
+    NewsletterIssue,
+}
+
+impl EmailTemplate {
+    pub const ALL: [EmailTemplate; 11] = [
+        EmailTemplate::Contact,
+        EmailTemplate::ContactReply,
+        EmailTemplate::VerifyEmail,
+        EmailTemplate::ResetPassword,
+        EmailTemplate::ChangeEmailConfirmation,
+        EmailTemplate::ChangeEmailNotice,
+        EmailTemplate::CommentReply,
+        EmailTemplate::CommentModeration,
+        EmailTemplate::NewPostsDigest,
+        EmailTemplate::NewsletterConfirmation,
+        EmailTemplate::NewsletterIssue,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            EmailTemplate::Contact => "contact",
+            EmailTemplate::ContactReply => "contact_reply",
+            EmailTemplate::VerifyEmail => "verify_email",
+            EmailTemplate::ResetPassword => "reset_password",
+            EmailTemplate::ChangeEmailConfirmation => "change_email_confirmation",
+            EmailTemplate::ChangeEmailNotice => "change_email_notice",
+            EmailTemplate::CommentReply => "comment_reply",
+            EmailTemplate::CommentModeration => "comment_moderation",
+            EmailTemplate::NewPostsDigest => "new_posts_digest",
+            EmailTemplate::NewsletterConfirmation => "newsletter_confirmation",
+            EmailTemplate::NewsletterIssue => "newsletter_issue",
+        }
+    }
+
+    /// Data used to render the template when previewing it.
+    pub fn sample_context(&self, website_url: &str) -> Context {
+        let mut context = Context::new();
+        context.insert("website_url", website_url);
+        match self {
+            EmailTemplate::Contact => {
+                context.insert("from_email", "visitor@example.com");
+                context.insert("subject", "Hello there");
+                context.insert("body", "I liked your latest post.\nThanks!");
+            }
+            EmailTemplate::ContactReply => {
+                context.insert("subject", "Hello there");
+                context.insert("reply_body", "Thank you, glad you liked it!");
+                context.insert("original_body", "I liked your latest post.\nThanks!");
+            }
+            EmailTemplate::VerifyEmail | EmailTemplate::ResetPassword => {
+                context.insert("display_name", "Sample User");
+                context.insert("token", "sample-token");
+            }
+            EmailTemplate::ChangeEmailConfirmation | EmailTemplate::ChangeEmailNotice => {
+                context.insert("display_name", "Sample User");
+                context.insert("new_email", "new-address@example.com");
+                context.insert("token", "sample-token");
+            }
+            EmailTemplate::CommentReply => {
+                context.insert("display_name", "Sample User");
+                context.insert("replier_name", "Another User");
+                context.insert("post_title", "A Sample Post");
+                context.insert("post_slug", "a-sample-post");
+                context.insert("reply_body", "I disagree, and here is why.");
+                context.insert("unsubscribe_url", &sample_unsubscribe_url(website_url));
+            }
+            EmailTemplate::CommentModeration => {
+                context.insert("display_name", "Sample User");
+                context.insert("decision", "removed");
+                context.insert("post_title", "A Sample Post");
+                context.insert("post_slug", "a-sample-post");
+                context.insert("comment_body", "Buy cheap watches here!");
+                context.insert("unsubscribe_url", &sample_unsubscribe_url(website_url));
+            }
+            EmailTemplate::NewPostsDigest => {
+                let mut post = HashMap::new();
+                post.insert("title", "A Sample Post");
+                post.insert("slug", "a-sample-post");
+                context.insert("display_name", "Sample User");
+                context.insert("posts", &vec![post]);
+                context.insert("unsubscribe_url", &sample_unsubscribe_url(website_url));
+            }
+            EmailTemplate::NewsletterConfirmation => {
+                context.insert("email", "reader@example.com");
+                context.insert(
+                    "confirm_url",
+                    &format!(
+                        "http://{}/newsletter/confirm?token=sample-token",
+                        website_url
+                    ),
+                );
+            }
+            EmailTemplate::NewsletterIssue => {
+                let mut post = HashMap::new();
+                post.insert("title", "A Sample Post");
+                post.insert("slug", "a-sample-post");
+                context.insert("subject", "What happened this month");
+                context.insert("intro", "A few new posts went up since the last issue.");
+                context.insert("posts", &vec![post]);
+                context.insert(
+                    "unsubscribe_url",
+                    &format!(
+                        "http://{}/newsletter/unsubscribe?token=sample-token",
+                        website_url
+                    ),
+                );
+            }
+        }
+        context
+    }
+}
+
+fn sample_unsubscribe_url(website_url: &str) -> String {
+    format!(
+        "http://{}/notifications/unsubscribe?token=sample-token",
+        website_url
+    )
+}
+
+impl fmt::Display for EmailTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for EmailTemplate {
+    type Err = EmailError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        EmailTemplate::ALL
+            .iter()
+            .find(|template| template.name() == s)
+            .copied()
+            .ok_or_else(|| EmailError::new(format!("Unknown email template: {}", s)))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenderedEmail {
+    pub template: String,
+    pub locale: String,
+    pub subject: String,
+    pub body_html: String,
+    pub body_text: String,
+}
+
+/// Renders the subject and both bodies of a template, using the translation for
+/// `locale` where one exists and the default templates otherwise.
+pub fn render_email_template(
+    template: EmailTemplate,
+    locale: &str,
+    context: &Context,
+) -> Result<RenderedEmail, EmailError> {
+    let subject = render_variant(template, locale, "subject.txt", context)?;
+    let body_html = render_variant(template, locale, "html.html", context)?;
+    let body_text = render_variant(template, locale, "text.txt", context)?;
+    Ok(RenderedEmail {
+        template: template.name().to_string(),
+        locale: locale.to_string(),
+        subject: subject.trim().to_string(),
+        body_html,
+        body_text,
+    })
+}
+
+fn render_variant(
+    template: EmailTemplate,
+    locale: &str,
+    variant: &str,
+    context: &Context,
+) -> Result<String, EmailError> {
+    let template_name = resolve_template_name(template, locale, variant);
+    TEMPLATES.render(&template_name, context).map_err(|e| {
+        let mut message = e.to_string();
+        let mut cause = e.source();
+        while let Some(e) = cause {
+            message = format!("{}: {}", message, e);
+            cause = e.source();
+        }
+        EmailError::new(message)
+    })
+}
+
+fn resolve_template_name(template: EmailTemplate, locale: &str, variant: &str) -> String {
+    let name = template.name();
+    let localized = format!("emails/{}/{}/{}_{}", name, locale, name, variant);
+    if TEMPLATES.get_template_names().any(|t| t == localized) {
+        localized
+    } else {
+        format!("emails/{}/{}_{}", name, name, variant)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_templates_render_with_sample_data() {
+        for template in EmailTemplate::ALL.iter() {
+            let context = template.sample_context("axmouth.dev");
+            let rendered = render_email_template(*template, DEFAULT_LOCALE, &context).unwrap();
+            assert!(!rendered.subject.is_empty());
+            assert!(!rendered.subject.contains('\n'));
+            assert!(!rendered.body_html.is_empty());
+            assert!(!rendered.body_text.is_empty());
+        }
+    }
+
+    #[test]
+    fn unknown_locale_falls_back_to_default() {
+        let context = EmailTemplate::VerifyEmail.sample_context("axmouth.dev");
+        let default =
+            render_email_template(EmailTemplate::VerifyEmail, DEFAULT_LOCALE, &context).unwrap();
+        let fallback = render_email_template(EmailTemplate::VerifyEmail, "xx", &context).unwrap();
+        assert_eq!(default.body_html, fallback.body_html);
+        assert_eq!(fallback.locale, "xx");
+    }
+
+    #[test]
+    fn template_names_round_trip() {
+        for template in EmailTemplate::ALL.iter() {
+            assert_eq!(template.name().parse::<EmailTemplate>().unwrap(), *template);
+        }
+        assert!("nope".parse::<EmailTemplate>().is_err());
+    }
+}
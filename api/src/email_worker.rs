@@ -0,0 +1,105 @@
+use crate::app::DynEmailTransport;
+use crate::emails::EmailMessage;
+use backend_repo_pg::{
+    change_sets::UpdateOutboxEmail,
+    email_outbox::OutboxEmailRepo,
+    errors::PgRepoError,
+    extra::EmailStatus,
+    pg_util::{pg_transaction, DynRepo},
+};
+use chrono::{Duration, Utc};
+use tokio::task::block_in_place;
+
+const BATCH_SIZE: i64 = 20;
+const MAX_ATTEMPTS: i32 = 8;
+const POLL_INTERVAL_SECS: u64 = 10;
+/// How long a claimed message is held before another attempt may take it.
+const LEASE_SECS: i64 = 10 * 60;
+
+/// Delay before the next attempt, doubling from 30 seconds up to 6 hours.
+pub fn backoff(attempts: i32) -> Duration {
+    let seconds = 30_i64
+        .saturating_mul(1 << attempts.clamp(0, 20))
+        .min(6 * 60 * 60);
+    Duration::seconds(seconds)
+}
+
+/// Keeps delivering the outbox until the process stops.
+pub async fn run(repo: DynRepo, transport: DynEmailTransport) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(POLL_INTERVAL_SECS));
+    loop {
+        interval.tick().await;
+        if let Err(err) = deliver_due(repo.clone(), transport.clone()).await {
+            tracing::error!("Email outbox delivery failed: {}", err);
+        }
+    }
+}
+
+/// Sends the messages that are due and returns how many went out.
+///
+/// Messages are claimed in one short transaction and each outcome is recorded in
+/// its own, so no transaction stays open while the mail server is talking and a
+/// failure to record one outcome can't put already sent messages back in the queue.
+pub async fn deliver_due(
+    repo: DynRepo,
+    transport: DynEmailTransport,
+) -> Result<usize, PgRepoError> {
+    let claimed = pg_transaction(repo.clone(), |conn| {
+        OutboxEmailRepo::new(conn).claim_due(BATCH_SIZE, Duration::seconds(LEASE_SECS))
+    })
+    .await?;
+    let mut sent = 0;
+    for email in claimed {
+        let id = email.id;
+        let attempts = email.attempts;
+        let result = block_in_place(|| transport.send(&EmailMessage::from_outbox(email)));
+        if result.is_ok() {
+            sent += 1;
+        }
+        let recorded = pg_transaction(repo.clone(), move |conn| {
+            let outbox_repository = OutboxEmailRepo::new(conn);
+            match result {
+                Ok(_) => outbox_repository.mark_sent(id),
+                Err(err) => {
+                    let status = if attempts >= MAX_ATTEMPTS {
+                        EmailStatus::Failed
+                    } else {
+                        EmailStatus::Pending
+                    };
+                    outbox_repository.update_one(
+                        id,
+                        UpdateOutboxEmail {
+                            status: Some(status),
+                            attempts: None,
+                            last_error: Some(Some(err.to_string())),
+                            next_attempt_at: Some(Utc::now().naive_utc() + backoff(attempts)),
+                            sent_at: None,
+                        },
+                    )
+                }
+            }
+        })
+        .await;
+        if let Err(err) = recorded {
+            tracing::error!(
+                "Could not record the outcome of outbox email {}: {}",
+                id,
+                err
+            );
+        }
+    }
+    Ok(sent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_and_caps() {
+        assert_eq!(backoff(0), Duration::seconds(30));
+        assert_eq!(backoff(1), Duration::seconds(60));
+        assert_eq!(backoff(3), Duration::seconds(240));
+        assert_eq!(backoff(MAX_ATTEMPTS * 4), Duration::hours(6));
+    }
+}
@@ -1,33 +1,71 @@
 use lettre_email::Email;
 use tera::Context;
 
-use crate::app::TEMPLATES;
+use crate::app::KEYS;
+use crate::auth_tokens::encode_unsubscribe_token;
+use crate::email_templates::{render_email_template, EmailTemplate, RenderedEmail, DEFAULT_LOCALE};
 use crate::errors::EmailError;
+use crate::routes::{
+    API_PREFIX, NEWSLETTER_CONFIRM_PATH, NEWSLETTER_UNSUBSCRIBE_PATH,
+    NOTIFICATIONS_UNSUBSCRIBE_PATH,
+};
+use backend_repo_pg::{
+    email_outbox::OutboxEmailRepo,
+    errors::PgRepoError,
+    insertables::NewOutboxEmail,
+    models::domain::{BlogPost, NewsletterIssue, NotificationKind, OutboxEmail, User},
+    pg_util::RepoConnection,
+};
 use lettre::{
     smtp::authentication::{Credentials, Mechanism},
-    ClientSecurity, ClientTlsParameters, SendableEmail, SmtpClient, Transport,
+    ClientSecurity, ClientTlsParameters, SmtpClient, Transport,
 };
 use native_tls::{Protocol, TlsConnector};
-use std::{env, error::Error};
+use serde::Serialize;
+use std::env;
+use std::sync::Mutex;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmailMessage {
+    pub to_address: String,
+    pub to_name: Option<String>,
+    pub subject: String,
+    pub body_html: String,
+    pub body_text: String,
+    /// One-click unsubscribe address, sent as the `List-Unsubscribe` header.
+    pub list_unsubscribe: Option<String>,
+}
+
+impl EmailMessage {
+    pub fn from_outbox(email: OutboxEmail) -> Self {
+        Self {
+            to_address: email.to_address,
+            to_name: email.to_name,
+            subject: email.subject,
+            body_html: email.body_html,
+            body_text: email.body_text,
+            list_unsubscribe: email.list_unsubscribe,
+        }
+    }
+}
+
+/// Delivers messages taken from the outbox.
+pub trait EmailTransport {
+    fn send(&self, message: &EmailMessage) -> Result<(), EmailError>;
+}
 
 #[derive(Debug, Clone, Default)]
-pub struct EmailSender {
-    pub contact_address: String,
+pub struct SmtpEmailTransport {
     pub from_address: String,
     pub host: String,
     pub port: u16,
     pub username: Option<String>,
     pub password: Option<String>,
-    pub website_url: String,
 }
 
-impl EmailSender {
+impl SmtpEmailTransport {
     pub fn new() -> Self {
-        EmailSender {
-            contact_address: env::var("CONTACT_ADDRESS")
-                .expect("CONTACT_ADDRESS is not set")
-                .trim()
-                .to_string(),
+        SmtpEmailTransport {
             from_address: env::var("MAIL_FROM_ADDRESS")
                 .expect("MAIL_FROM_ADDRESS is not set")
                 .trim()
@@ -39,16 +77,30 @@ impl EmailSender {
                 .expect("MAIL_PORT is invalid"),
             password: env::var("MAIL_PASSWORD").ok(),
             username: env::var("MAIL_USERNAME").ok(),
-            website_url: env::var("WEBSITE_URL").expect("WEBSITE_URL is not set"),
         }
     }
 }
 
-impl EmailSender {
-    pub fn send_email(
-        &self,
-        email: SendableEmail,
-    ) -> Result<lettre::smtp::response::Response, EmailError> {
+impl EmailTransport for SmtpEmailTransport {
+    fn send(&self, message: &EmailMessage) -> Result<(), EmailError> {
+        let builder = match &message.to_name {
+            // Addresses can be specified by the tuple (email, alias)
+            Some(name) => Email::builder().to((message.to_address.clone(), name.clone())),
+            // ... or by an address only
+            None => Email::builder().to(message.to_address.clone()),
+        };
+        let builder = match &message.list_unsubscribe {
+            Some(url) => builder
+                .header(("List-Unsubscribe", format!("<{}>", url)))
+                .header(("List-Unsubscribe-Post", "List-Unsubscribe=One-Click")),
+            None => builder,
+        };
+        let email = builder
+            .from(self.from_address.clone())
+            .subject(message.subject.clone())
+            .alternative(message.body_html.clone(), message.body_text.clone())
+            .build()?;
+
         let mut tls_builder = TlsConnector::builder();
         tls_builder.min_protocol_version(Some(Protocol::Tlsv10));
         let tls_parameters = ClientTlsParameters::new(self.host.clone(), tls_builder.build()?);
@@ -67,153 +119,502 @@ impl EmailSender {
         }
         let mut mailer = mailer.transport();
         // Send the email
-        Ok(mailer.send(email)?)
+        mailer.send(email.into())?;
+        Ok(())
+    }
+}
+
+/// Keeps every message in memory instead of sending it, for tests.
+#[derive(Debug, Default)]
+pub struct InMemoryEmailTransport {
+    messages: Mutex<Vec<EmailMessage>>,
+}
+
+impl InMemoryEmailTransport {
+    pub fn messages(&self) -> Vec<EmailMessage> {
+        self.messages
+            .lock()
+            .expect("Email capture poisoned")
+            .clone()
+    }
+}
+
+impl EmailTransport for InMemoryEmailTransport {
+    fn send(&self, message: &EmailMessage) -> Result<(), EmailError> {
+        self.messages
+            .lock()
+            .expect("Email capture poisoned")
+            .push(message.clone());
+        Ok(())
+    }
+}
+
+/// Renders emails and puts them in the outbox, in the transaction of the caller.
+/// They are delivered later by the outbox worker.
+#[derive(Debug, Clone, Default)]
+pub struct EmailSender {
+    pub contact_address: String,
+    pub website_url: String,
+    pub api_url: String,
+    pub locale: String,
+}
+
+impl EmailSender {
+    pub fn new() -> Self {
+        EmailSender {
+            contact_address: env::var("CONTACT_ADDRESS")
+                .expect("CONTACT_ADDRESS is not set")
+                .trim()
+                .to_string(),
+            website_url: env::var("WEBSITE_URL").expect("WEBSITE_URL is not set"),
+            api_url: env::var("API_URL")
+                .expect("API_URL is not set")
+                .trim_end_matches('/')
+                .to_string(),
+            locale: env::var("EMAIL_LOCALE").unwrap_or_else(|_| DEFAULT_LOCALE.to_string()),
+        }
+    }
+}
+
+impl EmailSender {
+    pub fn queue_email(
+        &self,
+        conn: &RepoConnection,
+        message: EmailMessage,
+    ) -> Result<OutboxEmail, EmailError> {
+        let outbox_repository = OutboxEmailRepo::new(conn);
+        let new_email = NewOutboxEmail {
+            to_address: message.to_address,
+            to_name: message.to_name,
+            subject: message.subject,
+            body_html: message.body_html,
+            body_text: message.body_text,
+            list_unsubscribe: message.list_unsubscribe,
+        };
+        Ok(outbox_repository
+            .insert_one(new_email)
+            .map_err::<PgRepoError, _>(|e| e.into())?)
+    }
+
+    /// Renders a template in the given locale, or the configured one, and queues it.
+    pub fn send_templated_email(
+        &self,
+        conn: &RepoConnection,
+        template: EmailTemplate,
+        locale: Option<&str>,
+        to_address: String,
+        to_name: Option<String>,
+        context: Context,
+    ) -> Result<OutboxEmail, EmailError> {
+        let message = self.render_message(template, locale, to_address, to_name, context)?;
+        self.queue_email(conn, message)
+    }
+
+    /// Queues an email that a user can opt out of, with a signed link to do so in
+    /// the body and the one-click `List-Unsubscribe` headers.
+    pub fn send_notification_email(
+        &self,
+        conn: &RepoConnection,
+        template: EmailTemplate,
+        kind: NotificationKind,
+        recipient: &User,
+        mut context: Context,
+    ) -> Result<Option<OutboxEmail>, EmailError> {
+        let to_address = match &recipient.email {
+            Some(email) if !email.is_empty() => email.clone(),
+            _ => return Ok(None),
+        };
+        let token = encode_unsubscribe_token(&KEYS.encoding, recipient.id, kind);
+        context.insert("display_name", &recipient.display_name);
+        self.queue_unsubscribable_email(
+            conn,
+            template,
+            to_address,
+            Some(recipient.display_name.clone()),
+            self.notifications_unsubscribe_url(&token),
+            context,
+        )
+        .map(Some)
+    }
+
+    /// Address of an api route, `path` being what it is registered as in the router.
+    pub fn api_v1_url(&self, path: &str) -> String {
+        format!("{}{}{}", self.api_url, API_PREFIX, path)
+    }
+
+    fn notifications_unsubscribe_url(&self, token: &str) -> String {
+        self.api_v1_token_url(NOTIFICATIONS_UNSUBSCRIBE_PATH, token)
+    }
+
+    fn newsletter_confirm_url(&self, token: &str) -> String {
+        self.api_v1_token_url(NEWSLETTER_CONFIRM_PATH, token)
+    }
+
+    fn newsletter_unsubscribe_url(&self, token: &str) -> String {
+        self.api_v1_token_url(NEWSLETTER_UNSUBSCRIBE_PATH, token)
+    }
+
+    fn api_v1_token_url(&self, path: &str, token: &str) -> String {
+        format!(
+            "{}?token={}",
+            self.api_v1_url(path),
+            urlencoding::encode(token)
+        )
+    }
+
+    /// Queues an email with a link to `unsubscribe_url` on the API, which asks for
+    /// confirmation when opened, in the body and as the one-click address.
+    fn queue_unsubscribable_email(
+        &self,
+        conn: &RepoConnection,
+        template: EmailTemplate,
+        to_address: String,
+        to_name: Option<String>,
+        unsubscribe_url: String,
+        mut context: Context,
+    ) -> Result<OutboxEmail, EmailError> {
+        context.insert("unsubscribe_url", &unsubscribe_url);
+        let mut message = self.render_message(template, None, to_address, to_name, context)?;
+        message.list_unsubscribe = Some(unsubscribe_url);
+        self.queue_email(conn, message)
+    }
+
+    fn render_message(
+        &self,
+        template: EmailTemplate,
+        locale: Option<&str>,
+        to_address: String,
+        to_name: Option<String>,
+        mut context: Context,
+    ) -> Result<EmailMessage, EmailError> {
+        let locale = locale.unwrap_or(&self.locale);
+        context.insert("website_url", &self.website_url);
+        context.insert("locale", locale);
+        let rendered = render_email_template(template, locale, &context)?;
+        Ok(EmailMessage {
+            to_address,
+            to_name,
+            subject: rendered.subject,
+            body_html: rendered.body_html,
+            body_text: rendered.body_text,
+            list_unsubscribe: None,
+        })
+    }
+
+    /// Renders a template with its sample data, without sending anything.
+    pub fn preview(
+        &self,
+        template: EmailTemplate,
+        locale: Option<&str>,
+    ) -> Result<RenderedEmail, EmailError> {
+        let locale = locale.unwrap_or(&self.locale);
+        let mut context = template.sample_context(&self.website_url);
+        context.insert("locale", locale);
+        render_email_template(template, locale, &context)
     }
 
     pub fn send_contact_email(
         &self,
+        conn: &RepoConnection,
         from_email: String,
         subject: String,
         body: String,
     ) -> Result<(), EmailError> {
-        let body_html = format!(
-            "<p>Email to reply to: {}</p><br /><br /><br /><pre>{}</pre>",
-            from_email, body
-        );
-        let body_text = format!("Email to reply to: {}\n\n\n{}", from_email, body);
-        let email = Email::builder()
-            // Addresses can be specified by the tuple (email, alias)
-            .to(self.contact_address.clone())
-            // ... or by an address only
-            .from(self.from_address.clone())
-            .subject(subject)
-            .alternative(body_html, body_text)
-            .build()?;
-
-        let _ = self.send_email(email.into())?;
+        let mut context = Context::new();
+        context.insert("from_email", &from_email);
+        context.insert("subject", &subject);
+        context.insert("body", &body);
+        self.send_templated_email(
+            conn,
+            EmailTemplate::Contact,
+            None,
+            self.contact_address.clone(),
+            None,
+            context,
+        )?;
         Ok(())
     }
 
+    pub fn send_contact_reply_email(
+        &self,
+        conn: &RepoConnection,
+        to_address: String,
+        subject: &str,
+        reply_body: &str,
+        original_body: &str,
+    ) -> Result<OutboxEmail, EmailError> {
+        let mut context = Context::new();
+        context.insert("subject", subject);
+        context.insert("reply_body", reply_body);
+        context.insert("original_body", original_body);
+        self.send_templated_email(
+            conn,
+            EmailTemplate::ContactReply,
+            None,
+            to_address,
+            None,
+            context,
+        )
+    }
+
     pub fn send_email_verification_email(
         &self,
+        conn: &RepoConnection,
         user_email: String,
         username: String,
         token: String,
     ) -> Result<(), EmailError> {
-        let email = Email::builder()
-            // Addresses can be specified by the tuple (email, alias)
-            .to((user_email, username.clone()))
-            // ... or by an address only
-            .from(self.from_address.clone())
-            .subject(format!("Hi {}, we need you to verify your email", username))
-            .alternative(
-                self.get_verification_email_html(username.clone(), token.clone()),
-                self.get_verification_email_text(username, token),
-            )
-            .build()?;
-
-        let _ = self.send_email(email.into())?;
+        let mut context = Context::new();
+        context.insert("display_name", &username);
+        context.insert("token", &token);
+        self.send_templated_email(
+            conn,
+            EmailTemplate::VerifyEmail,
+            None,
+            user_email,
+            Some(username),
+            context,
+        )?;
         Ok(())
     }
 
     pub fn send_reset_password_email(
         &self,
+        conn: &RepoConnection,
         user_email: String,
         username: String,
         token: String,
     ) -> Result<(), EmailError> {
-        let email = Email::builder()
-            // Addresses can be specified by the tuple (email, alias)
-            .to((user_email, username.clone()))
-            // ... or by an address only
-            .from(self.from_address.clone())
-            .subject(format!(
-                "Hi {}, a password reset was request on your behalf",
-                username
-            ))
-            .alternative(
-                self.get_reset_password_email_text(username.clone(), token.clone()),
-                self.get_reset_password_email_html(username, token),
-            )
-            .build()?;
-
-        let _ = self.send_email(email.into())?;
+        let mut context = Context::new();
+        context.insert("display_name", &username);
+        context.insert("token", &token);
+        self.send_templated_email(
+            conn,
+            EmailTemplate::ResetPassword,
+            None,
+            user_email,
+            Some(username),
+            context,
+        )?;
         Ok(())
     }
 
-    fn get_verification_email_text(&self, username: String, token: String) -> String {
+    pub fn send_email_change_confirmation_email(
+        &self,
+        conn: &RepoConnection,
+        new_email: String,
+        username: String,
+        token: String,
+    ) -> Result<(), EmailError> {
         let mut context = Context::new();
         context.insert("display_name", &username);
+        context.insert("new_email", &new_email);
         context.insert("token", &token);
-        context.insert("website_url", &self.website_url);
-        match TEMPLATES.render("emails/verify_email/verify_email_html.html", &context) {
-            Ok(s) => s,
-            Err(e) => {
-                println!("Error: {}", e);
-                let mut cause = e.source();
-                while let Some(e) = cause {
-                    println!("Reason: {}", e);
-                    cause = e.source();
-                }
-                String::from("Error rendering email")
-            }
-        }
+        self.send_templated_email(
+            conn,
+            EmailTemplate::ChangeEmailConfirmation,
+            None,
+            new_email,
+            Some(username),
+            context,
+        )?;
+        Ok(())
     }
 
-    fn get_verification_email_html(&self, username: String, token: String) -> String {
+    pub fn send_email_change_notice_email(
+        &self,
+        conn: &RepoConnection,
+        old_email: String,
+        new_email: String,
+        username: String,
+        token: String,
+    ) -> Result<(), EmailError> {
         let mut context = Context::new();
         context.insert("display_name", &username);
+        context.insert("new_email", &new_email);
         context.insert("token", &token);
-        context.insert("website_url", &self.website_url);
-        match TEMPLATES.render("emails/verify_email/verify_email_text.html", &context) {
-            Ok(s) => s,
-            Err(e) => {
-                println!("Error: {}", e);
-                let mut cause = e.source();
-                while let Some(e) = cause {
-                    println!("Reason: {}", e);
-                    cause = e.source();
-                }
-                String::from("Error rendering email")
-            }
-        }
+        self.send_templated_email(
+            conn,
+            EmailTemplate::ChangeEmailNotice,
+            None,
+            old_email,
+            Some(username),
+            context,
+        )?;
+        Ok(())
     }
 
-    fn get_reset_password_email_text(&self, username: String, token: String) -> String {
+    pub fn send_comment_reply_email(
+        &self,
+        conn: &RepoConnection,
+        recipient: &User,
+        replier_name: String,
+        post: &BlogPost,
+        reply_body: String,
+    ) -> Result<(), EmailError> {
         let mut context = Context::new();
-        context.insert("display_name", &username);
-        context.insert("token", &token);
-        context.insert("website_url", &self.website_url);
-        match TEMPLATES.render("emails/reset_password/reset_password_html.html", &context) {
-            Ok(s) => s,
-            Err(e) => {
-                println!("Error: {}", e);
-                let mut cause = e.source();
-                while let Some(e) = cause {
-                    println!("Reason: {}", e);
-                    cause = e.source();
-                }
-                String::from("Error rendering email")
-            }
-        }
+        context.insert("replier_name", &replier_name);
+        context.insert("post_title", &post.title);
+        context.insert("post_slug", &post.slug);
+        context.insert("reply_body", &reply_body);
+        self.send_notification_email(
+            conn,
+            EmailTemplate::CommentReply,
+            NotificationKind::CommentReplies,
+            recipient,
+            context,
+        )?;
+        Ok(())
     }
 
-    fn get_reset_password_email_html(&self, username: String, token: String) -> String {
+    pub fn send_comment_moderation_email(
+        &self,
+        conn: &RepoConnection,
+        recipient: &User,
+        post: &BlogPost,
+        decision: ModerationDecision,
+        comment_body: String,
+    ) -> Result<(), EmailError> {
         let mut context = Context::new();
-        context.insert("display_name", &username);
-        context.insert("token", &token);
-        context.insert("website_url", &self.website_url);
-        match TEMPLATES.render("emails/reset_password/reset_password_text.html", &context) {
-            Ok(s) => s,
-            Err(e) => {
-                println!("Error: {}", e);
-                let mut cause = e.source();
-                while let Some(e) = cause {
-                    println!("Reason: {}", e);
-                    cause = e.source();
-                }
-                String::from("Error rendering email")
-            }
+        context.insert("decision", decision.as_str());
+        context.insert("post_title", &post.title);
+        context.insert("post_slug", &post.slug);
+        context.insert("comment_body", &comment_body);
+        self.send_notification_email(
+            conn,
+            EmailTemplate::CommentModeration,
+            NotificationKind::ModerationDecisions,
+            recipient,
+            context,
+        )?;
+        Ok(())
+    }
+
+    pub fn send_new_posts_digest_email(
+        &self,
+        conn: &RepoConnection,
+        recipient: &User,
+        posts: &[BlogPost],
+    ) -> Result<(), EmailError> {
+        let mut context = Context::new();
+        context.insert("posts", &digest_posts(posts));
+        self.send_notification_email(
+            conn,
+            EmailTemplate::NewPostsDigest,
+            NotificationKind::NewPostsDigest,
+            recipient,
+            context,
+        )?;
+        Ok(())
+    }
+
+    pub fn send_newsletter_confirmation_email(
+        &self,
+        conn: &RepoConnection,
+        email: String,
+        token: String,
+    ) -> Result<(), EmailError> {
+        let mut context = Context::new();
+        context.insert("email", &email);
+        context.insert("confirm_url", &self.newsletter_confirm_url(&token));
+        self.send_templated_email(
+            conn,
+            EmailTemplate::NewsletterConfirmation,
+            None,
+            email,
+            None,
+            context,
+        )?;
+        Ok(())
+    }
+
+    pub fn send_newsletter_issue_email(
+        &self,
+        conn: &RepoConnection,
+        email: String,
+        token: &str,
+        issue: &NewsletterIssue,
+        posts: &[BlogPost],
+    ) -> Result<OutboxEmail, EmailError> {
+        let mut context = Context::new();
+        context.insert("subject", &issue.subject);
+        context.insert("intro", &issue.intro);
+        context.insert("posts", &digest_posts(posts));
+        self.queue_unsubscribable_email(
+            conn,
+            EmailTemplate::NewsletterIssue,
+            email,
+            None,
+            self.newsletter_unsubscribe_url(token),
+            context,
+        )
+    }
+}
+
+/// What a moderator did to a comment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModerationDecision {
+    Edited,
+    Removed,
+}
+
+impl ModerationDecision {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ModerationDecision::Edited => "edited",
+            ModerationDecision::Removed => "removed",
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct DigestPost<'a> {
+    title: &'a str,
+    slug: &'a str,
+}
+
+fn digest_posts(posts: &[BlogPost]) -> Vec<DigestPost<'_>> {
+    posts
+        .iter()
+        .map(|post| DigestPost {
+            title: &post.title,
+            slug: &post.slug,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sender() -> EmailSender {
+        EmailSender {
+            api_url: "https://backend.axmouth.dev".to_string(),
+            ..Default::default()
         }
     }
+
+    #[test]
+    fn notification_unsubscribe_links_reach_the_api_routes() {
+        assert_eq!(
+            sender().notifications_unsubscribe_url("a b"),
+            "https://backend.axmouth.dev/api/v1/notifications/unsubscribe?token=a%20b"
+        );
+    }
+
+    #[test]
+    fn newsletter_confirmation_links_reach_the_api_routes() {
+        assert_eq!(
+            sender().newsletter_confirm_url("abc"),
+            "https://backend.axmouth.dev/api/v1/newsletter/confirm?token=abc"
+        );
+    }
+
+    #[test]
+    fn newsletter_unsubscribe_links_reach_the_api_routes() {
+        assert_eq!(
+            sender().newsletter_unsubscribe_url("abc"),
+            "https://backend.axmouth.dev/api/v1/newsletter/unsubscribe?token=abc"
+        );
+    }
 }
@@ -129,7 +129,16 @@ impl IntoResponse for AppError {
 pub enum AuthError {
     ExpiredAuthentication(ExpiredAuthentication),
     InvalidJWT(InvalidJWT),
+    InvalidApiToken(&'static str),
+    InsufficientScope,
     InsufficientPriviledge,
+    PgRepoError(PgRepoError),
+}
+
+impl From<PgRepoError> for AuthError {
+    fn from(err: PgRepoError) -> Self {
+        AuthError::PgRepoError(err)
+    }
 }
 
 impl IntoResponse for AuthError {
@@ -146,10 +155,22 @@ impl IntoResponse for AuthError {
                 message = format!("Authentication: {}", err.get_err());
                 code = StatusCode::UNAUTHORIZED;
             }
+            AuthError::InvalidApiToken(reason) => {
+                message = format!("Authentication: {}", reason);
+                code = StatusCode::UNAUTHORIZED;
+            }
+            AuthError::InsufficientScope => {
+                message = "Authentication: This api token is not allowed to do this".to_string();
+                code = StatusCode::FORBIDDEN;
+            }
             AuthError::InsufficientPriviledge => {
                 message = "Authentication: You are not authorized to do this".to_string();
                 code = StatusCode::UNAUTHORIZED;
             }
+            AuthError::PgRepoError(err) => {
+                message = format!("Authentication: {}", err);
+                code = StatusCode::INTERNAL_SERVER_ERROR;
+            }
         }
 
         let response_body = &BaseResponse::<()> {
@@ -210,6 +231,12 @@ pub struct EmailError {
     err: String,
 }
 
+impl EmailError {
+    pub fn new(err: String) -> Self {
+        EmailError { err }
+    }
+}
+
 impl ToString for EmailError {
     fn to_string(&self) -> String {
         self.err.clone()
@@ -232,6 +259,14 @@ impl From<lettre::smtp::error::Error> for EmailError {
     }
 }
 
+impl From<PgRepoError> for EmailError {
+    fn from(error: PgRepoError) -> EmailError {
+        EmailError {
+            err: error.to_string(),
+        }
+    }
+}
+
 impl From<native_tls::Error> for EmailError {
     fn from(error: native_tls::Error) -> EmailError {
         EmailError {
@@ -336,6 +371,14 @@ impl From<hyper::Error> for GeolocError {
     }
 }
 
+impl From<std::io::Error> for GeolocError {
+    fn from(error: std::io::Error) -> GeolocError {
+        GeolocError {
+            err_message: error.to_string(),
+        }
+    }
+}
+
 impl From<serde_json::Error> for GeolocError {
     fn from(error: serde_json::Error) -> GeolocError {
         GeolocError {
@@ -343,3 +386,11 @@ impl From<serde_json::Error> for GeolocError {
         }
     }
 }
+
+impl From<maxminddb::MaxMindDBError> for GeolocError {
+    fn from(error: maxminddb::MaxMindDBError) -> GeolocError {
+        GeolocError {
+            err_message: error.to_string(),
+        }
+    }
+}
@@ -2,12 +2,21 @@ use axum::{
     async_trait,
     body::BoxBody,
     extract::{
-        rejection::TypedHeaderRejectionReason, Form, FromRequest, Query, RequestParts, TypedHeader,
+        rejection::TypedHeaderRejectionReason, Extension, Form, FromRequest, Query, RequestParts,
+        TypedHeader,
     },
-    http::Response,
+    http::{Method, Response},
     response::IntoResponse,
     BoxError, Json,
 };
+use backend_repo_pg::{
+    api_tokens::ApiTokenRepo,
+    extra::ApiTokenScope,
+    models::domain::ApiToken,
+    pg_util::{pg_transaction, DynRepo},
+    users::UserRepo,
+};
+use chrono::{NaiveDateTime, Utc};
 use headers::{authorization::Bearer, Authorization};
 use jsonwebtoken::{decode, Validation};
 use serde::de::DeserializeOwned;
@@ -17,9 +26,10 @@ use validator::Validate;
 
 use crate::{
     app::KEYS,
-    auth_tokens::Claims,
+    auth_tokens::{hash_api_token, Claims, API_TOKEN_PREFIX, SESSION_AUDIENCES},
     errors::AuthError,
     filters::{validation_errors_to_msg, InvalidJWT},
+    routes::API_PREFIX,
     util::{bad_request_response, bad_request_response_many},
 };
 
@@ -55,10 +65,7 @@ where
         TypedHeader::<Authorization<Bearer>>::from_request(req)
             .await
             .map_err(|e| AuthError::InvalidJWT(InvalidJWT::new(e.to_string())))?;
-    // Decode the user data
-    decode::<Claims>(bearer.token(), &KEYS.decoding, &Validation::default())
-        .map_err(|e| AuthError::InvalidJWT(InvalidJWT::new(e.to_string())))
-        .map(|data| data.claims)
+    get_bearer_claims(req, bearer).await
 }
 
 async fn get_claims_opt<B>(req: &mut RequestParts<B>) -> Result<Option<Claims>, AuthError>
@@ -73,10 +80,107 @@ where
         },
     }
     .map_err(|e| AuthError::InvalidJWT(InvalidJWT::new(e.to_string())))?;
+    get_bearer_claims(req, bearer).await.map(Some)
+}
+
+async fn get_bearer_claims<B>(
+    req: &mut RequestParts<B>,
+    bearer: Bearer,
+) -> Result<Claims, AuthError>
+where
+    B: Send,
+{
+    if bearer.token().starts_with(API_TOKEN_PREFIX) {
+        return get_api_token_claims(req, bearer.token()).await;
+    }
     // Decode the user data
-    decode::<Claims>(bearer.token(), &KEYS.decoding, &Validation::default())
+    let mut validation = Validation::default();
+    validation.set_audience(&SESSION_AUDIENCES);
+    decode::<Claims>(bearer.token(), &KEYS.decoding, &validation)
         .map_err(|e| AuthError::InvalidJWT(InvalidJWT::new(e.to_string())))
-        .map(|data| Some(data.claims))
+        .map(|data| data.claims)
+}
+
+async fn get_api_token_claims<B>(
+    req: &mut RequestParts<B>,
+    token: &str,
+) -> Result<Claims, AuthError>
+where
+    B: Send,
+{
+    let scope = required_scope(req.method(), req.uri().path());
+    let Extension(repo) = Extension::<DynRepo>::from_request(req)
+        .await
+        .map_err(|_| AuthError::InvalidApiToken("Api tokens are not accepted here"))?;
+    let token_hash = hash_api_token(token);
+    let claims = pg_transaction(repo, |conn| {
+        let api_token_repository = ApiTokenRepo::new(conn);
+        let api_token = match api_token_repository.find_one_by_hash(&token_hash)? {
+            Some(value) => value,
+            None => return Ok(Err(AuthError::InvalidApiToken("Invalid api token"))),
+        };
+        if let Err(err) = check_api_token(&api_token, scope, Utc::now().naive_utc()) {
+            return Ok(Err(err));
+        }
+        let user_repository = UserRepo::new(conn);
+        let user = match user_repository.find_one(api_token.user_id)? {
+            Some(value) => value,
+            None => return Ok(Err(AuthError::InvalidApiToken("Invalid api token"))),
+        };
+        api_token_repository.touch(api_token.id)?;
+        Ok(Ok(Claims::new_api_token(
+            user.id,
+            user.role,
+            user.display_name,
+            api_token.scopes,
+            api_token.expires_at,
+        )))
+    })
+    .await??;
+    Ok(claims)
+}
+
+/// Checks that a token can be used at all before whether it may be used for this
+/// route, so a bad token is told apart from a good one lacking a scope.
+fn check_api_token(
+    api_token: &ApiToken,
+    scope: Option<ApiTokenScope>,
+    now: NaiveDateTime,
+) -> Result<(), AuthError> {
+    if api_token.revoked {
+        return Err(AuthError::InvalidApiToken("This api token is revoked"));
+    }
+    if api_token.is_expired(now) {
+        return Err(AuthError::InvalidApiToken("This api token is expired"));
+    }
+    match scope {
+        Some(scope) if api_token.has_scope(scope) => Ok(()),
+        _ => Err(AuthError::InsufficientScope),
+    }
+}
+
+/// Maps a request to the scope an api token needs for it. Routes that are not
+/// listed here can only be used with a login session.
+fn required_scope(method: &Method, path: &str) -> Option<ApiTokenScope> {
+    let path = path.trim_start_matches(API_PREFIX);
+    let resource = path
+        .trim_start_matches('/')
+        .split('/')
+        .next()
+        .unwrap_or_default();
+    let read = method == Method::GET || method == Method::HEAD;
+    match (resource, read) {
+        ("blog-posts" | "categories", true) => Some(ApiTokenScope::PostsRead),
+        ("blog-posts" | "categories", false) => Some(ApiTokenScope::PostsWrite),
+        ("projects" | "technologies", true) => Some(ApiTokenScope::ProjectsRead),
+        ("projects" | "technologies", false) => Some(ApiTokenScope::ProjectsWrite),
+        ("text-bodies" | "links", true) => Some(ApiTokenScope::PagesRead),
+        ("text-bodies" | "links", false) => Some(ApiTokenScope::PagesWrite),
+        ("blog-post-comments", true) => Some(ApiTokenScope::CommentsRead),
+        ("blog-post-comments", false) => Some(ApiTokenScope::CommentsWrite),
+        ("files", false) => Some(ApiTokenScope::MediaUpload),
+        _ => None,
+    }
 }
 
 #[async_trait]
@@ -226,3 +330,96 @@ impl IntoResponse for ServerError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backend_repo_pg::extra::UserRole;
+    use chrono::Duration;
+
+    fn api_token(scopes: Vec<ApiTokenScope>) -> ApiToken {
+        ApiToken {
+            id: 1,
+            user_id: 1,
+            name: String::from("Deploys"),
+            token_prefix: String::from("axd_1234"),
+            scopes,
+            revoked: false,
+            created_at: Utc::now().naive_utc(),
+            expires_at: None,
+            last_used_at: None,
+        }
+    }
+
+    #[test]
+    fn routes_need_the_scope_of_their_resource() {
+        assert_eq!(
+            required_scope(&Method::GET, "/api/v1/blog-posts/1"),
+            Some(ApiTokenScope::PostsRead)
+        );
+        assert_eq!(
+            required_scope(&Method::HEAD, "/api/v1/categories"),
+            Some(ApiTokenScope::PostsRead)
+        );
+        assert_eq!(
+            required_scope(&Method::PUT, "/api/v1/projects/1"),
+            Some(ApiTokenScope::ProjectsWrite)
+        );
+        assert_eq!(
+            required_scope(&Method::DELETE, "/api/v1/links/3"),
+            Some(ApiTokenScope::PagesWrite)
+        );
+        assert_eq!(
+            required_scope(&Method::GET, "/api/v1/blog-post-comments"),
+            Some(ApiTokenScope::CommentsRead)
+        );
+        assert_eq!(
+            required_scope(&Method::POST, "/api/v1/files/upload"),
+            Some(ApiTokenScope::MediaUpload)
+        );
+        assert_eq!(required_scope(&Method::GET, "/api/v1/files/upload"), None);
+        assert_eq!(required_scope(&Method::GET, "/api/v1/auth/profile"), None);
+        assert_eq!(required_scope(&Method::GET, "/api/v1/blog-postsx"), None);
+    }
+
+    #[test]
+    fn tokens_have_only_their_own_scopes() {
+        let token = api_token(vec![ApiTokenScope::PostsRead]);
+        assert!(token.has_scope(ApiTokenScope::PostsRead));
+        assert!(!token.has_scope(ApiTokenScope::PostsWrite));
+        let claims = Claims::new_api_token(1, UserRole::Admin, String::new(), token.scopes, None);
+        assert!(claims.has_scope(ApiTokenScope::PostsRead));
+        assert!(!claims.has_scope(ApiTokenScope::PostsWrite));
+    }
+
+    #[test]
+    fn bad_tokens_are_rejected_before_scopes_are_checked() {
+        let now = Utc::now().naive_utc();
+        let scope = Some(ApiTokenScope::PostsWrite);
+        let mut revoked = api_token(vec![]);
+        revoked.revoked = true;
+        let mut expired = api_token(vec![ApiTokenScope::PostsWrite]);
+        expired.expires_at = Some(now - Duration::minutes(1));
+        for token in [revoked, expired] {
+            assert!(matches!(
+                check_api_token(&token, scope, now),
+                Err(AuthError::InvalidApiToken(_))
+            ));
+            assert!(matches!(
+                check_api_token(&token, None, now),
+                Err(AuthError::InvalidApiToken(_))
+            ));
+        }
+
+        let token = api_token(vec![ApiTokenScope::PostsRead]);
+        assert!(matches!(
+            check_api_token(&token, scope, now),
+            Err(AuthError::InsufficientScope)
+        ));
+        assert!(matches!(
+            check_api_token(&token, None, now),
+            Err(AuthError::InsufficientScope)
+        ));
+        assert!(check_api_token(&token, Some(ApiTokenScope::PostsRead), now).is_ok());
+    }
+}
@@ -0,0 +1,109 @@
+use std::fs;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
+
+use crate::app::Geolocation;
+use crate::errors::GeolocError;
+use maxminddb::{geoip2, MaxMindDBError, Reader};
+
+/// Where an address is, as far as the database knows.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Location {
+    pub country_code: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+}
+
+impl Location {
+    /// Reads a record in the GeoLite2 and DB-IP city or country layout.
+    fn from(record: &geoip2::City) -> Self {
+        let country = record
+            .country
+            .as_ref()
+            .or(record.registered_country.as_ref());
+        let location = record.location.as_ref();
+        Self {
+            country_code: country
+                .and_then(|country| country.iso_code)
+                .map(String::from),
+            latitude: location.and_then(|location| location.latitude),
+            longitude: location.and_then(|location| location.longitude),
+        }
+    }
+}
+
+/// Finds nothing, for when no database is configured.
+pub struct NullGeolocation;
+
+impl Geolocation for NullGeolocation {
+    fn lookup(&self, _address: IpAddr) -> Option<Location> {
+        None
+    }
+}
+
+struct LoadedDatabase {
+    reader: Arc<Reader<Vec<u8>>>,
+    modified: Option<SystemTime>,
+}
+
+/// Looks addresses up in a MaxMind DB file kept in memory, such as GeoLite2 City or
+/// DB-IP Lite City.
+pub struct MmdbGeolocation {
+    path: PathBuf,
+    database: RwLock<Option<LoadedDatabase>>,
+}
+
+impl MmdbGeolocation {
+    /// Loads the database at `path`. If that fails, lookups find nothing until a reload
+    /// succeeds.
+    pub fn open<P: Into<PathBuf>>(path: P) -> Self {
+        let geolocation = Self {
+            path: path.into(),
+            database: RwLock::new(None),
+        };
+        // Errors are reported by the reload worker, once logging is set up
+        let _ = geolocation.reload();
+        geolocation
+    }
+}
+
+impl Geolocation for MmdbGeolocation {
+    fn lookup(&self, address: IpAddr) -> Option<Location> {
+        let reader = match self.database.read() {
+            Ok(database) => database.as_ref()?.reader.clone(),
+            Err(_) => return None,
+        };
+        match reader.lookup::<geoip2::City>(address) {
+            Ok(record) => Some(Location::from(&record)),
+            Err(MaxMindDBError::AddressNotFoundError(_)) => None,
+            Err(err) => {
+                tracing::debug!("Geolocation lookup failed: {}", err);
+                None
+            }
+        }
+    }
+
+    fn reload(&self) -> Result<bool, GeolocError> {
+        let modified = fs::metadata(&self.path)?.modified().ok();
+        let loaded_modified = match self.database.read() {
+            Ok(database) => database.as_ref().and_then(|loaded| loaded.modified),
+            Err(_) => None,
+        };
+        if modified.is_some() && modified == loaded_modified {
+            return Ok(false);
+        }
+        // Lookups keep using the old database while the new one is read
+        let reader = Reader::from_source(fs::read(&self.path)?)?;
+        let mut database = self
+            .database
+            .write()
+            .map_err(|_| GeolocError::new("Geolocation database lock is poisoned"))?;
+        *database = Some(LoadedDatabase {
+            reader: Arc::new(reader),
+            modified,
+        });
+        Ok(true)
+    }
+}
@@ -0,0 +1,28 @@
+use crate::app::DynGeolocation;
+use tokio::task::block_in_place;
+
+const RELOAD_INTERVAL_SECS: u64 = 60;
+
+/// Picks up a replaced geolocation database until the process stops. A failure is logged
+/// once until it changes or a reload succeeds, so a missing file doesn't flood the log.
+pub async fn run(geolocation: DynGeolocation) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(RELOAD_INTERVAL_SECS));
+    let mut last_error: Option<String> = None;
+    loop {
+        interval.tick().await;
+        match block_in_place(|| geolocation.reload()) {
+            Ok(true) => {
+                last_error = None;
+                tracing::info!("Loaded geolocation database")
+            }
+            Ok(false) => last_error = None,
+            Err(err) => {
+                let err = err.get_err();
+                if last_error.as_ref() != Some(&err) {
+                    tracing::error!("Geolocation database reload failed: {}", err);
+                    last_error = Some(err);
+                }
+            }
+        }
+    }
+}
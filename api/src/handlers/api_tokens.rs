@@ -0,0 +1,118 @@
+use crate::auth_tokens::{generate_api_token, hash_api_token};
+use crate::errors::AppError;
+use crate::extractors::{ClaimsContext, ValidatedJson};
+use crate::util::{
+    not_found_response, paginated_ok_response, simple_created_response, simple_no_content_response,
+    simple_ok_response, unauthorized_response,
+};
+use axum::extract::{Extension, Path};
+use axum::response::IntoResponse;
+use backend_repo_pg::api_tokens::ApiTokenRepo;
+use backend_repo_pg::errors::PgRepoError;
+use backend_repo_pg::filters::GetAllApiTokensFilter;
+use backend_repo_pg::insertables::NewApiToken;
+use backend_repo_pg::models::queries::{GetAllApiTokensQuery, PaginatedQuery};
+use backend_repo_pg::models::requests::CreateApiTokenRequest;
+use backend_repo_pg::models::responses::CreatedApiTokenResponse;
+use backend_repo_pg::pg_util::{pg_transaction, DynRepo};
+use chrono::{Duration, Utc};
+use tokio::task::block_in_place;
+
+pub async fn get(
+    Path(id): Path<i32>,
+    ClaimsContext { claims }: ClaimsContext,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    block_in_place(|| {
+        let conn = repo.get_conn()?;
+        let api_token_repository = ApiTokenRepo::new(&conn);
+        let api_token = match api_token_repository
+            .find_one(id)
+            .map_err::<PgRepoError, _>(|e| e.into())?
+        {
+            Some(value) if value.user_id == claims.user_id() => value,
+            _ => {
+                return Ok(not_found_response("Api Token"));
+            }
+        };
+        Ok(simple_ok_response(api_token))
+    })
+}
+
+pub async fn get_all(
+    query: GetAllApiTokensQuery,
+    ClaimsContext { claims }: ClaimsContext,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    block_in_place(|| {
+        let conn = repo.get_conn()?;
+        let mut filter = GetAllApiTokensFilter::from_query(query.clone());
+        filter.user_id = Some(claims.user_id());
+        let api_token_repository = ApiTokenRepo::new(&conn);
+        let pagination_opts = query.pagination_options();
+        let sort_type = query.sort_type;
+        let (api_tokens_list, total_results) = api_token_repository
+            .find(filter, sort_type, pagination_opts)
+            .map_err::<PgRepoError, _>(|e| e.into())?;
+        Ok(paginated_ok_response(
+            api_tokens_list,
+            query.page,
+            query.page_size,
+            total_results,
+        ))
+    })
+}
+
+pub async fn create(
+    ClaimsContext { claims }: ClaimsContext,
+    ValidatedJson(request): ValidatedJson<CreateApiTokenRequest>,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    if !claims.is_verified() {
+        return Ok(unauthorized_response("Api Token"));
+    }
+    let token = generate_api_token();
+    let new_api_token = NewApiToken {
+        user_id: claims.user_id(),
+        name: request.name,
+        token_prefix: token.chars().take(12).collect(),
+        token_hash: hash_api_token(&token),
+        scopes: request
+            .scopes
+            .iter()
+            .map(|scope| scope.as_str().to_string())
+            .collect(),
+        expires_at: request
+            .expires_in_days
+            .map(|days| Utc::now().naive_utc() + Duration::days(days)),
+    };
+    Ok(pg_transaction(repo, |conn| {
+        let api_token_repository = ApiTokenRepo::new(conn);
+        let api_token = api_token_repository.insert_one(new_api_token)?;
+        // The plain token is only ever shown once, right after creation.
+        Ok(simple_created_response(CreatedApiTokenResponse {
+            token,
+            api_token,
+        }))
+    })
+    .await?)
+}
+
+pub async fn revoke(
+    Path(id): Path<i32>,
+    ClaimsContext { claims }: ClaimsContext,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    Ok(pg_transaction(repo, |conn| {
+        let api_token_repository = ApiTokenRepo::new(conn);
+        match api_token_repository.find_one(id)? {
+            Some(value) if value.user_id == claims.user_id() => value,
+            _ => {
+                return Ok(not_found_response("Api Token"));
+            }
+        };
+        let api_token = api_token_repository.revoke(id)?;
+        Ok(simple_no_content_response(api_token))
+    })
+    .await?)
+}
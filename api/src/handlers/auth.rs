@@ -1,4 +1,4 @@
-use crate::app::{DynEmailSender, DynJwtDuration, DynJwtSecret};
+use crate::app::{DynCaptchaVerifier, DynEmailSender, DynJwtDuration, DynJwtSecret};
 use crate::errors::AppError;
 use crate::extractors::{ClaimsContext, ValidatedJson};
 use crate::util::{
@@ -8,10 +8,25 @@ use crate::util::{
 };
 use crate::{auth_tokens, util::simple_error_response};
 use crate::{auth_tokens::decode_token, util::simple_ok_response};
+use axum::body::BoxBody;
 use axum::extract::Extension;
+use axum::http::Response;
 use axum::response::IntoResponse;
 use axum::Json;
-use backend_repo_pg::pg_util::{get_roll_back_err, pg_transaction, DynRepo};
+use backend_repo_pg::blog_comments::BlogPostCommentRepo;
+use backend_repo_pg::blog_post_comment_ratings::BlogPostCommentRatingRepo;
+use backend_repo_pg::filters::{GetAllBlogPostCommentRatingsFilter, GetAllBlogPostCommentsFilter};
+use backend_repo_pg::models::domain;
+use backend_repo_pg::models::requests::{
+    ChangeEmailRequest, ChangePasswordRequest, DeleteAccountRequest, UndoEmailChangeRequest,
+    UpdateProfileRequest,
+};
+use backend_repo_pg::models::responses::UserDataExportResponse;
+use backend_repo_pg::options::PaginationOptions;
+use backend_repo_pg::pg_util::{
+    get_roll_back_err, pg_transaction, DynRepo, QueryResult, RepoConnection,
+};
+use backend_repo_pg::users::TOMBSTONE_DISPLAY_NAME;
 use backend_repo_pg::{
     change_password_tokens::ChangePasswordTokenRepo, change_sets::UpdateChangePasswordToken,
     change_sets::UpdateUser, change_sets::UpdateVerifyEmailToken, errors::PgRepoError,
@@ -149,7 +164,9 @@ pub async fn register(
     Extension(jwt_secret): Extension<DynJwtSecret>,
     Extension(jwt_duration): Extension<DynJwtDuration>,
     Extension(email_sender): Extension<DynEmailSender>,
+    Extension(captcha_verifier): Extension<DynCaptchaVerifier>,
 ) -> Result<impl IntoResponse, AppError> {
+    captcha_verifier.verify(&request.captcha_token).await?;
     Ok(pg_transaction(repo, |conn| {
         let user_repository = UserRepo::new(conn);
         if user_repository
@@ -203,6 +220,7 @@ pub async fn register(
             request.email.clone(),
             None,
             user_result.id,
+            false,
         ) {
             Ok(v) => v,
             Err(_) => {
@@ -210,6 +228,7 @@ pub async fn register(
             }
         };
         match email_sender.email_sender().send_email_verification_email(
+            conn,
             request.email,
             request.display_name,
             token,
@@ -409,25 +428,9 @@ pub async fn request_verification_email(
     Extension(email_sender): Extension<DynEmailSender>,
 ) -> Result<impl IntoResponse, AppError> {
     Ok(pg_transaction(repo, |conn| {
-        let user_id: i32;
-        let old_email: Option<String>;
-        let display_name: String;
-
         let user_repository = UserRepo::new(conn);
-        let mut email: String = match user_repository.find_one(claims.user_id())? {
-            Some(user) => match user.email {
-                Some(value) => {
-                    user_id = user.id;
-                    display_name = user.display_name;
-                    value
-                }
-                None => {
-                    return Ok(simple_error_response(
-                        String::from("This should not happen, but couldn't find email."),
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                    ));
-                }
-            },
+        let user = match user_repository.find_one(claims.user_id())? {
+            Some(value) => value,
             None => {
                 return Ok(simple_error_response(
                     String::from("Invalid User Id in JWT."),
@@ -436,28 +439,37 @@ pub async fn request_verification_email(
             }
         };
         if let Some(new_email) = request.email {
-            old_email = Some(email);
-            email = new_email;
-        } else {
-            old_email = None;
+            return start_email_change(conn, &email_sender, user, new_email);
         }
+        let email = match user.email {
+            Some(value) => value,
+            None => {
+                return Ok(simple_error_response(
+                    String::from("This should not happen, but couldn't find email."),
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                ));
+            }
+        };
 
         let verify_email_tokens_repository = VerifyEmailTokenRepo::new(conn);
         let token: String = match create_verify_email_token(
             verify_email_tokens_repository,
             email.clone(),
-            old_email,
-            user_id,
+            None,
+            user.id,
+            false,
         ) {
             Ok(value) => value,
             Err(err) => {
                 return Ok(server_error_response(err));
             }
         };
-        match email_sender
-            .email_sender()
-            .send_email_verification_email(email, display_name, token)
-        {
+        match email_sender.email_sender().send_email_verification_email(
+            conn,
+            email,
+            user.display_name,
+            token,
+        ) {
             Ok(_) => {}
             Err(_) => {
                 return Err(get_roll_back_err());
@@ -469,15 +481,111 @@ pub async fn request_verification_email(
     .await?)
 }
 
-pub async fn verify_email(
-    ValidatedJson(request): ValidatedJson<VerifyEmailRequest>,
+pub async fn change_email(
+    ClaimsContext { claims }: ClaimsContext,
+    ValidatedJson(request): ValidatedJson<ChangeEmailRequest>,
+    Extension(repo): Extension<DynRepo>,
+    Extension(email_sender): Extension<DynEmailSender>,
+) -> Result<impl IntoResponse, AppError> {
+    Ok(pg_transaction(repo, |conn| {
+        let user_repository = UserRepo::new(conn);
+        let user = match user_repository.find_one(claims.user_id())? {
+            Some(value) => value,
+            None => {
+                return Ok(simple_error_response(
+                    String::from("Invalid User Id in JWT."),
+                    StatusCode::BAD_REQUEST,
+                ));
+            }
+        };
+        start_email_change(conn, &email_sender, user, request.email)
+    })
+    .await?)
+}
+
+pub async fn undo_email_change(
+    ValidatedJson(request): ValidatedJson<UndoEmailChangeRequest>,
     Extension(repo): Extension<DynRepo>,
 ) -> Result<impl IntoResponse, AppError> {
     Ok(pg_transaction(repo, |conn| {
         let verify_email_tokens_repository = VerifyEmailTokenRepo::new(conn);
         let token_data = match verify_email_tokens_repository.find_one_by_token(request.token)? {
+            Some(value) if value.undo => value,
+            _ => {
+                return Ok(bad_request_response("Invalid Token"));
+            }
+        };
+        if token_data.used {
+            return Ok(simple_error_response(
+                String::from("This token is already used"),
+                StatusCode::CONFLICT,
+            ));
+        } else if token_data.invalidated {
+            return Ok(simple_error_response(
+                String::from("This token is invalidated"),
+                StatusCode::CONFLICT,
+            ));
+        } else if token_data.expires_at <= Utc::now().naive_utc() {
+            return Ok(simple_error_response(
+                String::from("This token is expired"),
+                StatusCode::CONFLICT,
+            ));
+        }
+        let user_repository = UserRepo::new(conn);
+        let user = match user_repository.find_one(token_data.user_id)? {
             Some(value) => value,
             None => {
+                return Ok(bad_request_response(
+                    "Invalid Token Data, couldn't find User",
+                ));
+            }
+        };
+        verify_email_tokens_repository.invalidate_pending_changes(user.id)?;
+        // If the change was already confirmed, switch back to the old address
+        if user.email.as_ref() != Some(&token_data.email) {
+            if user.email != token_data.old_email {
+                return Ok(simple_error_response(
+                    String::from("The E-Mail Address was changed again since this token was sent"),
+                    StatusCode::CONFLICT,
+                ));
+            }
+            if user_repository
+                .find_one_by_email(token_data.email.clone())?
+                .is_some()
+            {
+                return Ok(simple_error_response(
+                    String::from("This E-Mail Address is already in use"),
+                    StatusCode::CONFLICT,
+                ));
+            }
+            let updated_user = UpdateUser {
+                display_name: None,
+                email: Some(token_data.email.clone()),
+                password: None,
+                role: None,
+                updated_at: Some(Some(Utc::now().naive_utc())),
+            };
+            user_repository.update_one(user.id, updated_user)?;
+        }
+        let updated_token = UpdateVerifyEmailToken {
+            invalidated: None,
+            used: Some(true),
+        };
+        verify_email_tokens_repository.update_one(token_data.id, updated_token)?;
+        Ok(simple_ok_response(()))
+    })
+    .await?)
+}
+
+pub async fn verify_email(
+    ValidatedJson(request): ValidatedJson<VerifyEmailRequest>,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    Ok(pg_transaction(repo, |conn| {
+        let verify_email_tokens_repository = VerifyEmailTokenRepo::new(conn);
+        let token_data = match verify_email_tokens_repository.find_one_by_token(request.token)? {
+            Some(value) if !value.undo => value,
+            _ => {
                 return Ok(bad_request_response("Invalid Token"));
             }
         };
@@ -513,6 +621,25 @@ pub async fn verify_email(
             role: None,
             updated_at: Some(Some(Utc::now().naive_utc())),
         };
+        // Tokens with an old email confirm an email change
+        if token_data.old_email.is_some() {
+            if user.email != token_data.old_email {
+                return Ok(simple_error_response(
+                    String::from("The E-Mail Address was changed since this token was sent"),
+                    StatusCode::CONFLICT,
+                ));
+            }
+            if user_repository
+                .find_one_by_email(token_data.email.clone())?
+                .is_some()
+            {
+                return Ok(simple_error_response(
+                    String::from("This E-Mail Address is already in use"),
+                    StatusCode::CONFLICT,
+                ));
+            }
+            updated_user.email = Some(token_data.email.clone());
+        }
         if user.role == UserRole::Ghost {
             updated_user.role = Some(UserRole::User);
         }
@@ -531,7 +658,9 @@ pub async fn request_reset_password_email(
     ValidatedJson(request): ValidatedJson<RequestResetPasswordEmailRequest>,
     Extension(repo): Extension<DynRepo>,
     Extension(email_sender): Extension<DynEmailSender>,
+    Extension(captcha_verifier): Extension<DynCaptchaVerifier>,
 ) -> Result<impl IntoResponse, AppError> {
+    captcha_verifier.verify(&request.captcha_token).await?;
     Ok(pg_transaction(repo, |conn| {
         let user_repository = UserRepo::new(conn);
         let user = match user_repository.find_one_by_email(request.email.clone())? {
@@ -550,6 +679,7 @@ pub async fn request_reset_password_email(
                 }
             };
         match email_sender.email_sender().send_reset_password_email(
+            conn,
             request.email,
             user.display_name,
             token,
@@ -639,11 +769,263 @@ pub async fn get_profile(
     .await?)
 }
 
+pub async fn update_profile(
+    ClaimsContext { claims }: ClaimsContext,
+    ValidatedJson(request): ValidatedJson<UpdateProfileRequest>,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    Ok(pg_transaction(repo, |conn| {
+        let user_repository = UserRepo::new(conn);
+        let user = match user_repository.find_one(claims.user_id())? {
+            Some(value) => value,
+            None => {
+                return Ok(not_found_response("User"));
+            }
+        };
+        if user.display_name != request.display_name
+            && user_repository
+                .find_one_by_display_name(request.display_name.clone())?
+                .is_some()
+        {
+            return Ok(simple_error_response(
+                String::from("This Display Name is already in use"),
+                StatusCode::CONFLICT,
+            ));
+        }
+        let updated_user = UpdateUser {
+            display_name: Some(request.display_name),
+            email: None,
+            password: None,
+            role: None,
+            updated_at: Some(Some(Utc::now().naive_utc())),
+        };
+        let user_result = user_repository.update_one(user.id, updated_user)?;
+        Ok(simple_ok_response(user_result))
+    })
+    .await?)
+}
+
+pub async fn change_password(
+    ClaimsContext { claims }: ClaimsContext,
+    ValidatedJson(request): ValidatedJson<ChangePasswordRequest>,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    Ok(pg_transaction(repo, |conn| {
+        let user_repository = UserRepo::new(conn);
+        let user = match find_user_with_password(&user_repository, claims.user_id())? {
+            Some(value) => value,
+            None => {
+                return Ok(not_found_response("User"));
+            }
+        };
+        if !passwords::verify(user.password.as_str(), request.current_password.as_bytes()) {
+            return Ok(bad_request_response("Wrong Password"));
+        }
+        let updated_user = UpdateUser {
+            display_name: None,
+            email: None,
+            password: Some(passwords::hash(request.new_password.as_bytes())),
+            role: None,
+            updated_at: Some(Some(Utc::now().naive_utc())),
+        };
+        user_repository.update_one(user.id, updated_user)?;
+        // Only the session that changed the password stays logged in
+        let refresh_token_repository = RefreshTokenRepo::new(conn);
+        refresh_token_repository.invalidate_all_for_user(user.id, Some(claims.jti()))?;
+        Ok(simple_ok_response(()))
+    })
+    .await?)
+}
+
+pub async fn delete_account(
+    ClaimsContext { claims }: ClaimsContext,
+    ValidatedJson(request): ValidatedJson<DeleteAccountRequest>,
+    cookies: Cookies,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    Ok(pg_transaction(repo, |conn| {
+        let user_repository = UserRepo::new(conn);
+        let user = match find_user_with_password(&user_repository, claims.user_id())? {
+            Some(value) => value,
+            None => {
+                return Ok(not_found_response("User"));
+            }
+        };
+        if !passwords::verify(user.password.as_str(), request.password.as_bytes()) {
+            return Ok(bad_request_response("Wrong Password"));
+        }
+        let tombstone = match user_repository
+            .find_one_by_display_name(String::from(TOMBSTONE_DISPLAY_NAME))?
+        {
+            Some(value) => value,
+            None => {
+                return Ok(simple_error_response(
+                    String::from("This should not happen, but couldn't find the tombstone user."),
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                ));
+            }
+        };
+        if tombstone.id == user.id {
+            return Ok(bad_request_response("This user can not be deleted"));
+        }
+        user_repository.delete_account(user.id, tombstone.id, request.delete_comments)?;
+
+        let resp_body = Json(BaseResponse {
+            data: Some(()),
+            success: Some(true),
+            errors: None,
+            messages: None,
+            pagination: None,
+        });
+        cookies.remove(Cookie::new("refresh_token", ""));
+        Ok((StatusCode::NO_CONTENT, resp_body).into_response())
+    })
+    .await?)
+}
+
+pub async fn export_profile(
+    ClaimsContext { claims }: ClaimsContext,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    Ok(pg_transaction(repo, |conn| {
+        let user_repository = UserRepo::new(conn);
+        let profile = match user_repository.find_one(claims.user_id())? {
+            Some(value) => value,
+            None => {
+                return Ok(not_found_response("User"));
+            }
+        };
+        let all_pages = PaginationOptions {
+            page: None,
+            page_size: None,
+        };
+        let blog_comment_repository = BlogPostCommentRepo::new(conn);
+        let (comments, _) = blog_comment_repository.find(
+            GetAllBlogPostCommentsFilter {
+                post_id: None,
+                author_id: Some(profile.id),
+            },
+            None,
+            all_pages.clone(),
+        )?;
+        let blog_comment_rating_repository = BlogPostCommentRatingRepo::new(conn);
+        let ratings = blog_comment_rating_repository.find(
+            GetAllBlogPostCommentRatingsFilter {
+                user_id: Some(profile.id),
+            },
+            None,
+            all_pages,
+        )?;
+        Ok(simple_ok_response(UserDataExportResponse {
+            profile,
+            comments,
+            ratings,
+        }))
+    })
+    .await?)
+}
+
+fn find_user_with_password(
+    user_repository: &UserRepo<'_>,
+    user_id: i32,
+) -> QueryResult<Option<backend_repo_pg::models::db_models::User>> {
+    let email = match user_repository.find_one(user_id)? {
+        Some(domain::User {
+            email: Some(value), ..
+        }) => value,
+        _ => return Ok(None),
+    };
+    user_repository.find_one_by_email(email)
+}
+
+fn start_email_change(
+    conn: &RepoConnection,
+    email_sender: &DynEmailSender,
+    user: domain::User,
+    new_email: String,
+) -> QueryResult<Response<BoxBody>> {
+    let old_email = match user.email {
+        Some(value) => value,
+        None => {
+            return Ok(simple_error_response(
+                String::from("This should not happen, but couldn't find email."),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ));
+        }
+    };
+    if old_email == new_email {
+        return Ok(bad_request_response("This is already your E-Mail Address"));
+    }
+    let user_repository = UserRepo::new(conn);
+    if user_repository
+        .find_one_by_email(new_email.clone())?
+        .is_some()
+    {
+        return Ok(simple_error_response(
+            String::from("This E-Mail Address is already in use"),
+            StatusCode::CONFLICT,
+        ));
+    }
+    VerifyEmailTokenRepo::new(conn).invalidate_pending_changes(user.id)?;
+    let confirm_token = match create_verify_email_token(
+        VerifyEmailTokenRepo::new(conn),
+        new_email.clone(),
+        Some(old_email.clone()),
+        user.id,
+        false,
+    ) {
+        Ok(value) => value,
+        Err(_) => {
+            return Err(get_roll_back_err());
+        }
+    };
+    let undo_token = match create_verify_email_token(
+        VerifyEmailTokenRepo::new(conn),
+        old_email.clone(),
+        Some(new_email.clone()),
+        user.id,
+        true,
+    ) {
+        Ok(value) => value,
+        Err(_) => {
+            return Err(get_roll_back_err());
+        }
+    };
+    match email_sender
+        .email_sender()
+        .send_email_change_confirmation_email(
+            conn,
+            new_email.clone(),
+            user.display_name.clone(),
+            confirm_token,
+        ) {
+        Ok(_) => {}
+        Err(_) => {
+            return Err(get_roll_back_err());
+        }
+    };
+    match email_sender.email_sender().send_email_change_notice_email(
+        conn,
+        old_email,
+        new_email,
+        user.display_name,
+        undo_token,
+    ) {
+        Ok(_) => {}
+        Err(_) => {
+            return Err(get_roll_back_err());
+        }
+    };
+
+    Ok(simple_ok_response(()))
+}
+
 fn create_verify_email_token(
     verify_email_tokens_repository: backend_repo_pg::verify_email_tokens::VerifyEmailTokenRepo<'_>,
     email: String,
     old_email: Option<String>,
     user_id: i32,
+    undo: bool,
 ) -> Result<String, PgRepoError> {
     let token = rand::thread_rng()
         .sample_iter(&Alphanumeric)
@@ -657,6 +1039,7 @@ fn create_verify_email_token(
         old_email,
         token,
         user_id,
+        undo,
     };
     let inserted_token = verify_email_tokens_repository.insert_one(new_verify_email_token)?;
 
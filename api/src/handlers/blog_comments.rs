@@ -1,3 +1,5 @@
+use crate::app::DynEmailSender;
+use crate::emails::ModerationDecision;
 use crate::errors::AppError;
 use crate::extractors::ClaimsContext;
 use crate::extractors::ValidatedJson;
@@ -12,15 +14,18 @@ use axum::extract::Path;
 use axum::response::IntoResponse;
 use backend_repo_pg::errors::PgRepoError;
 use backend_repo_pg::models::queries::PaginatedQuery;
+use backend_repo_pg::notification_preferences::NotificationPreferencesRepo;
 use backend_repo_pg::pg_util::get_roll_back_err;
 use backend_repo_pg::pg_util::pg_transaction;
-use backend_repo_pg::pg_util::DynRepo;
+use backend_repo_pg::pg_util::{DynRepo, QueryResult, RepoConnection};
+use backend_repo_pg::users::UserRepo;
 use backend_repo_pg::{blog_comments::BlogPostCommentRepo, blog_posts::BlogPostRepo};
 use backend_repo_pg::{
     change_sets::UpdateBlogPostComment,
     filters::GetAllBlogPostCommentsFilter,
     insertables::NewBlogPostComment,
     models::{
+        domain::BlogPostComment,
         queries::GetAllBlogPostCommentsQuery,
         requests::{CreateBlogPostCommentRequest, UpdateBlogPostCommentRequest},
     },
@@ -74,6 +79,7 @@ pub async fn delete(
     Path(id): Path<i32>,
     ClaimsContext { claims }: ClaimsContext,
     Extension(repo): Extension<DynRepo>,
+    Extension(email_sender): Extension<DynEmailSender>,
 ) -> Result<impl IntoResponse, AppError> {
     Ok(pg_transaction(repo, |conn| {
         let blog_comment_repository = BlogPostCommentRepo::new(conn);
@@ -83,13 +89,16 @@ pub async fn delete(
             }
             Some(value) => value,
         };
-        if comment.author.id != claims.user_id() || !claims.is_admin() {
+        if !claims.can_manage(comment.author.id) {
             return Ok(unauthorized_response("comment"));
         }
         let comment_result = blog_comment_repository.delete_one(id)?;
         if comment_result == 0 {
             return Ok(not_found_response("Comment"));
         }
+        if comment.author.id != claims.user_id() {
+            notify_moderation_decision(conn, &email_sender, &comment, ModerationDecision::Removed)?;
+        }
         if claims.is_for_admin_site() {
             match create_deletion_admin_log(
                 id.to_string(),
@@ -116,6 +125,7 @@ pub async fn update(
     ClaimsContext { claims }: ClaimsContext,
     ValidatedJson(request): ValidatedJson<UpdateBlogPostCommentRequest>,
     Extension(repo): Extension<DynRepo>,
+    Extension(email_sender): Extension<DynEmailSender>,
 ) -> Result<impl IntoResponse, AppError> {
     Ok(pg_transaction(repo, |conn| {
         let blog_comment_repository = BlogPostCommentRepo::new(conn);
@@ -126,7 +136,7 @@ pub async fn update(
                 return Ok(not_found_response("Comment"));
             }
         };
-        if comment.author.id != claims.user_id() || !claims.is_admin() {
+        if !claims.can_manage(comment.author.id) {
             return Ok(not_found_response("Comment"));
         }
         let comment_updates = UpdateBlogPostComment {
@@ -134,6 +144,9 @@ pub async fn update(
             updated_at: Some(Some(Utc::now().naive_utc())),
         };
         let comment_result = blog_comment_repository.update_one(id, comment_updates)?;
+        if comment.author.id != claims.user_id() {
+            notify_moderation_decision(conn, &email_sender, &comment, ModerationDecision::Edited)?;
+        }
         if claims.is_for_admin_site() {
             match create_update_admin_log(
                 id.to_string(),
@@ -160,23 +173,85 @@ pub async fn create(
     ClaimsContext { claims }: ClaimsContext,
     ValidatedJson(request): ValidatedJson<CreateBlogPostCommentRequest>,
     Extension(repo): Extension<DynRepo>,
+    Extension(email_sender): Extension<DynEmailSender>,
 ) -> Result<impl IntoResponse, AppError> {
     Ok(pg_transaction(repo, |conn| {
         let blog_post_repository = BlogPostRepo::new(conn);
-        let _ = match blog_post_repository.find_one(request.post_id)? {
+        let post = match blog_post_repository.find_one(request.post_id)? {
             None => {
                 return Ok(bad_request_response("Invalid post id"));
             }
             Some(value) => value,
         };
+        let blog_comment_repository = BlogPostCommentRepo::new(conn);
+        let parent = match request.parent_id {
+            Some(parent_id) => match blog_comment_repository.find_one(parent_id)? {
+                Some(value) if value.post_id == request.post_id => Some(value),
+                _ => {
+                    return Ok(bad_request_response("Invalid parent id"));
+                }
+            },
+            None => None,
+        };
         let new_comment = NewBlogPostComment {
-            body: Some(request.body),
+            body: Some(request.body.clone()),
             post_id: request.post_id,
             author_id: claims.user_id(),
+            parent_id: request.parent_id,
         };
-        let blog_comment_repository = BlogPostCommentRepo::new(conn);
         let comment_result = blog_comment_repository.insert_one(new_comment)?;
+        if let Some(parent) = parent {
+            let preferences =
+                NotificationPreferencesRepo::new(conn).find_one_by_user(parent.author.id)?;
+            if parent.author.id != claims.user_id() && preferences.comment_replies {
+                if let Some(recipient) = UserRepo::new(conn).find_one(parent.author.id)? {
+                    match email_sender.email_sender().send_comment_reply_email(
+                        conn,
+                        &recipient,
+                        claims.display_name(),
+                        &post,
+                        request.body,
+                    ) {
+                        Ok(_) => {}
+                        Err(_) => {
+                            return Err(get_roll_back_err());
+                        }
+                    };
+                }
+            }
+        }
         Ok(simple_no_content_response(comment_result))
     })
     .await?)
 }
+
+/// Lets the author of a comment know that a moderator changed or removed it.
+fn notify_moderation_decision(
+    conn: &RepoConnection,
+    email_sender: &DynEmailSender,
+    comment: &BlogPostComment,
+    decision: ModerationDecision,
+) -> QueryResult<()> {
+    let preferences = NotificationPreferencesRepo::new(conn).find_one_by_user(comment.author.id)?;
+    if !preferences.moderation_decisions {
+        return Ok(());
+    }
+    let recipient = match UserRepo::new(conn).find_one(comment.author.id)? {
+        Some(value) => value,
+        None => return Ok(()),
+    };
+    let post = match BlogPostRepo::new(conn).find_one(comment.post_id)? {
+        Some(value) => value,
+        None => return Ok(()),
+    };
+    match email_sender.email_sender().send_comment_moderation_email(
+        conn,
+        &recipient,
+        &post,
+        decision,
+        comment.body.clone(),
+    ) {
+        Ok(_) => Ok(()),
+        Err(_) => Err(get_roll_back_err()),
+    }
+}
@@ -1,6 +1,7 @@
 use crate::errors::AppError;
 use crate::extractors::AdminClaimsContext;
 use crate::extractors::ValidatedJson;
+use crate::util::bad_request_response;
 use crate::util::create_creation_admin_log;
 use crate::util::create_deletion_admin_log;
 use crate::util::create_update_admin_log;
@@ -11,15 +12,19 @@ use crate::util::{
 use axum::extract::Extension;
 use axum::extract::Path;
 use axum::response::IntoResponse;
+use backend_repo_pg::blog_posts::BlogPostRepo;
 use backend_repo_pg::categories::CategoryRepo;
 use backend_repo_pg::errors::PgRepoError;
+use backend_repo_pg::models::domain::CategoryDetail;
+use backend_repo_pg::models::queries::GetCategoryQuery;
 use backend_repo_pg::models::queries::PaginatedQuery;
+use backend_repo_pg::options::BlogPostSortType;
 use backend_repo_pg::pg_util::get_roll_back_err;
 use backend_repo_pg::pg_util::pg_transaction;
 use backend_repo_pg::pg_util::DynRepo;
 use backend_repo_pg::{
     change_sets::UpdateCategory,
-    filters::GetAllCategoriesFilter,
+    filters::{GetAllBlogPostsFilter, GetAllCategoriesFilter},
     insertables::NewCategory,
     models::{
         queries::GetAllCategoriesQuery,
@@ -29,22 +34,68 @@ use backend_repo_pg::{
 use tokio::task::block_in_place;
 
 pub async fn get(
-    Path(id): Path<i32>,
+    Path(id): Path<String>,
+    query: GetCategoryQuery,
     Extension(repo): Extension<DynRepo>,
 ) -> Result<impl IntoResponse, AppError> {
     block_in_place(|| {
         let conn = repo.get_conn()?;
         let category_repository = CategoryRepo::new(&conn);
-        let category_result = match category_repository
-            .find_one(id)
-            .map_err::<PgRepoError, _>(|e| e.into())?
-        {
+        let category_result = if let Some(true) = query.use_slug {
+            category_repository
+                .find_one_by_slug(id)
+                .map_err::<PgRepoError, _>(|e| e.into())?
+        } else {
+            let id = match id.parse::<i32>() {
+                Ok(v) => v,
+                Err(_) => {
+                    return Ok(bad_request_response("Url: Bad Id value"));
+                }
+            };
+            category_repository
+                .find_one(id)
+                .map_err::<PgRepoError, _>(|e| e.into())?
+        };
+        let category_result = match category_result {
             None => {
                 return Ok(not_found_response("Category"));
             }
             Some(value) => value,
         };
-        Ok(simple_ok_response(category_result))
+        let parent = match category_result.parent_id {
+            Some(parent_id) => category_repository
+                .find_one(parent_id)
+                .map_err::<PgRepoError, _>(|e| e.into())?,
+            None => None,
+        };
+        let children = category_repository
+            .find_children(category_result.id)
+            .map_err::<PgRepoError, _>(|e| e.into())?;
+        let category_ids = category_repository
+            .find_subtree_ids(category_result.id)
+            .map_err::<PgRepoError, _>(|e| e.into())?;
+        let filter = GetAllBlogPostsFilter {
+            author_id: None,
+            category_id: None,
+            category_name: None,
+            category_ids: Some(category_ids),
+            published: Some(true),
+            published_after: None,
+        };
+        let (blog_posts, blog_post_count) = BlogPostRepo::new(&conn)
+            .find(
+                filter,
+                Some(BlogPostSortType::CreatedAtDesc),
+                query.pagination_options(),
+            )
+            .map_err::<PgRepoError, _>(|e| e.into())?;
+        Ok(simple_ok_response(CategoryDetail {
+            category: category_result,
+            parent,
+            children,
+            blog_posts,
+            blog_post_count,
+        }))
     })
 }
 
@@ -121,7 +172,24 @@ pub async fn update(
                 return Ok(not_found_response("Link"));
             }
         };
-        let updated_category = UpdateCategory { name: request.name };
+        if let Some(Some(parent_id)) = request.parent_id {
+            if category_repository
+                .find_subtree_ids(id)?
+                .contains(&parent_id)
+            {
+                return Ok(bad_request_response(
+                    "Parent: Can't be the category or one nested under it",
+                ));
+            }
+        }
+        let updated_category = UpdateCategory {
+            name: request.name,
+            slug: request.slug,
+            description: request.description,
+            icon: request.icon,
+            color: request.color,
+            parent_id: request.parent_id,
+        };
         let category_result = category_repository.update_one(id, &updated_category)?;
         match create_update_admin_log(
             id.to_string(),
@@ -149,7 +217,14 @@ pub async fn create(
     Extension(repo): Extension<DynRepo>,
 ) -> Result<impl IntoResponse, AppError> {
     Ok(pg_transaction(repo.clone(), |conn| {
-        let new_category = NewCategory { name: request.name };
+        let new_category = NewCategory {
+            name: request.name,
+            slug: request.slug,
+            description: request.description,
+            icon: request.icon,
+            color: request.color,
+            parent_id: request.parent_id,
+        };
         let new_category_copy = new_category.clone();
         let category_repository = CategoryRepo::new(conn);
         let category_result = category_repository.insert_one(&new_category)?;
@@ -15,11 +15,16 @@ use axum::extract::Path;
 use axum::response::IntoResponse;
 use backend_repo_pg::blog_posts::BlogPostRepo;
 use backend_repo_pg::errors::PgRepoError;
+use backend_repo_pg::extra::SearchItemType;
 use backend_repo_pg::models::queries::GetBlogPostQuery;
+use backend_repo_pg::models::queries::GetRelatedItemsQuery;
 use backend_repo_pg::models::queries::PaginatedQuery;
 use backend_repo_pg::pg_util::get_roll_back_err;
 use backend_repo_pg::pg_util::pg_transaction;
 use backend_repo_pg::pg_util::DynRepo;
+use backend_repo_pg::related_items::RelatedItemRepo;
+use backend_repo_pg::series::SeriesRepo;
+use backend_repo_pg::text_search::detect_language;
 use backend_repo_pg::{
     change_sets::UpdateBlogPost,
     filters::GetAllBlogPostsFilter,
@@ -32,6 +37,8 @@ use backend_repo_pg::{
 use chrono::Utc;
 use tokio::task::block_in_place;
 
+const DEFAULT_RELATED: i64 = 5;
+
 pub async fn get(
     Path(id): Path<String>,
     GetBlogPostQuery { use_slug }: GetBlogPostQuery,
@@ -41,7 +48,7 @@ pub async fn get(
     block_in_place(|| {
         let conn = repo.get_conn()?;
         let blog_post_repository = BlogPostRepo::new(&conn);
-        let post_result = if let Some(true) = use_slug {
+        let mut post_result = if let Some(true) = use_slug {
             match blog_post_repository
                 .find_one_by_slug(id)
                 .map_err::<PgRepoError, _>(|e| e.into())?
@@ -68,15 +75,47 @@ pub async fn get(
                 Some(value) => value,
             }
         };
+        let is_staff = match &claims {
+            Some(claims) => claims.is_staff(),
+            None => false,
+        };
         if let Some(claims) = claims {
             if !claims.is_staff() && !post_result.published {
                 return Ok(not_found_response("Post"));
             }
         }
+        post_result.series = SeriesRepo::new(&conn)
+            .find_navigation(post_result.id, !is_staff)
+            .map_err::<PgRepoError, _>(|e| e.into())?;
         Ok(simple_ok_response(post_result))
     })
 }
 
+/// Other published posts related to this one, most related first.
+pub async fn get_related(
+    Path(id): Path<String>,
+    query: GetRelatedItemsQuery,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    block_in_place(|| {
+        let conn = repo.get_conn()?;
+        let id = match id.parse::<i32>() {
+            Ok(v) => v,
+            Err(_) => {
+                return Ok(bad_request_response("Url: Bad Id value"));
+            }
+        };
+        let related = RelatedItemRepo::new(&conn)
+            .find(
+                SearchItemType::BlogPost,
+                id,
+                query.limit.unwrap_or(DEFAULT_RELATED),
+            )
+            .map_err::<PgRepoError, _>(|e| e.into())?;
+        Ok(simple_ok_response(related))
+    })
+}
+
 pub async fn get_all(
     query: GetAllBlogPostsQuery,
     OptClaimsContext { claims }: OptClaimsContext,
@@ -164,6 +203,7 @@ pub async fn update(
             updated_at: Some(Some(Utc::now().naive_utc())),
             description: request.description,
             slug: request.slug,
+            language: request.language,
         };
         let post_result = if let Some(categories_list) = request.categories {
             blog_post_repository.update_one_with_categories(id, &post_updates, &categories_list)?
@@ -196,6 +236,10 @@ pub async fn create(
     Extension(repo): Extension<DynRepo>,
 ) -> Result<impl IntoResponse, AppError> {
     Ok(pg_transaction(repo, |conn| {
+        let language = match request.language {
+            Some(value) => value,
+            None => detect_language(&request.body),
+        };
         let new_post = NewBlogPost {
             title: request.title,
             body: request.body,
@@ -203,6 +247,7 @@ pub async fn create(
             published: false,
             description: request.description,
             slug: request.slug,
+            language,
         };
         let new_post_copy = new_post.clone();
         let blog_post_repository = BlogPostRepo::new(conn);
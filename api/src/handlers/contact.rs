@@ -1,48 +1,234 @@
-use crate::app::{DynCaptchaSecret, DynEmailSender};
+use std::net::SocketAddr;
+
+use crate::app::{DynCaptchaVerifier, DynEmailSender};
 use crate::errors::AppError;
-use crate::extractors::ValidatedJson;
-use crate::{errors::CaptchaError, util::simple_created_response};
-use axum::extract::Extension;
+use crate::extractors::{AdminClaimsContext, ValidatedJson};
+use crate::spam::{spam_score, ContactSubmission, SPAM_THRESHOLD};
+use crate::util::{
+    create_creation_admin_log, create_update_admin_log, not_found_response, paginated_ok_response,
+    simple_created_response, simple_ok_response,
+};
+use axum::extract::{ConnectInfo, Extension, Path, TypedHeader};
 use axum::response::IntoResponse;
-use backend_repo_pg::models::requests::SendContactEmailRequest;
-use hyper::{Body, Client, Method, Request};
+use backend_repo_pg::{
+    change_sets::UpdateContactMessage,
+    contact_message_replies::ContactMessageReplyRepo,
+    contact_messages::ContactMessageRepo,
+    errors::PgRepoError,
+    filters::GetAllContactMessagesFilter,
+    insertables::{NewContactMessage, NewContactMessageReply},
+    models::queries::{GetAllContactMessagesQuery, PaginatedQuery},
+    models::requests::{
+        ReplyContactMessageRequest, SendContactEmailRequest, UpdateContactMessageRequest,
+    },
+    pg_util::{get_roll_back_err, pg_transaction, DynRepo},
+};
+use chrono::{Duration, Utc};
+use headers::UserAgent;
 use tokio::task::block_in_place;
 
 pub async fn contact_email(
+    user_agent: Option<TypedHeader<UserAgent>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
     ValidatedJson(request): ValidatedJson<SendContactEmailRequest>,
-    Extension(captcha_secret): Extension<DynCaptchaSecret>,
+    Extension(repo): Extension<DynRepo>,
+    Extension(captcha_verifier): Extension<DynCaptchaVerifier>,
     Extension(email_sender): Extension<DynEmailSender>,
 ) -> Result<impl IntoResponse, AppError> {
-    let captcha_req_body = format!(
-        "response={}&secret={}",
-        request.captcha_token,
-        captcha_secret.captcha_secret()
-    );
-    let https = hyper_tls::HttpsConnector::new();
-    let captcha_request = Request::builder()
-        .method(Method::POST)
-        .uri("http://hcaptcha.com/siteverify")
-        .header("content-type", "application/x-www-form-urlencoded")
-        .body(Body::from(captcha_req_body))
-        .map_err(CaptchaError::from)?;
-
-    let client = Client::builder().build(https);
-    let resp = client
-        .request(captcha_request)
-        .await
-        .map_err(CaptchaError::from)?;
-    if resp.status().is_client_error() || resp.status().is_server_error() {
-        return Err(AppError::CaptchaError(CaptchaError::new(
-            "Error verifying captcha",
-        )));
-    }
+    captcha_verifier.verify(&request.captcha_token).await?;
+    let ip_address = addr.ip().to_string();
+    let user_agent = user_agent.map(|TypedHeader(user_agent)| user_agent.to_string());
+
+    Ok(pg_transaction(repo, |conn| {
+        let message_repository = ContactMessageRepo::new(conn);
+        let recent_submissions = message_repository
+            .count_recent_by_ip(&ip_address, Utc::now().naive_utc() - Duration::hours(1))?;
+        let spam_score = spam_score(&ContactSubmission {
+            subject: &request.subject,
+            body: &request.body,
+            honeypot: request.website.as_deref(),
+            recent_submissions,
+        });
+        let message = message_repository.insert_one(NewContactMessage {
+            from_email: request.from_email,
+            subject: request.subject,
+            body: request.body,
+            ip_address: Some(ip_address),
+            user_agent,
+            spam_score,
+            spam: spam_score >= SPAM_THRESHOLD,
+        })?;
+        // Suspected spam stays in the inbox without notifying anyone
+        if !message.spam {
+            match email_sender.email_sender().send_contact_email(
+                conn,
+                message.from_email,
+                message.subject,
+                message.body,
+            ) {
+                Ok(_) => {}
+                Err(_) => {
+                    return Err(get_roll_back_err());
+                }
+            };
+        }
+        Ok(simple_created_response(1))
+    })
+    .await?)
+}
+
+pub async fn get_all_messages(
+    _: AdminClaimsContext,
+    query: GetAllContactMessagesQuery,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    block_in_place(|| {
+        let conn = repo.get_conn()?;
+        let filter = GetAllContactMessagesFilter::from_query(query.clone());
+        let message_repository = ContactMessageRepo::new(&conn);
+        let pagination_opts = query.pagination_options();
+        let sort_type = query.sort_type;
+        let (messages, total_results) = message_repository
+            .find(filter, sort_type, pagination_opts)
+            .map_err::<PgRepoError, _>(|e| e.into())?;
+        Ok(paginated_ok_response(
+            messages,
+            query.page,
+            query.page_size,
+            total_results,
+        ))
+    })
+}
+
+pub async fn get_message(
+    Path(id): Path<i32>,
+    _: AdminClaimsContext,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    let message = pg_transaction(repo, |conn| ContactMessageRepo::new(conn).find_one(id))
+        .await?
+        .ok_or(AppError::NotFound("Contact message"))?;
+    Ok(simple_ok_response(message))
+}
 
+pub async fn update_message(
+    Path(id): Path<i32>,
+    AdminClaimsContext { claims }: AdminClaimsContext,
+    ValidatedJson(request): ValidatedJson<UpdateContactMessageRequest>,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    Ok(pg_transaction(repo, |conn| {
+        let message_repository = ContactMessageRepo::new(conn);
+        let message = match message_repository.find_one(id)? {
+            Some(value) => value,
+            None => {
+                return Ok(not_found_response("Contact message"));
+            }
+        };
+        let now = Utc::now().naive_utc();
+        let set_or_clear = |flag: bool| if flag { Some(now) } else { None };
+        message_repository.update_one(
+            id,
+            UpdateContactMessage {
+                spam: request.spam,
+                read_at: request.read.map(set_or_clear),
+                archived_at: request.archived.map(set_or_clear),
+                replied_at: None,
+            },
+        )?;
+        match create_update_admin_log(
+            id.to_string(),
+            claims.user_id(),
+            String::from("Contact Message"),
+            String::from("contact_messages"),
+            &request,
+            &message,
+            String::from("/contact-messages"),
+            conn,
+        ) {
+            Ok(_) => {}
+            Err(_) => {
+                return Err(get_roll_back_err());
+            }
+        };
+        Ok(simple_ok_response(message_repository.find_one(id)?))
+    })
+    .await?)
+}
+
+pub async fn get_replies(
+    Path(id): Path<i32>,
+    _: AdminClaimsContext,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
     block_in_place(|| {
-        email_sender.email_sender().send_contact_email(
-            request.from_email,
-            request.subject,
-            request.body,
-        )
-    })?;
-    Ok(simple_created_response(1))
+        let conn = repo.get_conn()?;
+        let replies = ContactMessageReplyRepo::new(&conn)
+            .find_by_message(id)
+            .map_err::<PgRepoError, _>(|e| e.into())?;
+        Ok(simple_ok_response(replies))
+    })
+}
+
+pub async fn reply(
+    Path(id): Path<i32>,
+    AdminClaimsContext { claims }: AdminClaimsContext,
+    ValidatedJson(request): ValidatedJson<ReplyContactMessageRequest>,
+    Extension(repo): Extension<DynRepo>,
+    Extension(email_sender): Extension<DynEmailSender>,
+) -> Result<impl IntoResponse, AppError> {
+    Ok(pg_transaction(repo, |conn| {
+        let message_repository = ContactMessageRepo::new(conn);
+        let message = match message_repository.find_one(id)? {
+            Some(value) => value,
+            None => {
+                return Ok(not_found_response("Contact message"));
+            }
+        };
+        let email = match email_sender.email_sender().send_contact_reply_email(
+            conn,
+            message.from_email.clone(),
+            &message.subject,
+            &request.body,
+            &message.body,
+        ) {
+            Ok(value) => value,
+            Err(_) => {
+                return Err(get_roll_back_err());
+            }
+        };
+        let new_reply = NewContactMessageReply {
+            contact_message_id: id,
+            user_id: claims.user_id(),
+            body: request.body,
+            email_outbox_id: Some(email.id),
+        };
+        let reply_id = ContactMessageReplyRepo::new(conn).insert_one(new_reply.clone())?;
+        let now = Utc::now().naive_utc();
+        message_repository.update_one(
+            id,
+            UpdateContactMessage {
+                spam: None,
+                read_at: Some(Some(message.read_at.unwrap_or(now))),
+                archived_at: None,
+                replied_at: Some(Some(now)),
+            },
+        )?;
+        match create_creation_admin_log(
+            reply_id.to_string(),
+            claims.user_id(),
+            String::from("Contact Message Reply"),
+            String::from("contact_message_replies"),
+            &new_reply,
+            String::from("/contact-messages"),
+            conn,
+        ) {
+            Ok(_) => {}
+            Err(_) => {
+                return Err(get_roll_back_err());
+            }
+        };
+        Ok(simple_created_response(reply_id))
+    })
+    .await?)
 }
@@ -0,0 +1,71 @@
+use crate::{
+    errors::AppError,
+    extractors::AdminClaimsContext,
+    util::{paginated_ok_response, simple_ok_response},
+};
+use axum::{
+    extract::{Extension, Path},
+    response::IntoResponse,
+};
+use backend_repo_pg::{
+    email_outbox::OutboxEmailRepo,
+    errors::PgRepoError,
+    filters::GetAllOutboxEmailsFilter,
+    models::queries::{GetAllOutboxEmailsQuery, PaginatedQuery},
+    pg_util::{pg_transaction, DynRepo},
+};
+use tokio::task::block_in_place;
+
+pub async fn get(
+    Path(id): Path<i32>,
+    _: AdminClaimsContext,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    let email = pg_transaction(repo, |conn| {
+        let outbox_repository = OutboxEmailRepo::new(conn);
+        outbox_repository.find_one(id)
+    })
+    .await?
+    .ok_or(AppError::NotFound("Email"))?;
+    Ok(simple_ok_response(email))
+}
+
+pub async fn get_all(
+    _: AdminClaimsContext,
+    query: GetAllOutboxEmailsQuery,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    block_in_place(|| {
+        let conn = repo.get_conn()?;
+        let filter = GetAllOutboxEmailsFilter::from_query(query.clone());
+        let outbox_repository = OutboxEmailRepo::new(&conn);
+        let pagination_opts = query.pagination_options();
+        let sort_type = query.sort_type;
+        let (emails, total_results) = outbox_repository
+            .find(filter, sort_type, pagination_opts)
+            .map_err::<PgRepoError, _>(|e| e.into())?;
+        Ok(paginated_ok_response(
+            emails,
+            query.page,
+            query.page_size,
+            total_results,
+        ))
+    })
+}
+
+pub async fn retry(
+    Path(id): Path<i32>,
+    _: AdminClaimsContext,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    let email = pg_transaction(repo, |conn| {
+        let outbox_repository = OutboxEmailRepo::new(conn);
+        if outbox_repository.find_one(id)?.is_none() {
+            return Ok(None);
+        }
+        outbox_repository.retry(id).map(Some)
+    })
+    .await?
+    .ok_or(AppError::NotFound("Email"))?;
+    Ok(simple_ok_response(email))
+}
@@ -0,0 +1,36 @@
+use crate::{
+    app::DynEmailSender,
+    email_templates::EmailTemplate,
+    errors::AppError,
+    extractors::AdminClaimsContext,
+    util::{not_found_response, simple_ok_response},
+};
+use axum::{
+    extract::{Extension, Path},
+    response::IntoResponse,
+};
+use backend_repo_pg::models::queries::PreviewEmailTemplateQuery;
+
+pub async fn get_all(_: AdminClaimsContext) -> Result<impl IntoResponse, AppError> {
+    let names: Vec<&str> = EmailTemplate::ALL
+        .iter()
+        .map(|template| template.name())
+        .collect();
+    Ok(simple_ok_response(names))
+}
+
+pub async fn preview(
+    Path(name): Path<String>,
+    _: AdminClaimsContext,
+    query: PreviewEmailTemplateQuery,
+    Extension(email_sender): Extension<DynEmailSender>,
+) -> Result<impl IntoResponse, AppError> {
+    let template: EmailTemplate = match name.parse() {
+        Ok(template) => template,
+        Err(_) => return Ok(not_found_response("Email template")),
+    };
+    let rendered = email_sender
+        .email_sender()
+        .preview(template, query.locale.as_deref())?;
+    Ok(simple_ok_response(rendered))
+}
@@ -2,7 +2,8 @@ use axum::{extract::Extension, response::IntoResponse};
 use tokio::{task::block_in_place, time::Instant};
 
 use backend_repo_pg::{
-    errors::PgRepoError, health::HealthRepo, models::responses::HealthResponse, pg_util::DynRepo,
+    errors::PgRepoError, health::HealthRepo, models::domain::SearchRefresh,
+    models::responses::HealthResponse, pg_util::DynRepo, search_refreshes::SearchRefreshRepo,
 };
 
 use crate::util::simple_ok_response;
@@ -10,18 +11,32 @@ use crate::util::simple_ok_response;
 pub async fn health(Extension(repo): Extension<DynRepo>) -> impl IntoResponse {
     let start = Instant::now();
     let api_up = true;
-    let repo_check: Result<Option<u128>, PgRepoError> = block_in_place(|| {
-        let conn = repo.get_conn()?;
-        let health_repo = HealthRepo::new(&conn);
-        Ok(health_repo.check()?)
+    let (repo_check, search_check) = block_in_place(|| {
+        let conn = match repo.get_conn() {
+            Ok(conn) => conn,
+            Err(err) => return (Err(err), None),
+        };
+        let repo_check: Result<Option<u128>, PgRepoError> =
+            HealthRepo::new(&conn).check().map_err(|e| e.into());
+        let search_check: Result<SearchRefresh, PgRepoError> = SearchRefreshRepo::new(&conn)
+            .find_one()
+            .map_err(|e| e.into());
+        (repo_check, Some(search_check))
     });
     let db_up = repo_check.is_ok();
     let db_response_time = repo_check.ok().flatten().map(|t| t.to_string());
+    // Search being unreadable is reported on its own, the database may still be up
+    let (search_up, search_refreshed_at) = match search_check {
+        Some(Ok(search_refresh)) => (true, search_refresh.refreshed_at),
+        _ => (false, None),
+    };
     let api_response_time = Some(start.elapsed().as_millis().to_string());
     simple_ok_response(HealthResponse {
         api_up,
         api_response_time,
         db_up,
         db_response_time,
+        search_up,
+        search_refreshed_at,
     })
 }
@@ -1,15 +1,21 @@
 pub mod admin_logs;
+pub mod api_tokens;
 pub mod auth;
 pub mod blog_comments;
 pub mod blog_post_categories;
 pub mod blog_posts;
 pub mod contact;
+pub mod email_outbox;
+pub mod email_templates;
 pub mod files;
 pub mod health;
 pub mod links;
+pub mod newsletter;
+pub mod notifications;
 pub mod page_views;
 pub mod project_technologies;
 pub mod projects;
 pub mod search;
+pub mod series;
 pub mod text_bodies;
 pub mod users;
@@ -0,0 +1,494 @@
+use crate::app::{DynCaptchaVerifier, DynEmailSender};
+use crate::errors::AppError;
+use crate::extractors::{AdminClaimsContext, ValidatedJson};
+use crate::util::{
+    bad_request_response, create_creation_admin_log, create_update_admin_log, html_page_response,
+    not_found_response, paginated_ok_response, simple_created_response, simple_ok_response,
+};
+use axum::extract::{Extension, Path};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use backend_repo_pg::{
+    blog_posts::BlogPostRepo,
+    change_sets::{UpdateNewsletterIssue, UpdateNewsletterSubscriber},
+    errors::PgRepoError,
+    filters::{
+        GetAllBlogPostsFilter, GetAllNewsletterDeliveriesFilter, GetAllNewsletterIssuesFilter,
+        GetAllNewsletterSubscribersFilter,
+    },
+    insertables::{NewNewsletterIssue, NewNewsletterSubscriber},
+    models::{
+        queries::{
+            ConfirmNewsletterSubscriptionQuery, GetAllNewsletterDeliveriesQuery,
+            GetAllNewsletterIssuesQuery, GetAllNewsletterSubscribersQuery, PaginatedQuery,
+            UnsubscribeQuery,
+        },
+        requests::{
+            CreateNewsletterIssueRequest, SubscribeNewsletterRequest, UpdateNewsletterIssueRequest,
+        },
+    },
+    newsletter_deliveries::NewsletterDeliveryRepo,
+    newsletter_issues::NewsletterIssueRepo,
+    newsletter_subscribers::NewsletterSubscriberRepo,
+    options::{BlogPostSortType, PaginationOptions},
+    pg_util::{get_roll_back_err, pg_transaction, DynRepo, QueryResult, RepoConnection},
+};
+use chrono::{Duration, Utc};
+use rand::{distributions::Alphanumeric, Rng};
+use tera::Context;
+use tokio::task::block_in_place;
+
+/// How far back an issue looks for posts when none are picked and no issue was sent before.
+const DEFAULT_ISSUE_DAYS: i64 = 30;
+
+pub async fn subscribe(
+    ValidatedJson(request): ValidatedJson<SubscribeNewsletterRequest>,
+    Extension(repo): Extension<DynRepo>,
+    Extension(captcha_verifier): Extension<DynCaptchaVerifier>,
+    Extension(email_sender): Extension<DynEmailSender>,
+) -> Result<impl IntoResponse, AppError> {
+    captcha_verifier.verify(&request.captcha_token).await?;
+    let email = request.email.trim().to_lowercase();
+
+    Ok(pg_transaction(repo, |conn| {
+        let subscriber_repository = NewsletterSubscriberRepo::new(conn);
+        let token = generate_newsletter_token();
+        let subscriber = match subscriber_repository.find_one_by_email(&email)? {
+            // Answer the same way as for a new address, so subscriptions can't be probed
+            Some(value) if value.confirmed && value.unsubscribed_at.is_none() => {
+                return Ok(simple_created_response(1));
+            }
+            Some(value) => subscriber_repository.update_one(
+                value.id,
+                UpdateNewsletterSubscriber {
+                    token: Some(token),
+                    confirmed: Some(false),
+                    confirmed_at: Some(None),
+                    unsubscribed_at: Some(None),
+                },
+            )?,
+            None => subscriber_repository.insert_one(NewNewsletterSubscriber {
+                email: email.clone(),
+                token,
+            })?,
+        };
+        match email_sender
+            .email_sender()
+            .send_newsletter_confirmation_email(conn, subscriber.email, subscriber.token)
+        {
+            Ok(_) => {}
+            Err(_) => {
+                return Err(get_roll_back_err());
+            }
+        };
+        Ok(simple_created_response(1))
+    })
+    .await?)
+}
+
+/// Asks for confirmation, so that link scanners opening the email link don't
+/// subscribe someone who never asked to be.
+pub async fn confirm_page(
+    query: ConfirmNewsletterSubscriptionQuery,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    block_in_place(|| {
+        let conn = repo.get_conn()?;
+        let subscriber = NewsletterSubscriberRepo::new(&conn)
+            .find_one_by_token(&query.token)
+            .map_err::<PgRepoError, _>(|e| e.into())?;
+        let mut context = Context::new();
+        match subscriber {
+            Some(value) if value.unsubscribed_at.is_none() => {
+                context.insert("state", if value.confirmed { "done" } else { "confirm" });
+            }
+            _ => {
+                context.insert("state", "invalid");
+                return Ok(html_page_response(
+                    "newsletter_confirm",
+                    &context,
+                    StatusCode::NOT_FOUND,
+                ));
+            }
+        }
+        Ok(html_page_response(
+            "newsletter_confirm",
+            &context,
+            StatusCode::OK,
+        ))
+    })
+}
+
+pub async fn confirm(
+    query: ConfirmNewsletterSubscriptionQuery,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    Ok(pg_transaction(repo, |conn| {
+        let mut context = Context::new();
+        let subscriber_repository = NewsletterSubscriberRepo::new(conn);
+        let subscriber = match subscriber_repository.find_one_by_token(&query.token)? {
+            Some(value) if value.unsubscribed_at.is_none() => value,
+            _ => {
+                context.insert("state", "invalid");
+                return Ok(html_page_response(
+                    "newsletter_confirm",
+                    &context,
+                    StatusCode::NOT_FOUND,
+                ));
+            }
+        };
+        if !subscriber.confirmed {
+            subscriber_repository.update_one(
+                subscriber.id,
+                UpdateNewsletterSubscriber {
+                    token: None,
+                    confirmed: Some(true),
+                    confirmed_at: Some(Some(Utc::now().naive_utc())),
+                    unsubscribed_at: None,
+                },
+            )?;
+        }
+        context.insert("state", "done");
+        Ok(html_page_response(
+            "newsletter_confirm",
+            &context,
+            StatusCode::OK,
+        ))
+    })
+    .await?)
+}
+
+/// Asks for confirmation, so that link scanners opening the email link don't end the
+/// subscription.
+pub async fn unsubscribe_page(
+    query: UnsubscribeQuery,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    block_in_place(|| {
+        let conn = repo.get_conn()?;
+        let subscriber = NewsletterSubscriberRepo::new(&conn)
+            .find_one_by_token(&query.token)
+            .map_err::<PgRepoError, _>(|e| e.into())?;
+        let mut context = Context::new();
+        context.insert("what", "the newsletter");
+        match subscriber {
+            Some(value) if value.unsubscribed_at.is_none() => {
+                context.insert("state", "confirm");
+            }
+            Some(_) => {
+                context.insert("state", "done");
+            }
+            None => {
+                context.insert("state", "invalid");
+                return Ok(html_page_response(
+                    "unsubscribe",
+                    &context,
+                    StatusCode::NOT_FOUND,
+                ));
+            }
+        }
+        Ok(html_page_response("unsubscribe", &context, StatusCode::OK))
+    })
+}
+
+/// Handles both the confirmation form and the one-click `List-Unsubscribe-Post`
+/// request that mail clients send.
+pub async fn unsubscribe(
+    query: UnsubscribeQuery,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    Ok(pg_transaction(repo, |conn| {
+        let mut context = Context::new();
+        context.insert("what", "the newsletter");
+        let subscriber_repository = NewsletterSubscriberRepo::new(conn);
+        let subscriber = match subscriber_repository.find_one_by_token(&query.token)? {
+            Some(value) => value,
+            None => {
+                context.insert("state", "invalid");
+                return Ok(html_page_response(
+                    "unsubscribe",
+                    &context,
+                    StatusCode::NOT_FOUND,
+                ));
+            }
+        };
+        if subscriber.unsubscribed_at.is_none() {
+            subscriber_repository.update_one(
+                subscriber.id,
+                UpdateNewsletterSubscriber {
+                    token: None,
+                    confirmed: None,
+                    confirmed_at: None,
+                    unsubscribed_at: Some(Some(Utc::now().naive_utc())),
+                },
+            )?;
+        }
+        context.insert("state", "done");
+        Ok(html_page_response("unsubscribe", &context, StatusCode::OK))
+    })
+    .await?)
+}
+
+pub async fn get_all_subscribers(
+    _: AdminClaimsContext,
+    query: GetAllNewsletterSubscribersQuery,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    block_in_place(|| {
+        let conn = repo.get_conn()?;
+        let filter = GetAllNewsletterSubscribersFilter::from_query(query.clone());
+        let subscriber_repository = NewsletterSubscriberRepo::new(&conn);
+        let pagination_opts = query.pagination_options();
+        let sort_type = query.sort_type;
+        let (subscribers, total_results) = subscriber_repository
+            .find(filter, sort_type, pagination_opts)
+            .map_err::<PgRepoError, _>(|e| e.into())?;
+        Ok(paginated_ok_response(
+            subscribers,
+            query.page,
+            query.page_size,
+            total_results,
+        ))
+    })
+}
+
+pub async fn get_issue(
+    Path(id): Path<i32>,
+    _: AdminClaimsContext,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    let issue = pg_transaction(repo, |conn| NewsletterIssueRepo::new(conn).find_one(id))
+        .await?
+        .ok_or(AppError::NotFound("Newsletter issue"))?;
+    Ok(simple_ok_response(issue))
+}
+
+pub async fn get_all_issues(
+    _: AdminClaimsContext,
+    query: GetAllNewsletterIssuesQuery,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    block_in_place(|| {
+        let conn = repo.get_conn()?;
+        let filter = GetAllNewsletterIssuesFilter::from_query(query.clone());
+        let issue_repository = NewsletterIssueRepo::new(&conn);
+        let pagination_opts = query.pagination_options();
+        let sort_type = query.sort_type;
+        let (issues, total_results) = issue_repository
+            .find(filter, sort_type, pagination_opts)
+            .map_err::<PgRepoError, _>(|e| e.into())?;
+        Ok(paginated_ok_response(
+            issues,
+            query.page,
+            query.page_size,
+            total_results,
+        ))
+    })
+}
+
+pub async fn create_issue(
+    AdminClaimsContext { claims }: AdminClaimsContext,
+    ValidatedJson(request): ValidatedJson<CreateNewsletterIssueRequest>,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    Ok(pg_transaction(repo, |conn| {
+        let blog_post_ids = match request.blog_post_ids {
+            Some(value) => value,
+            None => recent_blog_post_ids(conn)?,
+        };
+        if !published_posts_exist(conn, &blog_post_ids)? {
+            return Ok(bad_request_response("Invalid blog post id"));
+        }
+        let new_issue = NewNewsletterIssue {
+            subject: request.subject,
+            intro: request.intro,
+            blog_post_ids,
+            user_id: claims.user_id(),
+        };
+        let issue_id = NewsletterIssueRepo::new(conn).insert_one(new_issue.clone())?;
+        match create_creation_admin_log(
+            issue_id.to_string(),
+            claims.user_id(),
+            String::from("Newsletter Issue"),
+            String::from("newsletter_issues"),
+            &new_issue,
+            String::from("/newsletter-issues"),
+            conn,
+        ) {
+            Ok(_) => {}
+            Err(_) => {
+                return Err(get_roll_back_err());
+            }
+        };
+        Ok(simple_created_response(issue_id))
+    })
+    .await?)
+}
+
+pub async fn update_issue(
+    Path(id): Path<i32>,
+    AdminClaimsContext { claims }: AdminClaimsContext,
+    ValidatedJson(request): ValidatedJson<UpdateNewsletterIssueRequest>,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    Ok(pg_transaction(repo, |conn| {
+        let issue_repository = NewsletterIssueRepo::new(conn);
+        let issue = match issue_repository.find_one(id)? {
+            Some(value) => value,
+            None => {
+                return Ok(not_found_response("Newsletter issue"));
+            }
+        };
+        if issue.sent_at.is_some() {
+            return Ok(bad_request_response("Issue was already sent"));
+        }
+        if let Some(blog_post_ids) = &request.blog_post_ids {
+            if !published_posts_exist(conn, blog_post_ids)? {
+                return Ok(bad_request_response("Invalid blog post id"));
+            }
+        }
+        let request_copy = request.clone();
+        issue_repository.update_one(
+            id,
+            UpdateNewsletterIssue {
+                subject: request.subject,
+                intro: request.intro,
+                blog_post_ids: request.blog_post_ids,
+                updated_at: Some(Some(Utc::now().naive_utc())),
+                sent_at: None,
+            },
+        )?;
+        match create_update_admin_log(
+            id.to_string(),
+            claims.user_id(),
+            String::from("Newsletter Issue"),
+            String::from("newsletter_issues"),
+            &request_copy,
+            &issue,
+            String::from("/newsletter-issues"),
+            conn,
+        ) {
+            Ok(_) => {}
+            Err(_) => {
+                return Err(get_roll_back_err());
+            }
+        };
+        Ok(simple_ok_response(issue_repository.find_one(id)?))
+    })
+    .await?)
+}
+
+/// Schedules a delivery for every active subscriber. The newsletter worker queues
+/// them in batches afterwards.
+pub async fn send_issue(
+    Path(id): Path<i32>,
+    AdminClaimsContext { claims }: AdminClaimsContext,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    Ok(pg_transaction(repo, |conn| {
+        let issue_repository = NewsletterIssueRepo::new(conn);
+        let issue = match issue_repository.find_one(id)? {
+            Some(value) => value,
+            None => {
+                return Ok(not_found_response("Newsletter issue"));
+            }
+        };
+        if issue.sent_at.is_some() {
+            return Ok(bad_request_response("Issue was already sent"));
+        }
+        let updates = UpdateNewsletterIssue {
+            subject: None,
+            intro: None,
+            blog_post_ids: None,
+            updated_at: None,
+            sent_at: Some(Some(Utc::now().naive_utc())),
+        };
+        issue_repository.update_one(id, updates.clone())?;
+        let deliveries = NewsletterDeliveryRepo::new(conn).insert_for_active_subscribers(id)?;
+        match create_update_admin_log(
+            id.to_string(),
+            claims.user_id(),
+            String::from("Newsletter Issue"),
+            String::from("newsletter_issues"),
+            &updates,
+            &issue,
+            String::from("/newsletter-issues"),
+            conn,
+        ) {
+            Ok(_) => {}
+            Err(_) => {
+                return Err(get_roll_back_err());
+            }
+        };
+        Ok(simple_ok_response(deliveries))
+    })
+    .await?)
+}
+
+pub async fn get_all_deliveries(
+    Path(id): Path<i32>,
+    _: AdminClaimsContext,
+    query: GetAllNewsletterDeliveriesQuery,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    block_in_place(|| {
+        let conn = repo.get_conn()?;
+        let filter = GetAllNewsletterDeliveriesFilter::from_query(id, query.clone());
+        let delivery_repository = NewsletterDeliveryRepo::new(&conn);
+        let pagination_opts = query.pagination_options();
+        let (deliveries, total_results) = delivery_repository
+            .find(filter, pagination_opts)
+            .map_err::<PgRepoError, _>(|e| e.into())?;
+        Ok(paginated_ok_response(
+            deliveries,
+            query.page,
+            query.page_size,
+            total_results,
+        ))
+    })
+}
+
+/// Posts published since the last issue went out.
+fn recent_blog_post_ids(conn: &RepoConnection) -> QueryResult<Vec<i32>> {
+    let since = match NewsletterIssueRepo::new(conn).find_last_sent()? {
+        Some(issue) => issue.sent_at,
+        None => None,
+    }
+    .unwrap_or_else(|| Utc::now().naive_utc() - Duration::days(DEFAULT_ISSUE_DAYS));
+    let filter = GetAllBlogPostsFilter {
+        author_id: None,
+        category_id: None,
+        category_name: None,
+        category_ids: None,
+        published: Some(true),
+        published_after: Some(since),
+    };
+    let (posts, _) = BlogPostRepo::new(conn).find(
+        filter,
+        Some(BlogPostSortType::CreatedAtDesc),
+        PaginationOptions {
+            page: None,
+            page_size: None,
+        },
+    )?;
+    Ok(posts.into_iter().map(|post| post.id).collect())
+}
+
+fn published_posts_exist(conn: &RepoConnection, blog_post_ids: &[i32]) -> QueryResult<bool> {
+    let blog_post_repository = BlogPostRepo::new(conn);
+    for id in blog_post_ids {
+        match blog_post_repository.find_one(*id)? {
+            Some(post) if post.published => {}
+            _ => return Ok(false),
+        }
+    }
+    Ok(true)
+}
+
+fn generate_newsletter_token() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .map(char::from)
+        .take(75)
+        .collect::<String>()
+}
@@ -0,0 +1,136 @@
+use crate::{
+    app::KEYS,
+    auth_tokens::decode_unsubscribe_token,
+    errors::AppError,
+    extractors::{ClaimsContext, ValidatedJson},
+    util::{html_page_response, simple_ok_response},
+};
+use axum::{extract::Extension, http::StatusCode, response::IntoResponse};
+use backend_repo_pg::{
+    errors::PgRepoError,
+    insertables::NewNotificationPreferences,
+    models::{
+        domain::NotificationKind, queries::UnsubscribeQuery,
+        requests::UpdateNotificationPreferencesRequest,
+    },
+    notification_preferences::NotificationPreferencesRepo,
+    pg_util::{pg_transaction, DynRepo},
+    users::UserRepo,
+};
+use tera::Context;
+use tokio::task::block_in_place;
+
+pub async fn get_preferences(
+    ClaimsContext { claims }: ClaimsContext,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    block_in_place(|| {
+        let conn = repo.get_conn()?;
+        let preferences_repository = NotificationPreferencesRepo::new(&conn);
+        let preferences = preferences_repository
+            .find_one_by_user(claims.user_id())
+            .map_err::<PgRepoError, _>(|e| e.into())?;
+        Ok(simple_ok_response(preferences))
+    })
+}
+
+pub async fn update_preferences(
+    ClaimsContext { claims }: ClaimsContext,
+    ValidatedJson(request): ValidatedJson<UpdateNotificationPreferencesRequest>,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    Ok(pg_transaction(repo, |conn| {
+        let preferences_repository = NotificationPreferencesRepo::new(conn);
+        let current = preferences_repository.find_one_by_user(claims.user_id())?;
+        let preferences = preferences_repository.upsert(NewNotificationPreferences {
+            user_id: claims.user_id(),
+            comment_replies: request.comment_replies.unwrap_or(current.comment_replies),
+            moderation_decisions: request
+                .moderation_decisions
+                .unwrap_or(current.moderation_decisions),
+            new_posts_digest: request.new_posts_digest.unwrap_or(current.new_posts_digest),
+        })?;
+        Ok(simple_ok_response(preferences))
+    })
+    .await?)
+}
+
+fn describe(kind: NotificationKind) -> &'static str {
+    match kind {
+        NotificationKind::CommentReplies => "emails about replies to your comments",
+        NotificationKind::ModerationDecisions => "emails about moderation of your comments",
+        NotificationKind::NewPostsDigest => "digests of new posts",
+    }
+}
+
+/// Asks for confirmation, so that link scanners opening the email link don't turn the
+/// emails off.
+pub async fn unsubscribe_page(
+    query: UnsubscribeQuery,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    let mut context = Context::new();
+    let claims = match decode_unsubscribe_token(&KEYS.decoding, &query.token) {
+        Ok(value) => value,
+        Err(_) => {
+            context.insert("state", "invalid");
+            return Ok(html_page_response(
+                "unsubscribe",
+                &context,
+                StatusCode::BAD_REQUEST,
+            ));
+        }
+    };
+    block_in_place(|| {
+        let conn = repo.get_conn()?;
+        let user = UserRepo::new(&conn)
+            .find_one(claims.sub)
+            .map_err::<PgRepoError, _>(|e| e.into())?;
+        if user.is_none() {
+            context.insert("state", "invalid");
+            return Ok(html_page_response(
+                "unsubscribe",
+                &context,
+                StatusCode::NOT_FOUND,
+            ));
+        }
+        context.insert("state", "confirm");
+        context.insert("what", describe(claims.kind));
+        Ok(html_page_response("unsubscribe", &context, StatusCode::OK))
+    })
+}
+
+/// Handles both the confirmation form and the one-click `List-Unsubscribe-Post`
+/// request that mail clients send.
+pub async fn unsubscribe(
+    query: UnsubscribeQuery,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    let mut context = Context::new();
+    let claims = match decode_unsubscribe_token(&KEYS.decoding, &query.token) {
+        Ok(value) => value,
+        Err(_) => {
+            context.insert("state", "invalid");
+            return Ok(html_page_response(
+                "unsubscribe",
+                &context,
+                StatusCode::BAD_REQUEST,
+            ));
+        }
+    };
+    Ok(pg_transaction(repo, |conn| {
+        if UserRepo::new(conn).find_one(claims.sub)?.is_none() {
+            context.insert("state", "invalid");
+            return Ok(html_page_response(
+                "unsubscribe",
+                &context,
+                StatusCode::NOT_FOUND,
+            ));
+        }
+        NotificationPreferencesRepo::new(conn).disable(claims.sub, claims.kind)?;
+        context.insert("state", "done");
+        context.insert("what", describe(claims.kind));
+        Ok(html_page_response("unsubscribe", &context, StatusCode::OK))
+    })
+    .await?)
+}
@@ -1,35 +1,48 @@
+use std::convert::Infallible;
 use std::net::SocketAddr;
+use std::time::Duration as StdDuration;
 
-use crate::errors::{AppError, GeolocError};
-use crate::extractors::{OptClaimsContext, ValidatedJson, ValidatedQuery};
+use crate::app::DynGeolocation;
+use crate::bots::is_bot;
+use crate::errors::AppError;
+use crate::extractors::{AdminClaimsContext, OptClaimsContext, ValidatedJson, ValidatedQuery};
+use crate::live_views::LiveViews;
+use crate::privacy::{daily_visitor_hash, new_salt, opted_out, AnalyticsSettings};
+use crate::referrers::{page_path, parse_referrer, split_campaign};
 use crate::util::bad_request_response;
 use crate::util::{server_error_response, simple_created_response, simple_ok_response};
 use axum::extract::{ConnectInfo, Extension, Path, TypedHeader};
+use axum::response::sse::{Event, KeepAlive, Sse};
 use axum::response::IntoResponse;
-use backend_repo_pg::models::responses::GeolocationDbResponse;
 use backend_repo_pg::pg_util::{pg_transaction, DynRepo};
 use backend_repo_pg::{
-    identification_cookies::IdentificationCookieRepo, models::requests::CreatePageViewRequest,
-    page_views::PageViewRepo,
+    daily_salts::DailySaltRepo, identification_cookies::IdentificationCookieRepo,
+    models::requests::CreatePageViewRequest, page_views::PageViewRepo, visits::VisitRepo,
 };
 use backend_repo_pg::{
-    insertables::{NewIdentificationCookie, NewPageView},
-    models::queries::GetPageViewsQuery,
+    errors::PgRepoError,
+    filters::PageViewAnalyticsFilter,
+    insertables::{NewIdentificationCookie, NewPageView, NewVisit},
+    models::domain::ActiveVisitors,
+    models::queries::{GetPageViewAnalyticsQuery, GetPageViewsQuery},
+    options::ReportInterval,
 };
-use headers::UserAgent;
-use hyper::body::HttpBody;
-use hyper::client::connect::dns::GaiResolver;
-use hyper::client::HttpConnector;
-use hyper::Response;
-use hyper::{Body, Client};
-use hyper_tls::HttpsConnector;
+use futures::stream::{self, Stream};
+use headers::{HeaderMap, Origin, UserAgent};
 use rand::{distributions::Alphanumeric, Rng};
 use sha2::{Digest, Sha512};
 use time::{Duration, OffsetDateTime};
+use tokio::sync::broadcast::error::RecvError;
 use tokio::task::block_in_place;
 use tower_cookies::{Cookie, Cookies};
 use urlencoding::decode;
 
+const DEFAULT_REPORT_LIMIT: i64 = 20;
+/// A view more than this long after the last one starts a new visit.
+const VISIT_TIMEOUT_MINUTES: i64 = 30;
+/// How often live subscribers get the active visitor count when nothing is viewed.
+const ACTIVE_VISITORS_INTERVAL_SECS: u64 = 30;
+
 pub async fn get(
     Path(url): Path<String>,
     ValidatedQuery(_query): ValidatedQuery<GetPageViewsQuery>,
@@ -54,123 +67,197 @@ pub async fn get(
     })
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn create(
     TypedHeader(user_agent): TypedHeader<UserAgent>,
+    origin: Option<TypedHeader<Origin>>,
+    headers: HeaderMap,
     cookies: Cookies,
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
     OptClaimsContext { claims }: OptClaimsContext,
     ValidatedJson(request): ValidatedJson<CreatePageViewRequest>,
     Extension(repo): Extension<DynRepo>,
+    Extension(geolocation): Extension<DynGeolocation>,
+    Extension(analytics): Extension<AnalyticsSettings>,
+    Extension(live_views): Extension<LiveViews>,
 ) -> Result<impl IntoResponse, AppError> {
+    // Visitors asking not to be tracked are counted without cookies, a precise
+    // location or their user agent
+    let cookieless = analytics.cookieless || opted_out(&headers);
     let id_cookie = cookies.get("identifier").map(|c| c.value().to_string());
-    let user_agent = Some(user_agent.to_string());
-    let https = hyper_tls::HttpsConnector::new();
-    let geoloc_url = format!("https://geolocation-db.com/json/{}", addr.ip());
-    println!("{}", geoloc_url);
-    let geoloc_uri = geoloc_url.parse().map_err(GeolocError::new)?;
-
-    let client: Client<HttpsConnector<HttpConnector<GaiResolver>>, Body> =
-        Client::builder().build(https);
-    let resp: Response<Body> = client.get(geoloc_uri).await.map_err(GeolocError::from)?;
-
-    let body: Vec<u8> = match resp.into_body().data().await {
-        Some(Ok(v)) => v.to_vec(),
-        _ => {
-            return Ok(server_error_response(GeolocError::new(
-                "Failed to read Geoloc Response",
-            )));
-        }
-    };
-    let geoloc: GeolocationDbResponse = serde_json::from_slice(&body).map_err(GeolocError::from)?;
-
-    let latitude: Option<f64> = geoloc.latitude.parse().ok();
-    let longitude: Option<f64> = geoloc.latitude.parse().ok();
-    let country_code: Option<String> = if geoloc.country_code.is_empty() {
-        None
+    if cookieless && id_cookie.is_some() {
+        let mut cookie = Cookie::named("identifier");
+        cookie.set_path("/");
+        cookies.remove(cookie);
+    }
+    let user_agent = user_agent.to_string();
+    // An address that can't be located is recorded without a location
+    let location = geolocation.lookup(addr.ip()).unwrap_or_default();
+    let (latitude, longitude) = if cookieless {
+        (None, None)
     } else {
-        Some(geoloc.country_code)
+        (location.latitude, location.longitude)
     };
+    let country_code = location.country_code;
+    // Links between our own pages are not referrals
+    let site_host = origin.map(|TypedHeader(origin)| origin.hostname().to_string());
+    let referrer = request
+        .referrer
+        .as_deref()
+        .and_then(|referrer| parse_referrer(referrer, site_host.as_deref()));
+    let (page_url, campaign) = split_campaign(&request.page_url);
 
-    Ok(pg_transaction(repo, |conn| {
+    let mut recorded_view = None;
+    let response = pg_transaction(repo, |conn| {
+        let now = chrono::Utc::now().naive_utc();
         let mut return_cookie = None;
 
         let identification_cookies_repository = IdentificationCookieRepo::new(conn);
 
         let id_hash;
 
-        let id_cookie_result = if let Some(token) = &id_cookie {
-            match identification_cookies_repository.find_one_by_token(token) {
+        if cookieless {
+            let salt = match DailySaltRepo::new(conn).find_or_insert(now.date(), new_salt()) {
                 Err(err) => {
                     return Ok(server_error_response(err));
                 }
-                Ok(value_opt) => value_opt,
-            }
-        } else {
-            None
-        };
-
-        if let Some(id_cookie) = id_cookie_result {
-            id_hash = id_cookie.id_hash;
+                Ok(value) => value,
+            };
+            id_hash = daily_visitor_hash(&salt, addr.ip(), &user_agent);
         } else {
-            let mut hasher = Sha512::new();
-            let hash_string = format!(
-                "{}{}{}",
-                latitude.unwrap_or(200.),
-                longitude.unwrap_or(200.),
-                addr
-            );
-            hasher.update(hash_string);
-
-            let hash_result = hasher.finalize();
-            let new_id_hash = format!("{:x}", hash_result);
-
-            let existing_cookie =
-                match identification_cookies_repository.find_one_by_hash(new_id_hash.clone()) {
+            let id_cookie_result = if let Some(token) = &id_cookie {
+                match identification_cookies_repository.find_one_by_token(token) {
                     Err(err) => {
                         return Ok(server_error_response(err));
                     }
-                    Ok(value) => value,
-                };
+                    Ok(value_opt) => value_opt,
+                }
+            } else {
+                None
+            };
 
-            return_cookie = if let Some(cookie) = existing_cookie {
-                id_hash = new_id_hash;
-                Some(cookie.token)
+            if let Some(id_cookie) = id_cookie_result {
+                id_hash = id_cookie.id_hash;
             } else {
-                let token = rand::thread_rng()
-                    .sample_iter(&Alphanumeric)
-                    .map(char::from)
-                    .take(35)
-                    .collect::<String>();
-
-                let new_cookie = NewIdentificationCookie {
-                    token: token.clone(),
-                    id_hash: new_id_hash.clone(),
-                    expires_at: (chrono::Utc::now() + chrono::Duration::days(6 * 30)).naive_utc(),
-                };
-
-                match identification_cookies_repository.insert_one(new_cookie) {
-                    Err(err) => {
-                        return Ok(server_error_response(err));
-                    }
-                    Ok(value) => value,
-                };
+                let mut hasher = Sha512::new();
+                let hash_string = format!(
+                    "{}{}{}",
+                    latitude.unwrap_or(200.),
+                    longitude.unwrap_or(200.),
+                    addr
+                );
+                hasher.update(hash_string);
+
+                let hash_result = hasher.finalize();
+                let new_id_hash = format!("{:x}", hash_result);
+
+                let existing_cookie =
+                    match identification_cookies_repository.find_one_by_hash(new_id_hash.clone()) {
+                        Err(err) => {
+                            return Ok(server_error_response(err));
+                        }
+                        Ok(value) => value,
+                    };
+
+                return_cookie = if let Some(cookie) = existing_cookie {
+                    id_hash = new_id_hash;
+                    Some(cookie.token)
+                } else {
+                    let token = rand::thread_rng()
+                        .sample_iter(&Alphanumeric)
+                        .map(char::from)
+                        .take(35)
+                        .collect::<String>();
+
+                    let new_cookie = NewIdentificationCookie {
+                        token: token.clone(),
+                        id_hash: new_id_hash.clone(),
+                        expires_at: (chrono::Utc::now() + chrono::Duration::days(6 * 30))
+                            .naive_utc(),
+                    };
+
+                    match identification_cookies_repository.insert_one(new_cookie) {
+                        Err(err) => {
+                            return Ok(server_error_response(err));
+                        }
+                        Ok(value) => value,
+                    };
 
-                id_hash = new_id_hash;
+                    id_hash = new_id_hash;
 
-                Some(token)
+                    Some(token)
+                }
             }
         }
 
+        let pages_views_repository = PageViewRepo::new(conn);
+        let views_in_last_minute = match pages_views_repository
+            .count_recent_by_id_hash(&id_hash, now - chrono::Duration::minutes(1))
+        {
+            Err(err) => {
+                return Ok(server_error_response(err));
+            }
+            Ok(value) => value,
+        };
+        let is_bot = is_bot(Some(&user_agent), views_in_last_minute);
+
+        let visits_repository = VisitRepo::new(conn);
+        let current_visit = match visits_repository.find_current(
+            &id_hash,
+            now - chrono::Duration::minutes(VISIT_TIMEOUT_MINUTES),
+        ) {
+            Err(err) => {
+                return Ok(server_error_response(err));
+            }
+            Ok(value) => value,
+        };
+        let visit_id = if let Some(visit_id) = current_visit {
+            if let Err(err) = visits_repository.add_view(visit_id, &page_url, now) {
+                return Ok(server_error_response(err));
+            }
+            visit_id
+        } else {
+            let new_visit = NewVisit {
+                id_hash: id_hash.clone(),
+                landing_page: page_url.clone(),
+                exit_page: page_url.clone(),
+            };
+            match visits_repository.insert_one(new_visit) {
+                Err(err) => {
+                    return Ok(server_error_response(err));
+                }
+                Ok(value) => value,
+            }
+        };
+
+        let (referrer, referrer_source, referrer_type) = match referrer {
+            Some(referrer) => (
+                Some(referrer.url),
+                Some(referrer.source),
+                Some(referrer.r#type),
+            ),
+            None => (None, None, None),
+        };
         let new_view = NewPageView {
             id_hash,
-            page_url: request.page_url,
+            page_path: page_path(&page_url).to_string(),
+            page_url,
             registered: claims.is_some(),
-            user_agent,
+            user_agent: if cookieless { None } else { Some(user_agent) },
             latitude,
             longitude,
             country_code,
+            visit_id: Some(visit_id),
+            referrer,
+            referrer_source,
+            referrer_type,
+            utm_source: campaign.source,
+            utm_medium: campaign.medium,
+            utm_campaign: campaign.campaign,
+            utm_term: campaign.term,
+            utm_content: campaign.content,
+            is_bot,
         };
-        let pages_views_repository = PageViewRepo::new(conn);
         let view_insert_result = match pages_views_repository.insert_one(new_view) {
             Err(err) => {
                 return Ok(server_error_response(err));
@@ -178,6 +265,7 @@ pub async fn create(
             Ok(value) => value,
         };
 
+        recorded_view = Some(view_insert_result.clone());
         let resp = simple_created_response(view_insert_result);
 
         if let Some(token) = return_cookie {
@@ -191,5 +279,148 @@ pub async fn create(
         }
         Ok(resp)
     })
-    .await?)
+    .await?;
+
+    // Only views that were committed are shown live
+    if let Some(view) = recorded_view {
+        live_views.publish(&view);
+    }
+    Ok(response)
+}
+
+pub async fn get_traffic(
+    _: AdminClaimsContext,
+    query: GetPageViewAnalyticsQuery,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    block_in_place(|| {
+        let conn = repo.get_conn()?;
+        let stats = PageViewRepo::new(&conn)
+            .find_traffic(
+                &PageViewAnalyticsFilter::from_query(&query),
+                query.interval.unwrap_or(ReportInterval::Day),
+            )
+            .map_err::<PgRepoError, _>(|e| e.into())?;
+        Ok(simple_ok_response(stats))
+    })
+}
+
+pub async fn get_top_pages(
+    _: AdminClaimsContext,
+    query: GetPageViewAnalyticsQuery,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    block_in_place(|| {
+        let conn = repo.get_conn()?;
+        let stats = PageViewRepo::new(&conn)
+            .find_top_pages(
+                &PageViewAnalyticsFilter::from_query(&query),
+                query.limit.unwrap_or(DEFAULT_REPORT_LIMIT),
+            )
+            .map_err::<PgRepoError, _>(|e| e.into())?;
+        Ok(simple_ok_response(stats))
+    })
+}
+
+pub async fn get_top_countries(
+    _: AdminClaimsContext,
+    query: GetPageViewAnalyticsQuery,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    block_in_place(|| {
+        let conn = repo.get_conn()?;
+        let stats = PageViewRepo::new(&conn)
+            .find_top_countries(
+                &PageViewAnalyticsFilter::from_query(&query),
+                query.limit.unwrap_or(DEFAULT_REPORT_LIMIT),
+            )
+            .map_err::<PgRepoError, _>(|e| e.into())?;
+        Ok(simple_ok_response(stats))
+    })
+}
+
+pub async fn get_visitor_types(
+    _: AdminClaimsContext,
+    query: GetPageViewAnalyticsQuery,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    block_in_place(|| {
+        let conn = repo.get_conn()?;
+        let stats = PageViewRepo::new(&conn)
+            .find_visitor_types(&PageViewAnalyticsFilter::from_query(&query))
+            .map_err::<PgRepoError, _>(|e| e.into())?;
+        Ok(simple_ok_response(stats))
+    })
+}
+
+pub async fn get_top_referrers(
+    _: AdminClaimsContext,
+    query: GetPageViewAnalyticsQuery,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    block_in_place(|| {
+        let conn = repo.get_conn()?;
+        let stats = PageViewRepo::new(&conn)
+            .find_top_referrers(
+                &PageViewAnalyticsFilter::from_query(&query),
+                query.limit.unwrap_or(DEFAULT_REPORT_LIMIT),
+            )
+            .map_err::<PgRepoError, _>(|e| e.into())?;
+        Ok(simple_ok_response(stats))
+    })
+}
+
+pub async fn get_top_campaigns(
+    _: AdminClaimsContext,
+    query: GetPageViewAnalyticsQuery,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    block_in_place(|| {
+        let conn = repo.get_conn()?;
+        let stats = PageViewRepo::new(&conn)
+            .find_top_campaigns(
+                &PageViewAnalyticsFilter::from_query(&query),
+                query.limit.unwrap_or(DEFAULT_REPORT_LIMIT),
+            )
+            .map_err::<PgRepoError, _>(|e| e.into())?;
+        Ok(simple_ok_response(stats))
+    })
+}
+
+/// Streams page views as they are recorded, each with the active visitor count, and
+/// the count alone when nothing is viewed for a while. Browsers' `EventSource` can't
+/// send the bearer token, so clients read the stream with `fetch`.
+pub async fn live(
+    _: AdminClaimsContext,
+    Extension(live_views): Extension<LiveViews>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let receiver = live_views.subscribe();
+    let ticker = tokio::time::interval(StdDuration::from_secs(ACTIVE_VISITORS_INTERVAL_SECS));
+    let events = stream::unfold(
+        (live_views, receiver, ticker),
+        |(live_views, mut receiver, mut ticker)| async move {
+            let event = loop {
+                tokio::select! {
+                    received = receiver.recv() => match received {
+                        Ok(view) => break Event::default().event("page-view").json_data(view),
+                        // Views missed while falling behind are skipped
+                        Err(RecvError::Lagged(_)) => continue,
+                        Err(RecvError::Closed) => return None,
+                    },
+                    _ = ticker.tick() => {
+                        let active = ActiveVisitors {
+                            active_visitors: live_views.active_visitors() as i64,
+                        };
+                        break Event::default().event("active-visitors").json_data(active);
+                    }
+                }
+            };
+            let event = match event {
+                Ok(event) => event,
+                Err(err) => Event::default().comment(err.to_string()),
+            };
+            Some((Ok(event), (live_views, receiver, ticker)))
+        },
+    );
+    Sse::new(events).keep_alive(KeepAlive::default())
 }
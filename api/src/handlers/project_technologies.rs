@@ -1,6 +1,7 @@
 use crate::errors::AppError;
 use crate::extractors::AdminClaimsContext;
 use crate::extractors::ValidatedJson;
+use crate::util::bad_request_response;
 use crate::util::create_creation_admin_log;
 use crate::util::create_deletion_admin_log;
 use crate::util::create_update_admin_log;
@@ -12,13 +13,17 @@ use axum::extract::Extension;
 use axum::extract::Path;
 use axum::response::IntoResponse;
 use backend_repo_pg::errors::PgRepoError;
+use backend_repo_pg::models::domain::TechnologyDetail;
+use backend_repo_pg::models::queries::GetTechnologyQuery;
 use backend_repo_pg::models::queries::PaginatedQuery;
+use backend_repo_pg::options::ProjectSortType;
 use backend_repo_pg::pg_util::pg_transaction;
 use backend_repo_pg::pg_util::DynRepo;
+use backend_repo_pg::projects::ProjectRepo;
 use backend_repo_pg::technologies::TechnologyRepo;
 use backend_repo_pg::{
     change_sets::UpdateTechnology,
-    filters::GetAllTechnologiesFilter,
+    filters::{GetAllProjectsFilter, GetAllTechnologiesFilter},
     insertables::NewTechnology,
     models::{
         queries::GetAllTechnologiesQuery,
@@ -28,22 +33,66 @@ use backend_repo_pg::{
 use tokio::task::block_in_place;
 
 pub async fn get(
-    Path(id): Path<i32>,
+    Path(id): Path<String>,
+    query: GetTechnologyQuery,
     Extension(repo): Extension<DynRepo>,
 ) -> Result<impl IntoResponse, AppError> {
     block_in_place(|| {
         let conn = repo.get_conn()?;
         let technology_repository = TechnologyRepo::new(&conn);
-        let technology_result = match technology_repository
-            .find_one(id)
-            .map_err::<PgRepoError, _>(|e| e.into())?
-        {
+        let technology_result = if let Some(true) = query.use_slug {
+            technology_repository
+                .find_one_by_slug(id)
+                .map_err::<PgRepoError, _>(|e| e.into())?
+        } else {
+            let id = match id.parse::<i32>() {
+                Ok(v) => v,
+                Err(_) => {
+                    return Ok(bad_request_response("Url: Bad Id value"));
+                }
+            };
+            technology_repository
+                .find_one(id)
+                .map_err::<PgRepoError, _>(|e| e.into())?
+        };
+        let technology_result = match technology_result {
             None => {
                 return Ok(not_found_response("Technology"));
             }
             Some(value) => value,
         };
-        Ok(simple_ok_response(technology_result))
+        let parent = match technology_result.parent_id {
+            Some(parent_id) => technology_repository
+                .find_one(parent_id)
+                .map_err::<PgRepoError, _>(|e| e.into())?,
+            None => None,
+        };
+        let children = technology_repository
+            .find_children(technology_result.id)
+            .map_err::<PgRepoError, _>(|e| e.into())?;
+        let technology_ids = technology_repository
+            .find_subtree_ids(technology_result.id)
+            .map_err::<PgRepoError, _>(|e| e.into())?;
+        let filter = GetAllProjectsFilter {
+            technology_id: None,
+            technology_name: None,
+            technology_ids: Some(technology_ids),
+            published: Some(true),
+        };
+        let (projects, project_count) = ProjectRepo::new(&conn)
+            .find(
+                filter,
+                Some(ProjectSortType::CreatedAtDesc),
+                query.pagination_options(),
+            )
+            .map_err::<PgRepoError, _>(|e| e.into())?;
+        Ok(simple_ok_response(TechnologyDetail {
+            technology: technology_result,
+            parent,
+            children,
+            projects,
+            project_count,
+        }))
     })
 }
 
@@ -135,7 +184,27 @@ pub async fn update(
                 }
             },
         };
-        let updated_technology = UpdateTechnology { name: request.name };
+        if let Some(Some(parent_id)) = request.parent_id {
+            let nested_ids = match technology_repository.find_subtree_ids(id) {
+                Err(err) => {
+                    return Ok(server_error_response(err));
+                }
+                Ok(value) => value,
+            };
+            if nested_ids.contains(&parent_id) {
+                return Ok(bad_request_response(
+                    "Parent: Can't be the technology or one nested under it",
+                ));
+            }
+        }
+        let updated_technology = UpdateTechnology {
+            name: request.name,
+            slug: request.slug,
+            description: request.description,
+            icon: request.icon,
+            color: request.color,
+            parent_id: request.parent_id,
+        };
         let technology_result = match technology_repository.update_one(id, &updated_technology) {
             Err(err) => {
                 return Ok(server_error_response(err));
@@ -168,7 +237,14 @@ pub async fn create(
     Extension(repo): Extension<DynRepo>,
 ) -> Result<impl IntoResponse, AppError> {
     Ok(pg_transaction(repo, |conn| {
-        let new_technology = NewTechnology { name: request.name };
+        let new_technology = NewTechnology {
+            name: request.name,
+            slug: request.slug,
+            description: request.description,
+            icon: request.icon,
+            color: request.color,
+            parent_id: request.parent_id,
+        };
         let new_technology_copy = new_technology.clone();
         let technology_repository = TechnologyRepo::new(conn);
         let technology_result = match technology_repository.insert_one(&new_technology) {
@@ -14,12 +14,16 @@ use axum::extract::Extension;
 use axum::extract::Path;
 use axum::response::IntoResponse;
 use backend_repo_pg::errors::PgRepoError;
+use backend_repo_pg::extra::SearchItemType;
 use backend_repo_pg::models::queries::GetProjectQuery;
+use backend_repo_pg::models::queries::GetRelatedItemsQuery;
 use backend_repo_pg::models::queries::PaginatedQuery;
 use backend_repo_pg::pg_util::get_roll_back_err;
 use backend_repo_pg::pg_util::pg_transaction;
 use backend_repo_pg::pg_util::DynRepo;
 use backend_repo_pg::projects::ProjectRepo;
+use backend_repo_pg::related_items::RelatedItemRepo;
+use backend_repo_pg::text_search::detect_language;
 use backend_repo_pg::{
     change_sets::UpdateProject,
     filters::GetAllProjectsFilter,
@@ -32,6 +36,8 @@ use backend_repo_pg::{
 use chrono::Utc;
 use tokio::task::block_in_place;
 
+const DEFAULT_RELATED: i64 = 5;
+
 pub async fn get(
     Path(id): Path<String>,
     GetProjectQuery { use_slug }: GetProjectQuery,
@@ -77,6 +83,31 @@ pub async fn get(
     })
 }
 
+/// Other published projects related to this one, most related first.
+pub async fn get_related(
+    Path(id): Path<String>,
+    query: GetRelatedItemsQuery,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    block_in_place(|| {
+        let conn = repo.get_conn()?;
+        let id = match id.parse::<i32>() {
+            Ok(v) => v,
+            Err(_) => {
+                return Ok(bad_request_response("Url: Bad Id value"));
+            }
+        };
+        let related = RelatedItemRepo::new(&conn)
+            .find(
+                SearchItemType::Project,
+                id,
+                query.limit.unwrap_or(DEFAULT_RELATED),
+            )
+            .map_err::<PgRepoError, _>(|e| e.into())?;
+        Ok(simple_ok_response(related))
+    })
+}
+
 pub async fn get_all(
     query: GetAllProjectsQuery,
     OptClaimsContext { claims }: OptClaimsContext,
@@ -166,6 +197,7 @@ pub async fn update(
             name: request.name,
             published: request.published,
             slug: request.slug,
+            language: request.language,
         };
         let project_result = if let Some(technologies_list) = request.technologies {
             project_repository.update_one_with_technologies(
@@ -203,12 +235,17 @@ pub async fn create(
 ) -> Result<impl IntoResponse, AppError> {
     Ok(pg_transaction(repo, |conn| {
         let request_copy = request.clone();
+        let language = match request.language {
+            Some(value) => value,
+            None => detect_language(&request.body),
+        };
         let new_project = NewProject {
             body: request.body,
             cover_image: request.cover_image,
             name: request.name,
             description: request.description,
             slug: request.slug,
+            language,
         };
         let project_repository = ProjectRepo::new(conn);
         let project_result =
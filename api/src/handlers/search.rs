@@ -1,14 +1,49 @@
-use crate::{errors::AppError, util::paginated_ok_response};
+use crate::{
+    errors::AppError,
+    extractors::{AdminClaimsContext, ValidatedJson},
+    util::{not_found_response, search_ok_response, simple_created_response, simple_ok_response},
+};
 use axum::{extract::Extension, response::IntoResponse};
 use backend_repo_pg::{
     errors::PgRepoError,
-    filters::GetAllSearchItemsFilter,
-    models::queries::{GetAllSearchItemsQuery, PaginatedQuery},
-    pg_util::DynRepo,
+    filters::{DateRangeFilter, GetAllSearchItemsFilter},
+    insertables::{NewSearchClick, NewSearchQuery},
+    models::domain::SearchSuggestions,
+    models::queries::{
+        GetAllSearchItemsQuery, GetSearchAnalyticsQuery, GetSearchSuggestionsQuery,
+        GetTrendingQuery, PaginatedQuery,
+    },
+    models::requests::CreateSearchClickRequest,
+    options::{PaginationOptions, ReportInterval},
+    pg_util::{pg_transaction, DynRepo},
+    search_clicks::SearchClickRepo,
     search_items::SearchItemRepo,
+    search_queries::SearchQueryRepo,
+    search_refreshes::SearchRefreshRepo,
+    text_search::normalize_query,
 };
 use tokio::task::block_in_place;
 
+const DEFAULT_SUGGESTIONS: i64 = 8;
+const DEFAULT_TRENDING: i64 = 6;
+/// How far back views count towards trending.
+const TRENDING_DAYS: i64 = 7;
+const DEFAULT_REPORT_LIMIT: i64 = 20;
+
+/// The text a search is logged under. Only the first page of a finished search is
+/// logged, so paging and search as you type are not counted as separate searches.
+fn logged_query(query: &GetAllSearchItemsQuery) -> Option<String> {
+    if query.prefix.unwrap_or(false) || query.page.unwrap_or(1) > 1 {
+        return None;
+    }
+    let normalized = normalize_query(query.search_text.as_deref()?);
+    if normalized.is_empty() {
+        None
+    } else {
+        Some(normalized)
+    }
+}
+
 pub async fn get_all(
     query: GetAllSearchItemsQuery,
     Extension(repo): Extension<DynRepo>,
@@ -18,14 +53,192 @@ pub async fn get_all(
         let filter = GetAllSearchItemsFilter::from_query(query.clone());
         let search_item_repository = SearchItemRepo::new(&conn);
         let pagination_opts = query.pagination_options();
+        let facets = search_item_repository
+            .find_facets(&filter)
+            .map_err::<PgRepoError, _>(|e| e.into())?;
         let (search_items_list, total_results) = search_item_repository
-            .find(filter, None, pagination_opts)
+            .find(filter, query.sort_type.clone(), pagination_opts)
             .map_err::<PgRepoError, _>(|e| e.into())?;
-        Ok(paginated_ok_response(
+        // A search that failed to be logged is still answered
+        let search_id = match logged_query(&query) {
+            Some(logged) => match SearchQueryRepo::new(&conn).insert_one(NewSearchQuery {
+                query: logged,
+                item_type: query.r#type.clone(),
+                result_count: total_results,
+            }) {
+                Ok(value) => Some(value),
+                Err(err) => {
+                    tracing::error!("Failed to log search: {}", err);
+                    None
+                }
+            },
+            None => None,
+        };
+        Ok(search_ok_response(
             search_items_list,
+            facets,
+            search_id,
             query.page,
             query.page_size,
             total_results,
         ))
     })
 }
+
+/// The posts and projects viewed most this week, for the home page.
+pub async fn trending(
+    query: GetTrendingQuery,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    block_in_place(|| {
+        let conn = repo.get_conn()?;
+        let trending = SearchItemRepo::new(&conn)
+            .find_trending(
+                query.r#type,
+                TRENDING_DAYS,
+                query.limit.unwrap_or(DEFAULT_TRENDING),
+            )
+            .map_err::<PgRepoError, _>(|e| e.into())?;
+        Ok(simple_ok_response(trending))
+    })
+}
+
+pub async fn suggest(
+    query: GetSearchSuggestionsQuery,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    block_in_place(|| {
+        let conn = repo.get_conn()?;
+        let search_item_repository = SearchItemRepo::new(&conn);
+        let completions = search_item_repository
+            .find_completions(
+                &query.search_text,
+                query.r#type.clone(),
+                query.limit.unwrap_or(DEFAULT_SUGGESTIONS),
+            )
+            .map_err::<PgRepoError, _>(|e| e.into())?;
+        let filter = GetAllSearchItemsFilter {
+            search_text: Some(query.search_text.clone()),
+            r#type: query.r#type.clone(),
+            prefix: true,
+            category: None,
+            technology: None,
+            created_after: None,
+            created_before: None,
+            language: None,
+        };
+        let (_, total_results) = search_item_repository
+            .find(filter, None, PaginationOptions::new(Some(1), Some(1)))
+            .map_err::<PgRepoError, _>(|e| e.into())?;
+        let did_you_mean = if total_results == 0 {
+            search_item_repository
+                .find_correction(&query.search_text, query.r#type.clone())
+                .map_err::<PgRepoError, _>(|e| e.into())?
+        } else {
+            None
+        };
+        Ok(simple_ok_response(SearchSuggestions {
+            completions,
+            did_you_mean,
+        }))
+    })
+}
+
+pub async fn get_refresh(
+    _: AdminClaimsContext,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    block_in_place(|| {
+        let conn = repo.get_conn()?;
+        let search_refresh = SearchRefreshRepo::new(&conn)
+            .find_one()
+            .map_err::<PgRepoError, _>(|e| e.into())?;
+        Ok(simple_ok_response(search_refresh))
+    })
+}
+
+/// Rebuilds search right away instead of waiting for the background refresh.
+pub async fn refresh(
+    _: AdminClaimsContext,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    block_in_place(|| {
+        let conn = repo.get_conn()?;
+        let search_refresh = SearchRefreshRepo::new(&conn)
+            .refresh()
+            .map_err::<PgRepoError, _>(|e| e.into())?;
+        Ok(simple_ok_response(search_refresh))
+    })
+}
+
+/// Records which result of a logged search was followed.
+pub async fn click(
+    ValidatedJson(request): ValidatedJson<CreateSearchClickRequest>,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    Ok(pg_transaction(repo, |conn| {
+        if !SearchQueryRepo::new(conn).exists(request.search_id)? {
+            return Ok(not_found_response("Search"));
+        }
+        let click_id = SearchClickRepo::new(conn).insert_one(NewSearchClick {
+            search_query_id: request.search_id,
+            link: request.link,
+            position: request.position,
+        })?;
+        Ok(simple_created_response(click_id))
+    })
+    .await?)
+}
+
+pub async fn get_top_queries(
+    _: AdminClaimsContext,
+    query: GetSearchAnalyticsQuery,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    block_in_place(|| {
+        let conn = repo.get_conn()?;
+        let stats = SearchQueryRepo::new(&conn)
+            .find_top(
+                DateRangeFilter::from_search_analytics_query(&query),
+                false,
+                query.limit.unwrap_or(DEFAULT_REPORT_LIMIT),
+            )
+            .map_err::<PgRepoError, _>(|e| e.into())?;
+        Ok(simple_ok_response(stats))
+    })
+}
+
+pub async fn get_zero_result_queries(
+    _: AdminClaimsContext,
+    query: GetSearchAnalyticsQuery,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    block_in_place(|| {
+        let conn = repo.get_conn()?;
+        let stats = SearchQueryRepo::new(&conn)
+            .find_top(
+                DateRangeFilter::from_search_analytics_query(&query),
+                true,
+                query.limit.unwrap_or(DEFAULT_REPORT_LIMIT),
+            )
+            .map_err::<PgRepoError, _>(|e| e.into())?;
+        Ok(simple_ok_response(stats))
+    })
+}
+
+pub async fn get_click_through(
+    _: AdminClaimsContext,
+    query: GetSearchAnalyticsQuery,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    block_in_place(|| {
+        let conn = repo.get_conn()?;
+        let stats = SearchQueryRepo::new(&conn)
+            .find_click_through(
+                DateRangeFilter::from_search_analytics_query(&query),
+                query.interval.unwrap_or(ReportInterval::Day),
+            )
+            .map_err::<PgRepoError, _>(|e| e.into())?;
+        Ok(simple_ok_response(stats))
+    })
+}
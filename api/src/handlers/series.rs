@@ -0,0 +1,223 @@
+use crate::errors::AppError;
+use crate::extractors::AdminClaimsContext;
+use crate::extractors::OptClaimsContext;
+use crate::extractors::ValidatedJson;
+use crate::util::bad_request_response;
+use crate::util::create_creation_admin_log;
+use crate::util::create_deletion_admin_log;
+use crate::util::create_update_admin_log;
+use crate::util::{
+    not_found_response, paginated_ok_response, simple_created_response, simple_no_content_response,
+    simple_ok_response,
+};
+use axum::extract::Extension;
+use axum::extract::Path;
+use axum::response::IntoResponse;
+use backend_repo_pg::errors::PgRepoError;
+use backend_repo_pg::models::queries::GetSeriesQuery;
+use backend_repo_pg::models::queries::PaginatedQuery;
+use backend_repo_pg::pg_util::get_roll_back_err;
+use backend_repo_pg::pg_util::pg_transaction;
+use backend_repo_pg::pg_util::DynRepo;
+use backend_repo_pg::series::SeriesRepo;
+use backend_repo_pg::text_search::detect_language;
+use backend_repo_pg::{
+    change_sets::UpdateSeries,
+    filters::GetAllSeriesFilter,
+    insertables::NewSeries,
+    models::{
+        queries::GetAllSeriesQuery,
+        requests::{CreateSeriesRequest, UpdateSeriesRequest},
+    },
+};
+use chrono::Utc;
+use tokio::task::block_in_place;
+
+pub async fn get(
+    Path(id): Path<String>,
+    GetSeriesQuery { use_slug }: GetSeriesQuery,
+    OptClaimsContext { claims }: OptClaimsContext,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    block_in_place(|| {
+        let conn = repo.get_conn()?;
+        let is_staff = match &claims {
+            Some(claims) => claims.is_staff(),
+            None => false,
+        };
+        let series_repository = SeriesRepo::new(&conn);
+        let series_result = if let Some(true) = use_slug {
+            series_repository
+                .find_one_by_slug(id, !is_staff)
+                .map_err::<PgRepoError, _>(|e| e.into())?
+        } else {
+            let id = match id.parse::<i32>() {
+                Ok(v) => v,
+                Err(_) => {
+                    return Ok(bad_request_response("Url: Bad Id value"));
+                }
+            };
+            series_repository
+                .find_one(id, !is_staff)
+                .map_err::<PgRepoError, _>(|e| e.into())?
+        };
+        let series_result = match series_result {
+            Some(value) if value.published || is_staff => value,
+            _ => {
+                return Ok(not_found_response("Series"));
+            }
+        };
+        Ok(simple_ok_response(series_result))
+    })
+}
+
+pub async fn get_all(
+    query: GetAllSeriesQuery,
+    OptClaimsContext { claims }: OptClaimsContext,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    block_in_place(|| {
+        let conn = repo.get_conn()?;
+        let is_staff = match &claims {
+            Some(claims) => claims.is_staff(),
+            None => false,
+        };
+        let mut filter = GetAllSeriesFilter::from_query(query.clone());
+        if !is_staff {
+            filter.published = Some(true);
+        }
+        let pagination_opts = query.pagination_options();
+        let (series_list, total_results) = SeriesRepo::new(&conn)
+            .find(filter, !is_staff, pagination_opts)
+            .map_err::<PgRepoError, _>(|e| e.into())?;
+        Ok(paginated_ok_response(
+            series_list,
+            query.page,
+            query.page_size,
+            total_results,
+        ))
+    })
+}
+
+pub async fn delete(
+    Path(id): Path<i32>,
+    AdminClaimsContext { claims }: AdminClaimsContext,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    Ok(pg_transaction(repo, |conn| {
+        let series_repository = SeriesRepo::new(conn);
+        let old_data = match series_repository.find_one(id, false)? {
+            None => {
+                return Ok(not_found_response("Series"));
+            }
+            Some(value) => value,
+        };
+        let series_result = series_repository.delete_one(id)?;
+        if series_result == 0 {
+            return Ok(not_found_response("Series"));
+        }
+        match create_deletion_admin_log(
+            id.to_string(),
+            claims.user_id(),
+            String::from("Series"),
+            String::from("series"),
+            &old_data,
+            String::from("/series"),
+            conn,
+        ) {
+            Ok(_) => {}
+            Err(_) => {
+                return Err(get_roll_back_err());
+            }
+        };
+        Ok(simple_no_content_response(series_result))
+    })
+    .await?)
+}
+
+pub async fn update(
+    Path(id): Path<i32>,
+    AdminClaimsContext { claims }: AdminClaimsContext,
+    ValidatedJson(request): ValidatedJson<UpdateSeriesRequest>,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    Ok(pg_transaction(repo, |conn| {
+        let series_repository = SeriesRepo::new(conn);
+        let request_copy = request.clone();
+        let old_data = match series_repository.find_one(id, false)? {
+            Some(value) => value,
+            None => {
+                return Ok(not_found_response("Series"));
+            }
+        };
+        let updated_series = UpdateSeries {
+            title: request.title,
+            slug: request.slug,
+            description: request.description,
+            published: request.published,
+            language: request.language,
+            updated_at: Some(Some(Utc::now().naive_utc())),
+        };
+        let series_result =
+            series_repository.update_one(id, &updated_series, request.blog_post_ids.as_deref())?;
+        match create_update_admin_log(
+            id.to_string(),
+            claims.user_id(),
+            String::from("Series"),
+            String::from("series"),
+            &request_copy,
+            &old_data,
+            String::from("/series"),
+            conn,
+        ) {
+            Ok(_) => {}
+            Err(_) => {
+                return Err(get_roll_back_err());
+            }
+        };
+        Ok(simple_created_response(series_result))
+    })
+    .await?)
+}
+
+pub async fn create(
+    AdminClaimsContext { claims }: AdminClaimsContext,
+    ValidatedJson(request): ValidatedJson<CreateSeriesRequest>,
+    Extension(repo): Extension<DynRepo>,
+) -> Result<impl IntoResponse, AppError> {
+    Ok(pg_transaction(repo, |conn| {
+        let request_copy = request.clone();
+        let language = match request.language {
+            Some(value) => value,
+            None => detect_language(&format!(
+                "{} {}",
+                request.title,
+                request.description.as_deref().unwrap_or("")
+            )),
+        };
+        let new_series = NewSeries {
+            title: request.title,
+            slug: request.slug,
+            description: request.description,
+            language,
+        };
+        let series_result =
+            SeriesRepo::new(conn).insert_one_with_posts(&new_series, &request.blog_post_ids)?;
+        match create_creation_admin_log(
+            series_result.id.to_string(),
+            claims.user_id(),
+            String::from("Series"),
+            String::from("series"),
+            &request_copy,
+            String::from("/series"),
+            conn,
+        ) {
+            Ok(_) => {}
+            Err(_) => {
+                return Err(get_roll_back_err());
+            }
+        };
+        Ok(simple_created_response(series_result))
+    })
+    .await?)
+}
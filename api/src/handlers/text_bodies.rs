@@ -17,6 +17,7 @@ use backend_repo_pg::pg_util::get_roll_back_err;
 use backend_repo_pg::pg_util::pg_transaction;
 use backend_repo_pg::pg_util::DynRepo;
 use backend_repo_pg::text_bodies::TextBodyRepo;
+use backend_repo_pg::text_search::detect_language;
 use backend_repo_pg::{
     change_sets::UpdateTextBody,
     filters::GetAllTextBodiesFilter,
@@ -128,6 +129,7 @@ pub async fn update(
             title: request.title,
             url_used: request.url_used,
             updated_at: Some(Utc::now().naive_utc()),
+            language: request.language,
         };
         let text_body_result =
             text_body_repository.update_one(old_entity.id, &updated_text_body)?;
@@ -157,11 +159,16 @@ pub async fn create(
     Extension(repo): Extension<DynRepo>,
 ) -> Result<impl IntoResponse, AppError> {
     Ok(pg_transaction(repo, |conn| {
+        let language = match request.language {
+            Some(value) => value,
+            None => detect_language(&request.body),
+        };
         let new_text_body = NewTextBody {
             body: request.body,
             slug: request.slug,
             title: request.title,
             url_used: request.url_used,
+            language,
         };
         let new_text_body_copy = new_text_body.clone();
         let text_body_repository = TextBodyRepo::new(conn);
@@ -1,13 +1,27 @@
 #[macro_use]
 extern crate lazy_static;
 
+pub mod analytics_retention_worker;
 pub mod app;
 pub mod auth_tokens;
+pub mod bots;
+pub mod captcha;
 pub mod db;
+pub mod digest_worker;
+pub mod email_templates;
+pub mod email_worker;
 pub mod emails;
 pub mod errors;
 pub mod extractors;
 pub mod filters;
+pub mod geolocation;
+pub mod geolocation_worker;
 pub mod handlers;
+pub mod live_views;
+pub mod newsletter_worker;
+pub mod privacy;
+pub mod referrers;
 pub mod routes;
+pub mod search_refresh_worker;
+pub mod spam;
 pub mod util;
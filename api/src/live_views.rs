@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use backend_repo_pg::models::domain::{LivePageView, PageView};
+use tokio::sync::broadcast;
+
+/// Visitors count as active for this long after their last view.
+pub const ACTIVE_WINDOW: Duration = Duration::from_secs(5 * 60);
+/// Views held for subscribers that fall behind. Past that they skip ahead.
+const CHANNEL_CAPACITY: usize = 256;
+
+/// Hands recorded page views to the admins watching live and keeps track of the
+/// visitors seen lately. Only covers views recorded by this process since it started.
+#[derive(Clone)]
+pub struct LiveViews {
+    sender: broadcast::Sender<LivePageView>,
+    last_seen: Arc<Mutex<HashMap<String, Instant>>>,
+}
+
+impl LiveViews {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        Self {
+            sender,
+            last_seen: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Counts the visitor as active, unless it is a bot, and sends the view to every
+    /// subscriber.
+    pub fn publish(&self, view: &PageView) {
+        if !view.is_bot {
+            self.seen(&view.id_hash, Instant::now());
+        }
+        let active_visitors = self.active_visitors();
+        // Nobody may be listening, which is fine
+        let _ = self
+            .sender
+            .send(LivePageView::from(view, active_visitors as i64));
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<LivePageView> {
+        self.sender.subscribe()
+    }
+
+    /// Visitors with a view in the last [`ACTIVE_WINDOW`].
+    pub fn active_visitors(&self) -> usize {
+        self.active_at(Instant::now())
+    }
+
+    fn active_at(&self, now: Instant) -> usize {
+        let mut last_seen = match self.last_seen.lock() {
+            Ok(last_seen) => last_seen,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        last_seen.retain(|_, seen_at| now.saturating_duration_since(*seen_at) < ACTIVE_WINDOW);
+        last_seen.len()
+    }
+
+    fn seen(&self, id_hash: &str, at: Instant) {
+        let mut last_seen = match self.last_seen.lock() {
+            Ok(last_seen) => last_seen,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        last_seen.insert(id_hash.to_string(), at);
+    }
+}
+
+impl Default for LiveViews {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn view(id_hash: &str, is_bot: bool) -> PageView {
+        PageView {
+            id: 1,
+            page_url: "/blog".to_string(),
+            user_agent: None,
+            latitude: None,
+            longitude: None,
+            country_code: Some("GR".to_string()),
+            id_hash: id_hash.to_string(),
+            registered: false,
+            created_at: chrono::Utc::now().naive_utc(),
+            visit_id: None,
+            referrer: None,
+            referrer_source: None,
+            referrer_type: None,
+            utm_source: None,
+            utm_medium: None,
+            utm_campaign: None,
+            utm_term: None,
+            utm_content: None,
+            is_bot,
+        }
+    }
+
+    #[test]
+    fn subscribers_get_views_with_active_count() {
+        let live_views = LiveViews::new();
+        let mut receiver = live_views.subscribe();
+        live_views.publish(&view("a", false));
+        live_views.publish(&view("a", false));
+        live_views.publish(&view("b", false));
+        live_views.publish(&view("crawler", true));
+
+        let counts: Vec<i64> = (0..4)
+            .map(|_| receiver.try_recv().unwrap().active_visitors)
+            .collect();
+        assert_eq!(counts, vec![1, 1, 2, 2]);
+    }
+
+    #[test]
+    fn visitors_stop_being_active() {
+        let live_views = LiveViews::new();
+        let start = Instant::now();
+        live_views.seen("a", start);
+        live_views.seen("b", start + Duration::from_secs(60));
+        assert_eq!(live_views.active_at(start + ACTIVE_WINDOW), 1);
+    }
+}
@@ -0,0 +1,87 @@
+use crate::emails::EmailSender;
+use backend_repo_pg::{
+    blog_posts::BlogPostRepo,
+    errors::PgRepoError,
+    models::domain::{BlogPost, NewsletterIssue},
+    newsletter_deliveries::NewsletterDeliveryRepo,
+    newsletter_issues::NewsletterIssueRepo,
+    pg_util::{pg_transaction, DynRepo},
+};
+use std::collections::{hash_map::Entry, HashMap};
+
+const BATCH_SIZE: i64 = 50;
+const POLL_INTERVAL_SECS: u64 = 30;
+
+/// Keeps moving scheduled newsletter deliveries into the outbox until the process stops.
+pub async fn run(repo: DynRepo, email_sender: EmailSender) {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(POLL_INTERVAL_SECS));
+    loop {
+        interval.tick().await;
+        if let Err(err) = queue_batch(repo.clone(), &email_sender).await {
+            tracing::error!("Newsletter delivery failed: {}", err);
+        }
+    }
+}
+
+/// Renders the next batch of deliveries into the outbox and returns how many were
+/// queued. Deliveries to addresses that unsubscribed in the meantime, or of issues
+/// that no longer exist, are dropped. Ones that fail to render are marked failed.
+pub async fn queue_batch(repo: DynRepo, email_sender: &EmailSender) -> Result<usize, PgRepoError> {
+    pg_transaction(repo, move |conn| {
+        let delivery_repository = NewsletterDeliveryRepo::new(conn);
+        let issue_repository = NewsletterIssueRepo::new(conn);
+        let blog_post_repository = BlogPostRepo::new(conn);
+        let mut issues: HashMap<i32, Option<(NewsletterIssue, Vec<BlogPost>)>> = HashMap::new();
+        let mut queued = 0;
+        for (delivery, subscriber) in delivery_repository.find_unqueued(BATCH_SIZE)? {
+            if !subscriber.confirmed || subscriber.unsubscribed_at.is_some() {
+                delivery_repository.delete_one(delivery.id)?;
+                continue;
+            }
+            if let Entry::Vacant(entry) = issues.entry(delivery.newsletter_issue_id) {
+                let issue = match issue_repository.find_one(delivery.newsletter_issue_id)? {
+                    Some(issue) => {
+                        let mut posts = Vec::new();
+                        for id in &issue.blog_post_ids {
+                            if let Some(post) = blog_post_repository.find_one(*id)? {
+                                posts.push(post);
+                            }
+                        }
+                        Some((issue, posts))
+                    }
+                    None => None,
+                };
+                entry.insert(issue);
+            }
+            let (issue, posts) = match issues.get(&delivery.newsletter_issue_id) {
+                Some(Some(value)) => value,
+                _ => {
+                    delivery_repository.delete_one(delivery.id)?;
+                    continue;
+                }
+            };
+            match email_sender.send_newsletter_issue_email(
+                conn,
+                subscriber.email,
+                &subscriber.token,
+                issue,
+                posts,
+            ) {
+                Ok(email) => {
+                    delivery_repository.set_email(delivery.id, email.id)?;
+                    queued += 1;
+                }
+                Err(err) => {
+                    tracing::error!(
+                        "Newsletter delivery {} failed: {}",
+                        delivery.id,
+                        err.to_string()
+                    );
+                    delivery_repository.set_failed(delivery.id, err.to_string())?;
+                }
+            }
+        }
+        Ok(queued)
+    })
+    .await
+}
@@ -0,0 +1,107 @@
+use std::env;
+use std::net::IpAddr;
+
+use headers::HeaderMap;
+use rand::{distributions::Alphanumeric, Rng};
+use sha2::{Digest, Sha512};
+
+/// How page views are counted and how long they are kept.
+#[derive(Debug, Clone, Default)]
+pub struct AnalyticsSettings {
+    /// Counts every visitor without cookies, not only those asking not to be tracked.
+    pub cookieless: bool,
+    /// Page views older than this many days are rolled up into daily totals.
+    pub retention_days: Option<i64>,
+}
+
+impl AnalyticsSettings {
+    pub fn from_env() -> Self {
+        let cookieless = matches!(
+            env::var("ANALYTICS_COOKIELESS").map(|value| value.trim().to_lowercase()),
+            Ok(value) if value == "true" || value == "1"
+        );
+        let retention_days = match env::var("ANALYTICS_RETENTION_DAYS") {
+            Ok(value) if !value.trim().is_empty() => Some(parse_retention_days(&value)),
+            _ => None,
+        };
+        Self {
+            cookieless,
+            retention_days,
+        }
+    }
+}
+
+/// Reads `ANALYTICS_RETENTION_DAYS`. A count below one would roll up today's views,
+/// so it is refused at startup like any other bad setting.
+fn parse_retention_days(value: &str) -> i64 {
+    let days: i64 = value
+        .trim()
+        .parse()
+        .expect("Failed to parse ANALYTICS_RETENTION_DAYS");
+    if days <= 0 {
+        panic!("ANALYTICS_RETENTION_DAYS must be at least 1, got {}", days);
+    }
+    days
+}
+
+/// Whether a request asks not to be tracked, through `DNT` or `Sec-GPC`.
+pub fn opted_out(headers: &HeaderMap) -> bool {
+    ["dnt", "sec-gpc"].iter().any(|name| {
+        matches!(
+            headers.get(*name).and_then(|value| value.to_str().ok()),
+            Some(value) if value.trim() == "1"
+        )
+    })
+}
+
+pub fn new_salt() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .map(char::from)
+        .take(32)
+        .collect()
+}
+
+/// Tells visitors apart for one day only. Once the day's salt is deleted, the hash
+/// can't be linked back to the address it came from.
+pub fn daily_visitor_hash(salt: &str, address: IpAddr, user_agent: &str) -> String {
+    let mut hasher = Sha512::new();
+    hasher.update(format!("{}{}{}", salt, address, user_agent));
+    format!("{:x}", hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use headers::HeaderValue;
+
+    #[test]
+    fn dnt_and_gpc_opt_out() {
+        let mut headers = HeaderMap::new();
+        assert!(!opted_out(&headers));
+        headers.insert("dnt", HeaderValue::from_static("0"));
+        assert!(!opted_out(&headers));
+        headers.insert("sec-gpc", HeaderValue::from_static("1"));
+        assert!(opted_out(&headers));
+
+        let mut headers = HeaderMap::new();
+        headers.insert("dnt", HeaderValue::from_static("1"));
+        assert!(opted_out(&headers));
+    }
+
+    #[test]
+    fn retention_days_must_be_positive() {
+        assert_eq!(parse_retention_days(" 180 "), 180);
+        assert!(std::panic::catch_unwind(|| parse_retention_days("0")).is_err());
+        assert!(std::panic::catch_unwind(|| parse_retention_days("-30")).is_err());
+    }
+
+    #[test]
+    fn visitor_hash_changes_with_salt() {
+        let address: IpAddr = "203.0.113.7".parse().unwrap();
+        let hash = daily_visitor_hash("monday", address, "Firefox");
+        assert_eq!(hash, daily_visitor_hash("monday", address, "Firefox"));
+        assert_ne!(hash, daily_visitor_hash("tuesday", address, "Firefox"));
+        assert_ne!(hash, daily_visitor_hash("monday", address, "Chrome"));
+    }
+}
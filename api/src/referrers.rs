@@ -0,0 +1,233 @@
+use backend_repo_pg::extra::ReferrerType;
+use url::{form_urlencoded, Url};
+
+/// Search engines that run under many country domains, matched by any label of the host.
+const SEARCH_ENGINES: [(&str, &str); 9] = [
+    ("baidu", "Baidu"),
+    ("bing", "Bing"),
+    ("duckduckgo", "DuckDuckGo"),
+    ("ecosia", "Ecosia"),
+    ("google", "Google"),
+    ("qwant", "Qwant"),
+    ("startpage", "Startpage"),
+    ("yahoo", "Yahoo"),
+    ("yandex", "Yandex"),
+];
+
+/// Known sites, matched by the domain or any subdomain of it.
+const SITES: [(&str, &str, ReferrerType); 23] = [
+    ("search.brave.com", "Brave Search", ReferrerType::Search),
+    ("kagi.com", "Kagi", ReferrerType::Search),
+    ("t.co", "Twitter", ReferrerType::Social),
+    ("twitter.com", "Twitter", ReferrerType::Social),
+    ("x.com", "Twitter", ReferrerType::Social),
+    ("facebook.com", "Facebook", ReferrerType::Social),
+    ("fb.me", "Facebook", ReferrerType::Social),
+    ("instagram.com", "Instagram", ReferrerType::Social),
+    ("linkedin.com", "LinkedIn", ReferrerType::Social),
+    ("lnkd.in", "LinkedIn", ReferrerType::Social),
+    ("reddit.com", "Reddit", ReferrerType::Social),
+    ("redd.it", "Reddit", ReferrerType::Social),
+    ("news.ycombinator.com", "Hacker News", ReferrerType::Social),
+    ("lobste.rs", "Lobsters", ReferrerType::Social),
+    ("youtube.com", "YouTube", ReferrerType::Social),
+    ("youtu.be", "YouTube", ReferrerType::Social),
+    ("mastodon.social", "Mastodon", ReferrerType::Social),
+    ("t.me", "Telegram", ReferrerType::Social),
+    ("discord.com", "Discord", ReferrerType::Social),
+    ("pinterest.com", "Pinterest", ReferrerType::Social),
+    ("tiktok.com", "TikTok", ReferrerType::Social),
+    ("dev.to", "DEV", ReferrerType::Social),
+    ("medium.com", "Medium", ReferrerType::Social),
+];
+
+/// Longest UTM value kept, to stop junk links from filling the table.
+const MAX_UTM_LENGTH: usize = 100;
+
+/// Where a visitor came from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Referrer {
+    /// The referring page without its query, which may carry search terms or tokens.
+    pub url: String,
+    /// The site's name if it is a known one, otherwise its domain.
+    pub source: String,
+    pub r#type: ReferrerType,
+}
+
+/// The UTM parameters of a link.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Campaign {
+    pub source: Option<String>,
+    pub medium: Option<String>,
+    pub campaign: Option<String>,
+    pub term: Option<String>,
+    pub content: Option<String>,
+}
+
+fn strip_host_prefix(host: &str) -> &str {
+    ["www.", "m.", "l.", "lm.", "mobile."]
+        .iter()
+        .find_map(|prefix| host.strip_prefix(prefix))
+        .unwrap_or(host)
+}
+
+fn is_same_or_subdomain(host: &str, domain: &str) -> bool {
+    host == domain || (host.ends_with(domain) && host[..host.len() - domain.len()].ends_with('.'))
+}
+
+/// Parses a `Referer` value. Links from `site_host`, this site's own pages, and
+/// anything that is not a web page are ignored.
+pub fn parse_referrer(referrer: &str, site_host: Option<&str>) -> Option<Referrer> {
+    let url = Url::parse(referrer.trim()).ok()?;
+    if url.scheme() != "http" && url.scheme() != "https" {
+        return None;
+    }
+    let host = url.host_str()?.to_lowercase();
+    let domain = strip_host_prefix(&host);
+    if let Some(site_host) = site_host {
+        if domain == strip_host_prefix(&site_host.to_lowercase()) {
+            return None;
+        }
+    }
+
+    let (source, r#type) = if let Some((_, name, r#type)) = SITES
+        .iter()
+        .find(|(site, _, _)| is_same_or_subdomain(domain, site))
+    {
+        (name.to_string(), *r#type)
+    } else if let Some((_, name)) = SEARCH_ENGINES
+        .iter()
+        .find(|(label, _)| domain.split('.').any(|part| part == *label))
+    {
+        (name.to_string(), ReferrerType::Search)
+    } else {
+        (domain.to_string(), ReferrerType::Website)
+    };
+
+    Some(Referrer {
+        url: format!("{}://{}{}", url.scheme(), host, url.path()),
+        source,
+        r#type,
+    })
+}
+
+fn utm_value(value: &str) -> Option<String> {
+    let value = value.trim().to_lowercase();
+    if value.is_empty() {
+        return None;
+    }
+    Some(value.chars().take(MAX_UTM_LENGTH).collect())
+}
+
+/// The path of a page url, without its query or fragment.
+pub fn page_path(page_url: &str) -> &str {
+    match page_url.find(['?', '#']) {
+        Some(index) => &page_url[..index],
+        None => page_url,
+    }
+}
+
+/// Takes the UTM parameters out of a page url, leaving the rest of it as it was.
+pub fn split_campaign(page_url: &str) -> (String, Campaign) {
+    let (without_fragment, fragment) = match page_url.find('#') {
+        Some(index) => page_url.split_at(index),
+        None => (page_url, ""),
+    };
+    let (path, query) = match without_fragment.find('?') {
+        Some(index) => (&without_fragment[..index], &without_fragment[index + 1..]),
+        None => return (page_url.to_string(), Campaign::default()),
+    };
+
+    let mut campaign = Campaign::default();
+    let mut rest = form_urlencoded::Serializer::new(String::new());
+    let mut found = false;
+    for (key, value) in form_urlencoded::parse(query.as_bytes()) {
+        let field = match key.as_ref() {
+            "utm_source" => &mut campaign.source,
+            "utm_medium" => &mut campaign.medium,
+            "utm_campaign" => &mut campaign.campaign,
+            "utm_term" => &mut campaign.term,
+            "utm_content" => &mut campaign.content,
+            _ => {
+                rest.append_pair(&key, &value);
+                continue;
+            }
+        };
+        found = true;
+        *field = utm_value(&value);
+    }
+    if !found {
+        return (page_url.to_string(), campaign);
+    }
+
+    let rest = rest.finish();
+    let page_url = if rest.is_empty() {
+        format!("{}{}", path, fragment)
+    } else {
+        format!("{}?{}{}", path, rest, fragment)
+    };
+    (page_url, campaign)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_known_sites() {
+        let google = parse_referrer("https://www.google.co.uk/search?q=axmouth", None).unwrap();
+        assert_eq!(google.source, "Google");
+        assert_eq!(google.r#type, ReferrerType::Search);
+        assert_eq!(google.url, "https://www.google.co.uk/search");
+
+        let twitter = parse_referrer("https://t.co/abc123", None).unwrap();
+        assert_eq!(twitter.source, "Twitter");
+        assert_eq!(twitter.r#type, ReferrerType::Social);
+
+        let reddit = parse_referrer("https://old.reddit.com/r/rust/", None).unwrap();
+        assert_eq!(reddit.source, "Reddit");
+    }
+
+    #[test]
+    fn other_sites_are_grouped_by_domain() {
+        let referrer = parse_referrer("https://www.example.org/links", None).unwrap();
+        assert_eq!(referrer.source, "example.org");
+        assert_eq!(referrer.r#type, ReferrerType::Website);
+        // Not a subdomain of t.co
+        let referrer = parse_referrer("https://blogspot.co/", None).unwrap();
+        assert_eq!(referrer.r#type, ReferrerType::Website);
+    }
+
+    #[test]
+    fn ignores_own_site_and_non_web_referrers() {
+        assert_eq!(
+            parse_referrer("https://axmouth.dev/blog", Some("www.axmouth.dev")),
+            None
+        );
+        assert_eq!(parse_referrer("android-app://com.slack", None), None);
+        assert_eq!(parse_referrer("not a url", None), None);
+    }
+
+    #[test]
+    fn splits_campaign_from_page_url() {
+        let (page_url, campaign) =
+            split_campaign("/blog/rust?utm_source=Newsletter&id=3&utm_campaign=launch#top");
+        assert_eq!(page_url, "/blog/rust?id=3#top");
+        assert_eq!(campaign.source.as_deref(), Some("newsletter"));
+        assert_eq!(campaign.campaign.as_deref(), Some("launch"));
+        assert_eq!(campaign.medium, None);
+
+        let (page_url, _) = split_campaign("/projects?utm_medium=social");
+        assert_eq!(page_url, "/projects");
+        let (page_url, campaign) = split_campaign("/search?q=a+b");
+        assert_eq!(page_url, "/search?q=a+b");
+        assert_eq!(campaign, Campaign::default());
+    }
+
+    #[test]
+    fn page_path_drops_query_and_fragment() {
+        assert_eq!(page_path("/blog/rust?id=3#top"), "/blog/rust");
+        assert_eq!(page_path("/blog/rust#c12"), "/blog/rust");
+        assert_eq!(page_path("/projects"), "/projects");
+    }
+}
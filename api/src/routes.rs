@@ -3,10 +3,11 @@
 
 use crate::{
     app::{
-        AppState, CaptchaSecretImpl, DynCaptchaSecret, DynEmailSender, DynJwtDuration,
-        DynJwtSecret, DynStaticFileAddress, DynStaticFileDir, EmailSenderImpl, JwtDurationImpl,
-        JwtSecretImpl, StaticFileAddressImpl, StaticFileDirImpl,
+        AppState, DynCaptchaVerifier, DynEmailSender, DynJwtDuration, DynJwtSecret,
+        DynStaticFileAddress, DynStaticFileDir, EmailSenderImpl, JwtDurationImpl, JwtSecretImpl,
+        StaticFileAddressImpl, StaticFileDirImpl,
     },
+    captcha::{FixedCaptchaVerifier, SiteVerifyCaptchaVerifier},
     handlers::*,
     util::{not_found_response, server_error_response, simple_error_response},
 };
@@ -30,24 +31,111 @@ use tower_http::{
     trace::TraceLayer,
 };
 
+/// Where the api routes are mounted, for building links to them.
+pub const API_PREFIX: &str = "/api/v1";
+pub const NOTIFICATIONS_UNSUBSCRIBE_PATH: &str = "/notifications/unsubscribe";
+pub const NEWSLETTER_CONFIRM_PATH: &str = "/newsletter/confirm";
+pub const NEWSLETTER_UNSUBSCRIBE_PATH: &str = "/newsletter/unsubscribe";
+
 pub fn router(app_state: AppState) -> Router {
     let repo = Arc::new(app_state.repo) as DynRepo;
     let jwt_secret = Arc::new(JwtSecretImpl(app_state.jwt_secret)) as DynJwtSecret;
     let jwt_duration = Arc::new(JwtDurationImpl(app_state.jwt_duration)) as DynJwtDuration;
-    let captcha_secret = Arc::new(CaptchaSecretImpl(app_state.captcha_secret)) as DynCaptchaSecret;
+    let captcha_verifier = match app_state.captcha_provider {
+        Some(provider) => Arc::new(SiteVerifyCaptchaVerifier::new(
+            provider,
+            app_state.captcha_secret,
+        )) as DynCaptchaVerifier,
+        None => Arc::new(FixedCaptchaVerifier(true)) as DynCaptchaVerifier,
+    };
     let static_file_dir =
         Arc::new(StaticFileDirImpl(app_state.static_file_dir.clone())) as DynStaticFileDir;
     let static_file_address =
         Arc::new(StaticFileAddressImpl(app_state.static_file_address)) as DynStaticFileAddress;
     let email_sender = Arc::new(EmailSenderImpl(app_state.email_sender)) as DynEmailSender;
+    let geolocation = app_state.geolocation;
+    let analytics = app_state.analytics;
+    let live_views = app_state.live_views;
 
     let api_routes = Router::new()
         .route("/health", get(health::health))
         .route("/search", get(search::get_all))
+        .route("/search/suggest", get(search::suggest))
+        .route("/trending", get(search::trending))
+        .route("/search/clicks", post(search::click))
+        .route(
+            "/search/analytics/top-queries",
+            get(search::get_top_queries),
+        )
+        .route(
+            "/search/analytics/zero-result-queries",
+            get(search::get_zero_result_queries),
+        )
+        .route(
+            "/search/analytics/click-through",
+            get(search::get_click_through),
+        )
+        .route(
+            "/search/refresh",
+            get(search::get_refresh).post(search::refresh),
+        )
         .route("/admin-logs", get(admin_logs::get_all))
         .route("/admin-logs/:id", get(admin_logs::get))
+        .route("/email-outbox", get(email_outbox::get_all))
+        .route("/email-outbox/:id", get(email_outbox::get))
+        .route("/email-outbox/:id/retry", post(email_outbox::retry))
+        .route("/newsletter/subscribe", post(newsletter::subscribe))
+        .route(
+            NEWSLETTER_CONFIRM_PATH,
+            get(newsletter::confirm_page).post(newsletter::confirm),
+        )
+        .route(
+            NEWSLETTER_UNSUBSCRIBE_PATH,
+            get(newsletter::unsubscribe_page).post(newsletter::unsubscribe),
+        )
+        .route(
+            "/newsletter-subscribers",
+            get(newsletter::get_all_subscribers),
+        )
+        .route(
+            "/newsletter-issues",
+            get(newsletter::get_all_issues).post(newsletter::create_issue),
+        )
+        .route(
+            "/newsletter-issues/:id",
+            get(newsletter::get_issue).put(newsletter::update_issue),
+        )
+        .route("/newsletter-issues/:id/send", post(newsletter::send_issue))
+        .route(
+            "/newsletter-issues/:id/deliveries",
+            get(newsletter::get_all_deliveries),
+        )
+        .route("/email-templates", get(email_templates::get_all))
+        .route(
+            "/email-templates/:name/preview",
+            get(email_templates::preview),
+        )
         .route("/page-views", post(page_views::create))
         .route("/page-views/:url", get(page_views::get))
+        .route("/page-view-analytics/traffic", get(page_views::get_traffic))
+        .route("/page-view-analytics/pages", get(page_views::get_top_pages))
+        .route(
+            "/page-view-analytics/countries",
+            get(page_views::get_top_countries),
+        )
+        .route(
+            "/page-view-analytics/visitor-types",
+            get(page_views::get_visitor_types),
+        )
+        .route(
+            "/page-view-analytics/referrers",
+            get(page_views::get_top_referrers),
+        )
+        .route(
+            "/page-view-analytics/campaigns",
+            get(page_views::get_top_campaigns),
+        )
+        .route("/page-view-analytics/live", get(page_views::live))
         .route(
             "/blog-post-comments",
             get(blog_comments::get_all).post(blog_comments::create),
@@ -68,6 +156,7 @@ pub fn router(app_state: AppState) -> Router {
                 .put(blog_posts::update)
                 .delete(blog_posts::delete),
         )
+        .route("/blog-posts/:id/related", get(blog_posts::get_related))
         .route("/projects", get(projects::get_all).post(projects::create))
         .route(
             "/projects/:id",
@@ -75,6 +164,12 @@ pub fn router(app_state: AppState) -> Router {
                 .put(projects::update)
                 .delete(projects::delete),
         )
+        .route("/projects/:id/related", get(projects::get_related))
+        .route("/series", get(series::get_all).post(series::create))
+        .route(
+            "/series/:id",
+            get(series::get).put(series::update).delete(series::delete),
+        )
         .route("/links", get(links::get_all).post(links::create))
         .route(
             "/links/:id",
@@ -117,31 +212,68 @@ pub fn router(app_state: AppState) -> Router {
         .route("/auth/refresh", post(auth::refresh))
         .route("/auth/logout", delete(auth::logout))
         .route("/auth/admin-logout", delete(auth::logout_admin))
-        .route("/auth/profile", get(auth::get_profile))
+        .route(
+            "/auth/profile",
+            get(auth::get_profile)
+                .put(auth::update_profile)
+                .delete(auth::delete_account),
+        )
+        .route("/auth/profile/export", get(auth::export_profile))
+        .route("/auth/change-password", post(auth::change_password))
+        .route(
+            "/auth/notification-preferences",
+            get(notifications::get_preferences).put(notifications::update_preferences),
+        )
+        .route(
+            NOTIFICATIONS_UNSUBSCRIBE_PATH,
+            get(notifications::unsubscribe_page).post(notifications::unsubscribe),
+        )
         .route(
             "/auth/request-verification-email",
             post(auth::request_verification_email),
         )
         .route("/auth/verify-email", post(auth::verify_email))
+        .route("/auth/change-email", post(auth::change_email))
+        .route("/auth/undo-email-change", post(auth::undo_email_change))
         .route(
             "/auth/request-password-reset",
             post(auth::request_reset_password_email),
         )
         .route("/auth/reset-password", post(auth::reset_password))
+        .route(
+            "/auth/api-tokens",
+            get(api_tokens::get_all).post(api_tokens::create),
+        )
+        .route(
+            "/auth/api-tokens/:id",
+            get(api_tokens::get).delete(api_tokens::revoke),
+        )
         .route("/contact-email", post(contact::contact_email))
+        .route("/contact-messages", get(contact::get_all_messages))
+        .route(
+            "/contact-messages/:id",
+            get(contact::get_message).put(contact::update_message),
+        )
+        .route(
+            "/contact-messages/:id/replies",
+            get(contact::get_replies).post(contact::reply),
+        )
         .route("/files/upload/image", post(files::editor_js_upload))
         .route("/files/upload/editorjs", post(files::editor_js_upload))
         // We add middleware
         .layer(AddExtensionLayer::new(repo))
         .layer(AddExtensionLayer::new(jwt_secret))
         .layer(AddExtensionLayer::new(jwt_duration))
-        .layer(AddExtensionLayer::new(captcha_secret))
+        .layer(AddExtensionLayer::new(captcha_verifier))
         .layer(AddExtensionLayer::new(static_file_dir))
         .layer(AddExtensionLayer::new(static_file_address))
-        .layer(AddExtensionLayer::new(email_sender));
+        .layer(AddExtensionLayer::new(email_sender))
+        .layer(AddExtensionLayer::new(geolocation))
+        .layer(AddExtensionLayer::new(analytics))
+        .layer(AddExtensionLayer::new(live_views));
 
     Router::new()
-        .nest("/api/v1", api_routes)
+        .nest(API_PREFIX, api_routes)
         .nest(
             "/static",
             get_service(ServeDir::new(app_state.static_file_dir)).handle_error(
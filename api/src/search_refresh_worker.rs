@@ -0,0 +1,25 @@
+use backend_repo_pg::{errors::PgRepoError, pg_util::DynRepo, search_refreshes::SearchRefreshRepo};
+use tokio::task::block_in_place;
+
+const POLL_INTERVAL_MILLIS: u64 = 2000;
+/// How long content has to stay unchanged before search is refreshed.
+const QUIET_PERIOD_MILLIS: u64 = 3000;
+
+/// Keeps the search views in step with content writes until the process stops.
+pub async fn run(repo: DynRepo) {
+    let mut interval =
+        tokio::time::interval(std::time::Duration::from_millis(POLL_INTERVAL_MILLIS));
+    loop {
+        interval.tick().await;
+        if let Err(err) = refresh_if_due(repo.clone()) {
+            tracing::error!("Search refresh failed: {}", err);
+        }
+    }
+}
+
+pub fn refresh_if_due(repo: DynRepo) -> Result<bool, PgRepoError> {
+    block_in_place(|| {
+        let conn = repo.get_conn()?;
+        Ok(SearchRefreshRepo::new(&conn).refresh_if_due(QUIET_PERIOD_MILLIS)?)
+    })
+}
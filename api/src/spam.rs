@@ -0,0 +1,96 @@
+/// Contact messages scoring at least this much are kept for review but not forwarded.
+pub const SPAM_THRESHOLD: i32 = 5;
+
+const BLOCKLIST: [&str; 12] = [
+    "backlinks",
+    "bitcoin",
+    "casino",
+    "crypto",
+    "escort",
+    "forex",
+    "loan",
+    "porn",
+    "rank your website",
+    "seo services",
+    "viagra",
+    "whatsapp",
+];
+
+/// What is known about a contact form submission when scoring it.
+pub struct ContactSubmission<'a> {
+    pub subject: &'a str,
+    pub body: &'a str,
+    /// The hidden form field that only bots fill in.
+    pub honeypot: Option<&'a str>,
+    /// Earlier submissions from the same address in the last hour.
+    pub recent_submissions: i64,
+}
+
+/// A rough guess at how likely a submission is to be spam. Each signal adds to the
+/// score, and a filled honeypot is enough on its own to reach the threshold.
+pub fn spam_score(submission: &ContactSubmission) -> i32 {
+    let text = format!("{}\n{}", submission.subject, submission.body).to_lowercase();
+    let mut score = 0;
+
+    if matches!(submission.honeypot.map(str::trim), Some(value) if !value.is_empty()) {
+        score += SPAM_THRESHOLD;
+    }
+
+    let links = ["http://", "https://", "www."]
+        .iter()
+        .map(|pattern| text.matches(pattern).count() as i32)
+        .sum::<i32>();
+    score += match links {
+        0 => 0,
+        1 => 1,
+        2..=3 => 2,
+        _ => 4,
+    };
+
+    score += 2 * BLOCKLIST.iter().filter(|word| text.contains(*word)).count() as i32;
+
+    score += match submission.recent_submissions {
+        0 => 0,
+        1..=2 => 1,
+        _ => 3,
+    };
+
+    score
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn submission<'a>(
+        body: &'a str,
+        honeypot: Option<&'a str>,
+        recent: i64,
+    ) -> ContactSubmission<'a> {
+        ContactSubmission {
+            subject: "Hello",
+            body,
+            honeypot,
+            recent_submissions: recent,
+        }
+    }
+
+    #[test]
+    fn plain_message_is_not_spam() {
+        let score = spam_score(&submission("I liked your post about Rust.", Some(""), 0));
+        assert_eq!(score, 0);
+    }
+
+    #[test]
+    fn honeypot_alone_is_spam() {
+        let score = spam_score(&submission("Hi", Some("http://bot.example"), 0));
+        assert!(score >= SPAM_THRESHOLD);
+    }
+
+    #[test]
+    fn links_blocklist_and_rate_add_up() {
+        let body = "Cheap SEO services and backlinks: https://a.example https://b.example";
+        assert!(spam_score(&submission(body, None, 0)) >= SPAM_THRESHOLD);
+        assert_eq!(spam_score(&submission("See www.example.com", None, 3)), 4);
+    }
+}
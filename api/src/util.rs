@@ -1,6 +1,7 @@
+use crate::app::TEMPLATES;
 use axum::body::BoxBody;
 use axum::http::{Response, StatusCode};
-use axum::response::IntoResponse;
+use axum::response::{Html, IntoResponse};
 use axum::Json;
 use backend_repo_pg::pg_util::RepoConnection;
 use backend_repo_pg::{
@@ -9,10 +10,14 @@ use backend_repo_pg::{
 };
 use backend_repo_pg::{
     insertables::NewAdminLog,
-    models::responses::{AuthSuccess, BaseResponse, FileUploadedResponse, Pagination},
+    models::domain::SearchFacets,
+    models::responses::{
+        AuthSuccess, BaseResponse, FileUploadedResponse, Pagination, SearchResponse,
+    },
 };
 use chrono::{Duration, Utc};
 use serde::Serialize;
+use tera::Context;
 use time::OffsetDateTime;
 use tower_cookies::{Cookie, Cookies};
 
@@ -31,6 +36,15 @@ where
     (status, Json(resp_body)).into_response()
 }
 
+/// Renders one of the pages in `templates/pages/`, for links that are opened from
+/// emails rather than from a client.
+pub fn html_page_response(page: &str, context: &Context, status: StatusCode) -> Response<BoxBody> {
+    match TEMPLATES.render(&format!("pages/{}.html", page), context) {
+        Ok(body) => (status, Html(body)).into_response(),
+        Err(err) => server_error_response(err),
+    }
+}
+
 pub fn simple_internal_error_response(error_message: String) -> Response<BoxBody> {
     simple_error_response(error_message, StatusCode::INTERNAL_SERVER_ERROR)
 }
@@ -60,6 +74,33 @@ pub fn paginated_ok_response<T: Serialize>(
     (StatusCode::OK, Json(resp_body)).into_response()
 }
 
+pub fn search_ok_response<T: Serialize>(
+    data: T,
+    facets: SearchFacets,
+    search_id: Option<i32>,
+    page: Option<i64>,
+    page_size: Option<i64>,
+    total_results: i64,
+) -> Response<BoxBody> {
+    let resp_body = SearchResponse::<T> {
+        base: BaseResponse::<T> {
+            data: Some(data),
+            messages: None,
+            pagination: Some(Pagination {
+                page,
+                page_size,
+                total_results,
+            }),
+            errors: None,
+            success: Some(true),
+        },
+        facets,
+        search_id,
+    };
+
+    (StatusCode::OK, Json(resp_body)).into_response()
+}
+
 pub fn simple_created_response<T: Serialize>(data: T) -> Response<BoxBody> {
     success_response(data, StatusCode::CREATED).into_response()
 }
@@ -1,4 +1,5 @@
 pub mod create_super_user;
+pub mod refresh_search;
 
 #[tokio::main]
 async fn main() {
@@ -17,6 +18,16 @@ async fn main() {
                 Err(err) => eprintln!("{}", err),
             }
         }
+        CmdType::RefreshSearch => match refresh_search::refresh_search().await {
+            Ok(search_refresh) => println!(
+                "\n\nSearch has been refreshed at {}\n",
+                search_refresh
+                    .refreshed_at
+                    .map(|refreshed_at| refreshed_at.to_string())
+                    .unwrap_or_default()
+            ),
+            Err(err) => eprintln!("{}", err),
+        },
         _ => {}
     }
 }
@@ -37,6 +48,7 @@ pub enum CmdType {
         email: Option<String>,
         password: Option<String>,
     },
+    RefreshSearch,
     Help,
     None,
 }
@@ -56,6 +68,7 @@ pub fn parse_args() -> CmdType {
                     email: None,
                 }
             }
+            "refresh-search" => cmd = CmdType::RefreshSearch,
             "help" => cmd = CmdType::Help,
             _ => panic!("Unknown argument: {}", value),
         },
@@ -127,5 +140,7 @@ pub static HELP_TEXT: &str = "Commands:
         -d/--display-name <display name> (required)
         -e/--email <email> (required)
 
+    refresh-search (rebuilds the search index now)
+
     help (this message)
     ";
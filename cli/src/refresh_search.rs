@@ -0,0 +1,17 @@
+use backend_repo_pg::{
+    errors::PgRepoError,
+    models::domain::SearchRefresh,
+    pg_util::{get_pg_pool, RepoConnection},
+    search_refreshes::SearchRefreshRepo,
+};
+use std::env;
+
+pub async fn refresh_search() -> Result<SearchRefresh, PgRepoError> {
+    dotenv::dotenv().unwrap();
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let repo = get_pg_pool(database_url, 1);
+    let conn = RepoConnection::new(repo).expect("Could not get database connection");
+    let search_refresh_repository = SearchRefreshRepo::new(&conn);
+
+    Ok(search_refresh_repository.refresh()?)
+}
@@ -41,6 +41,7 @@ import { UnsignedIntegerFieldComponent } from './field-components/unsigned-integ
 import { HtmlFieldComponent } from './field-components/html-field/html-field.component';
 import { MarkdownFieldComponent } from './field-components/markdown-field/markdown-field.component';
 import { NameListFieldComponent } from './field-components/name-list-field/name-list-field.component';
+import { IdListFieldComponent } from './field-components/id-list-field/id-list-field.component';
 import { ImageFieldComponent } from './field-components/image-field/image-field.component';
 import { LinkFieldComponent } from './field-components/link-field/link-field.component';
 import { FieldChooserComponent } from './field-components/field-chooser/field-chooser.component';
@@ -70,6 +71,7 @@ import { WarningDialogComponent } from './components/warning-dialog/warning-dial
     HtmlFieldComponent,
     MarkdownFieldComponent,
     NameListFieldComponent,
+    IdListFieldComponent,
     ImageFieldComponent,
     LinkFieldComponent,
     FieldChooserComponent,
@@ -125,6 +127,7 @@ import { WarningDialogComponent } from './components/warning-dialog/warning-dial
     HtmlFieldComponent,
     MarkdownFieldComponent,
     NameListFieldComponent,
+    IdListFieldComponent,
     ImageFieldComponent,
     LinkFieldComponent,
     FieldChooserComponent,
@@ -79,6 +79,56 @@ const blogPostCommentModel: AdminModel = {
   ],
 };
 
+const seriesModel: AdminModel = {
+  name: 'Series',
+  idField: 'id',
+  displayField: 'title',
+  endpoint: `${apiRoot}/series`,
+  modelId: 'series',
+  fields: [
+    {
+      name: 'Title',
+      identifier: 'title',
+      type: FieldType.titleField,
+      editable: true,
+      insertable: true,
+      nullable: false,
+    },
+    {
+      name: 'Slug',
+      identifier: 'slug',
+      type: FieldType.titleField,
+      editable: true,
+      insertable: true,
+      nullable: false,
+    },
+    {
+      name: 'Description',
+      identifier: 'description',
+      type: FieldType.textField,
+      editable: true,
+      insertable: true,
+      nullable: true,
+    },
+    {
+      name: 'Blog Post Ids, in order',
+      identifier: 'blogPostIds',
+      type: FieldType.idListField,
+      editable: true,
+      insertable: true,
+      nullable: false,
+    },
+    {
+      name: 'Published',
+      identifier: 'published',
+      type: FieldType.booleanField,
+      editable: true,
+      insertable: false,
+      nullable: false,
+    },
+  ],
+};
+
 const userModel: AdminModel = {
   name: 'Users',
   idField: 'id',
@@ -302,7 +352,7 @@ const technologyModel: AdminModel = {
 export const categoryList: AdminCategory[] = [
   {
     name: 'Blog',
-    models: [blogPostModel, categoryModel, blogPostCommentModel],
+    models: [blogPostModel, seriesModel, categoryModel, blogPostCommentModel],
   },
   {
     name: 'Projects',
@@ -9,6 +9,7 @@ export enum FieldType {
   editorJsField = 'editorJsField',
   markdownField = 'markdownField',
   nameListField = 'nameListField',
+  idListField = 'idListField',
   imageField = 'imageField',
   linkField = 'linkField',
   dateField = 'dateField',
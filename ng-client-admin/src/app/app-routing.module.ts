@@ -13,11 +13,19 @@ import { ViewEntityComponent } from './admin-dashboard/components/view-entity/vi
 import { CreateEntityComponent } from './admin-dashboard/components/create-entity/create-entity.component';
 import { AdminLogDetailsComponent } from './components/pages/admin-log-details/admin-log-details.component';
 import { AdminLogsComponent } from './components/pages/admin-logs/admin-logs.component';
+import { ContactMessagesComponent } from './components/pages/contact-messages/contact-messages.component';
+import { ContactMessageDetailsComponent } from './components/pages/contact-message-details/contact-message-details.component';
 
 const routes: Routes = [
   { path: '', component: HomeComponent, pathMatch: 'full', canActivate: [AuthGuard] },
   { path: 'admin-logs', component: AdminLogsComponent, canActivate: [AuthGuard] },
   { path: 'admin-logs/:adminLogId', component: AdminLogDetailsComponent, canActivate: [AuthGuard] },
+  { path: 'contact-messages', component: ContactMessagesComponent, canActivate: [AuthGuard] },
+  {
+    path: 'contact-messages/:contactMessageId',
+    component: ContactMessageDetailsComponent,
+    canActivate: [AuthGuard],
+  },
   {
     path: '',
     loadChildren: () => import('./admin-dashboard/admin-dashboard.module').then((m) => m.AdminDashboardModule),
@@ -41,6 +41,8 @@ import {
   AdminLogDetailsComponent,
   ExampleDialogComponent,
 } from './components/pages/admin-log-details/admin-log-details.component';
+import { ContactMessagesComponent } from './components/pages/contact-messages/contact-messages.component';
+import { ContactMessageDetailsComponent } from './components/pages/contact-message-details/contact-message-details.component';
 import { WarningDialogComponent } from './components/warning-dialog/warning-dialog.component';
 
 @NgModule({
@@ -54,6 +56,8 @@ import { WarningDialogComponent } from './components/warning-dialog/warning-dial
     AdminLogPreviewComponent,
     AdminLogsComponent,
     AdminLogDetailsComponent,
+    ContactMessagesComponent,
+    ContactMessageDetailsComponent,
     WarningDialogComponent,
     ExampleDialogComponent,
   ],
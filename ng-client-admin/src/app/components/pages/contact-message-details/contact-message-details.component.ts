@@ -0,0 +1,108 @@
+import { Component, OnDestroy, OnInit } from '@angular/core';
+import { FormControl, Validators } from '@angular/forms';
+import { MatSnackBar } from '@angular/material/snack-bar';
+import { Title } from '@angular/platform-browser';
+import { ActivatedRoute } from '@angular/router';
+import { Subject } from 'rxjs';
+import { takeUntil } from 'rxjs/operators';
+import { ContactMessage, ContactMessageReply } from 'src/app/models/api/contact-message';
+import { ContactMessagesService } from 'src/app/services/contact-messages.service';
+
+@Component({
+  selector: 'app-contact-message-details',
+  templateUrl: './contact-message-details.component.html',
+  styleUrls: ['./contact-message-details.component.scss'],
+})
+export class ContactMessageDetailsComponent implements OnInit, OnDestroy {
+  ngUnsubscribe = new Subject<void>();
+  message: ContactMessage;
+  messageId: string;
+  replies: ContactMessageReply[] = [];
+  replyBody = new FormControl('', [Validators.required, Validators.maxLength(10000)]);
+  sending = false;
+
+  constructor(
+    private contactMessagesService: ContactMessagesService,
+    private route: ActivatedRoute,
+    private title: Title,
+    private snackBar: MatSnackBar,
+  ) {}
+
+  ngOnInit(): void {
+    this.route.params.pipe(takeUntil(this.ngUnsubscribe)).subscribe((params) => {
+      this.messageId = params.contactMessageId;
+      this.initialiseState(); // reset and set based on new parameter this time
+    });
+  }
+
+  initialiseState() {
+    this.title.setTitle(`Loading Message | Axmouth's Website Admin Site`);
+    this.contactMessagesService
+      .get(this.messageId, {})
+      .pipe(takeUntil(this.ngUnsubscribe))
+      .subscribe((res) => {
+        this.message = res.data;
+        this.title.setTitle(`${this.message.subject} | Axmouth's Website Admin Site`);
+        // Opening a message reads it
+        if (!this.message.readAt) {
+          this.update({ read: true }, false);
+        }
+      });
+    this.loadReplies();
+  }
+
+  loadReplies() {
+    this.contactMessagesService
+      .getReplies(this.messageId)
+      .pipe(takeUntil(this.ngUnsubscribe))
+      .subscribe((res) => {
+        this.replies = res.data;
+      });
+  }
+
+  update(body: { read?: boolean; archived?: boolean; spam?: boolean }, notify = true) {
+    this.contactMessagesService
+      .update(this.messageId, body)
+      .pipe(takeUntil(this.ngUnsubscribe))
+      .subscribe(
+        (res) => {
+          this.message = res.data;
+          if (notify) {
+            this.snackBar.open(`Message saved successfully!`, `❌`, { duration: 3000 });
+          }
+        },
+        (err) => {
+          console.log(err);
+          this.snackBar.open(`Failed to save message..`, `❌`, { duration: 3000 });
+        },
+      );
+  }
+
+  onReplyClick() {
+    if (this.replyBody.invalid) {
+      return;
+    }
+    this.sending = true;
+    this.contactMessagesService
+      .reply(this.messageId, this.replyBody.value)
+      .pipe(takeUntil(this.ngUnsubscribe))
+      .subscribe(
+        (res) => {
+          this.sending = false;
+          this.replyBody.reset('');
+          this.snackBar.open(`Reply queued successfully!`, `❌`, { duration: 3000 });
+          this.initialiseState();
+        },
+        (err) => {
+          console.log(err);
+          this.sending = false;
+          this.snackBar.open(`Failed to send reply..`, `❌`, { duration: 3000 });
+        },
+      );
+  }
+
+  ngOnDestroy(): void {
+    this.ngUnsubscribe.next();
+    this.ngUnsubscribe.complete();
+  }
+}
@@ -0,0 +1,103 @@
+import { Component, OnDestroy, OnInit } from '@angular/core';
+import { PageEvent } from '@angular/material/paginator';
+import { Title } from '@angular/platform-browser';
+import { ActivatedRoute, Router } from '@angular/router';
+import { Subject } from 'rxjs';
+import { takeUntil } from 'rxjs/operators';
+import { ContactMessage } from 'src/app/models/api/contact-message';
+import { ContactMessagesService } from 'src/app/services/contact-messages.service';
+
+@Component({
+  selector: 'app-contact-messages',
+  templateUrl: './contact-messages.component.html',
+  styleUrls: ['./contact-messages.component.scss'],
+})
+export class ContactMessagesComponent implements OnInit, OnDestroy {
+  ngUnsubscribe = new Subject<void>();
+  resultNumber = 0;
+  page: number;
+  pageSize: number;
+  sortType?: string;
+  folder: string;
+  loading = true;
+  messages: ContactMessage[];
+
+  constructor(
+    private router: Router,
+    private contactMessagesService: ContactMessagesService,
+    private route: ActivatedRoute,
+    private title: Title,
+  ) {}
+
+  ngOnInit(): void {
+    this.route.queryParams.pipe(takeUntil(this.ngUnsubscribe)).subscribe((qParams) => {
+      if (isNaN(+qParams.page) === false) {
+        this.page = +qParams.page ?? 1;
+      } else {
+        this.page = 1;
+      }
+      if (isNaN(+qParams.pageSize) === false) {
+        this.pageSize = +qParams.pageSize ?? 25;
+      } else {
+        this.pageSize = 25;
+      }
+      this.sortType = qParams.sortType ?? 'CreatedAtDesc';
+      this.folder = qParams.folder ?? 'inbox';
+      this.initialiseState();
+    });
+  }
+
+  initialiseState() {
+    this.title.setTitle(`Loading Inbox | Axmouth's Website Admin Site`);
+    this.loading = true;
+    this.contactMessagesService
+      .getAll({ sortType: this.sortType, pageSize: this.pageSize, page: this.page, ...this.folderQuery() })
+      .pipe(takeUntil(this.ngUnsubscribe))
+      .subscribe((res) => {
+        this.messages = res.data;
+        this.resultNumber = res?.pagination?.totalResults;
+        this.loading = false;
+        this.title.setTitle(`Inbox | Axmouth's Website Admin Site`);
+      });
+  }
+
+  folderQuery(): object {
+    switch (this.folder) {
+      case 'archived':
+        return { archived: true };
+      case 'spam':
+        return { spam: true };
+      default:
+        return { archived: false, spam: false };
+    }
+  }
+
+  handlePageEvent(event: PageEvent) {
+    this.router.navigate([], {
+      relativeTo: this.route,
+      queryParams: { page: event.pageIndex + 1, pageSize: event.pageSize, sortType: this.sortType },
+      queryParamsHandling: 'merge',
+    });
+  }
+
+  sortValueChange(sortType: string) {
+    this.router.navigate([], {
+      relativeTo: this.route,
+      queryParams: { sortType },
+      queryParamsHandling: 'merge',
+    });
+  }
+
+  folderValueChange(folder: string) {
+    this.router.navigate([], {
+      relativeTo: this.route,
+      queryParams: { folder, page: 1 },
+      queryParamsHandling: 'merge',
+    });
+  }
+
+  ngOnDestroy(): void {
+    this.ngUnsubscribe.next();
+    this.ngUnsubscribe.complete();
+  }
+}
@@ -0,0 +1,7 @@
+import { ContactMessage } from './contact-message';
+
+describe('ContactMessage', () => {
+  it('should create an instance', () => {
+    expect(new ContactMessage()).toBeTruthy();
+  });
+});
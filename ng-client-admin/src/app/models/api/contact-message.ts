@@ -0,0 +1,26 @@
+import { User } from './user';
+
+export class ContactMessage {
+  id: number;
+  fromEmail: string;
+  subject: string;
+  body: string;
+  ipAddress?: string;
+  userAgent?: string;
+  spamScore: number;
+  spam: boolean;
+  createdAt: string;
+  readAt?: string;
+  archivedAt?: string;
+  repliedAt?: string;
+}
+
+export class ContactMessageReply {
+  id: number;
+  contactMessageId: number;
+  author: User;
+  body: string;
+  status: string;
+  createdAt: string;
+  sentAt?: string;
+}
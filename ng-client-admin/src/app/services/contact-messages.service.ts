@@ -0,0 +1,51 @@
+import { Injectable, OnDestroy } from '@angular/core';
+import { RestApiService } from '../admin-dashboard/services/rest-api.service';
+import { apiRoot } from 'src/environments/environment';
+import { Observable, Subject } from 'rxjs';
+import { takeUntil } from 'rxjs/operators';
+import { Response } from 'src/app/models/api/response';
+import { ContactMessage, ContactMessageReply } from '../models/api/contact-message';
+
+@Injectable({
+  providedIn: 'root',
+})
+export class ContactMessagesService implements OnDestroy {
+  url = `${apiRoot}/contact-messages`;
+  ngUnsubscribe = new Subject<void>();
+
+  constructor(private apiService: RestApiService) {}
+
+  get(id: string, query: object): Observable<Response<ContactMessage>> {
+    return this.apiService.get<Response<ContactMessage>>(this.url, id, query).pipe(takeUntil(this.ngUnsubscribe));
+  }
+
+  getAll(query: object = {}): Observable<Response<ContactMessage[]>> {
+    return this.apiService.getAll<Response<ContactMessage[]>>(this.url, query).pipe(takeUntil(this.ngUnsubscribe));
+  }
+
+  update(
+    id: string,
+    body: { read?: boolean; archived?: boolean; spam?: boolean },
+  ): Observable<Response<ContactMessage>> {
+    return this.apiService
+      .update<Response<ContactMessage>>(this.url, id, body, {})
+      .pipe(takeUntil(this.ngUnsubscribe));
+  }
+
+  getReplies(id: string): Observable<Response<ContactMessageReply[]>> {
+    return this.apiService
+      .getAll<Response<ContactMessageReply[]>>(`${this.url}/${id}/replies`, {})
+      .pipe(takeUntil(this.ngUnsubscribe));
+  }
+
+  reply(id: string, body: string): Observable<Response<ContactMessageReply>> {
+    return this.apiService
+      .create<Response<ContactMessageReply>>(`${this.url}/${id}/replies`, { body }, {})
+      .pipe(takeUntil(this.ngUnsubscribe));
+  }
+
+  ngOnDestroy(): void {
+    this.ngUnsubscribe.next();
+    this.ngUnsubscribe.complete();
+  }
+}
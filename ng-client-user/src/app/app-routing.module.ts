@@ -8,6 +8,8 @@ const routes: Routes = [
   { path: 'contact', loadChildren: () => import('./contact/contact.module').then((m) => m.ContactModule) },
   { path: 'auth', loadChildren: () => import('./auth-pages/auth-pages.module').then((m) => m.AuthPagesModule) },
   { path: 'login', redirectTo: '/auth/login' },
+  // Search results link series here
+  { path: 'series/:slug', redirectTo: '/blog/series/:slug' },
   { path: 'search', loadChildren: () => import('./search/search.module').then((m) => m.SearchModule) },
   { path: '', loadChildren: () => import('./home-page/home-page.module').then((m) => m.HomePageModule) },
   { path: '**', component: NotFoundPageComponent, pathMatch: 'full' },
@@ -34,6 +34,7 @@ import { FontAwesomeModule } from '@fortawesome/angular-fontawesome';
         passwordResetEndpoint: 'reset-password',
         requestPasswordResetEndpoint: 'request-password-reset',
         verifyEmailEndpoint: 'verify-email',
+        undoEmailChangeEndpoint: 'undo-email-change',
         requestVerificationEmailEndpoint: 'request-verification-email',
         userNameJwtKey: 'display_name',
         passwordResetConfig: {
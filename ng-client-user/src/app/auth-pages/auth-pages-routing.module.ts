@@ -9,6 +9,7 @@ import { AuthMainComponent } from './components/auth-main/auth-main.component';
 import { RegisterPageComponent } from './pages/register-page/register-page.component';
 import { PasswordChangePageComponent } from './pages/password-change-page/password-change-page.component';
 import { VerifyEmailPageComponent } from './pages/verify-email-page/verify-email-page.component';
+import { UndoEmailChangePageComponent } from './pages/undo-email-change-page/undo-email-change-page.component';
 import { SettingsPageComponent } from './pages/settings-page/settings-page.component';
 import { RequestPasswordChangePageComponent } from './pages/request-password-change-page/request-password-change-page.component';
 
@@ -23,6 +24,7 @@ const routes: Routes = [
       { path: 'logout', component: LogoutPageComponent, canActivate: [AuthGuard] },
       { path: 'settings', component: SettingsPageComponent, canActivate: [AuthGuard] },
       { path: 'verify-email', component: VerifyEmailPageComponent },
+      { path: 'undo-email-change', component: UndoEmailChangePageComponent },
       { path: 'reset-password', component: PasswordChangePageComponent },
       { path: 'request-password-reset', component: RequestPasswordChangePageComponent },
       { path: '**', component: NotFoundPageComponent, pathMatch: 'full' },
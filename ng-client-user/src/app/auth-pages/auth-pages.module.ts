@@ -12,6 +12,7 @@ import { VerifyEmailPageComponent } from './pages/verify-email-page/verify-email
 import { RequestPasswordChangePageComponent } from './pages/request-password-change-page/request-password-change-page.component';
 import { PasswordChangePageComponent } from './pages/password-change-page/password-change-page.component';
 import { NgbModule } from '@ng-bootstrap/ng-bootstrap';
+import { UndoEmailChangePageComponent } from './pages/undo-email-change-page/undo-email-change-page.component';
 import { SettingsPageComponent } from './pages/settings-page/settings-page.component';
 
 @NgModule({
@@ -21,6 +22,7 @@ import { SettingsPageComponent } from './pages/settings-page/settings-page.compo
     AuthMainComponent,
     RegisterPageComponent,
     VerifyEmailPageComponent,
+    UndoEmailChangePageComponent,
     RequestPasswordChangePageComponent,
     PasswordChangePageComponent,
     SettingsPageComponent,
@@ -9,7 +9,7 @@ import { Subject } from 'rxjs';
 import { takeUntil } from 'rxjs/operators';
 import { DOCUMENT } from '@angular/common';
 import { Inject } from '@angular/core';
-import { websiteUrl } from 'src/environments/environment';
+import { HCAPTCHA_SITE_KEY, websiteUrl } from 'src/environments/environment';
 
 export class MismatchValidator {
   static mismatch(otherInputControl: AbstractControl): ValidatorFn {
@@ -51,8 +51,14 @@ export class RegisterPageComponent implements OnInit, OnDestroy {
     ]),
     password: new FormControl(''),
     password2: new FormControl(''),
+    hcaptcha: new FormControl(''),
   });
   errors = [];
+  HCAPTCHA_SITE_KEY = HCAPTCHA_SITE_KEY;
+  captchaConfig = {
+    size: 'compact',
+  };
+  captchaDone = false;
 
   registerInProgress = false;
 
@@ -106,6 +112,14 @@ export class RegisterPageComponent implements OnInit, OnDestroy {
     this.registerForm.get('password2').setValidators(formValidators.password2);
   }
 
+  onCaptchaResponse() {
+    this.captchaDone = true;
+  }
+
+  onCaptchaExpired() {
+    this.captchaDone = false;
+  }
+
   onSubmit() {
     this.registerInProgress = true;
     this.authService
@@ -113,6 +127,7 @@ export class RegisterPageComponent implements OnInit, OnDestroy {
         email: this.registerForm.get('email').value,
         password: this.registerForm.get('password').value,
         displayName: this.registerForm.get('displayName').value,
+        captchaToken: this.registerForm.get('hcaptcha').value,
       })
       .pipe(takeUntil(this.ngUnsubscribe))
       .subscribe(
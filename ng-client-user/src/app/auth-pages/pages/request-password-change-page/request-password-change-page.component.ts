@@ -8,7 +8,7 @@ import { takeUntil } from 'rxjs/operators';
 import { AuthService } from 'src/auth';
 import { IsBrowserService } from 'src/auth/helpers/services/is-browser.service';
 import { AuthResult } from 'src/auth/internal/auth-result';
-import { websiteUrl } from 'src/environments/environment';
+import { HCAPTCHA_SITE_KEY, websiteUrl } from 'src/environments/environment';
 
 @Component({
   selector: 'app-request-password-change-page',
@@ -24,7 +24,13 @@ export class RequestPasswordChangePageComponent implements OnInit, OnDestroy {
   ngUnsubscribe = new Subject<void>();
   requestPasswordResetForm = new FormGroup({
     email: new FormControl('', [Validators.required, Validators.email]),
+    hcaptcha: new FormControl(''),
   });
+  HCAPTCHA_SITE_KEY = HCAPTCHA_SITE_KEY;
+  captchaConfig = {
+    size: 'compact',
+  };
+  captchaDone = false;
   constructor(
     private authService: AuthService,
     private isBrowserService: IsBrowserService,
@@ -51,9 +57,20 @@ export class RequestPasswordChangePageComponent implements OnInit, OnDestroy {
     }
   }
 
+  onCaptchaResponse() {
+    this.captchaDone = true;
+  }
+
+  onCaptchaExpired() {
+    this.captchaDone = false;
+  }
+
   onRequestPasswordResetSubmit(): void {
     this.authService
-      .requestPasswordReset({ email: this.requestPasswordResetForm.get('email').value })
+      .requestPasswordReset({
+        email: this.requestPasswordResetForm.get('email').value,
+        captchaToken: this.requestPasswordResetForm.get('hcaptcha').value,
+      })
       .pipe(takeUntil(this.ngUnsubscribe))
       .subscribe(
         (result) => {
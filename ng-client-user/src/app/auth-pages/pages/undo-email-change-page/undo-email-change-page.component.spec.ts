@@ -0,0 +1,25 @@
+import { ComponentFixture, TestBed, waitForAsync } from '@angular/core/testing';
+
+import { UndoEmailChangePageComponent } from './undo-email-change-page.component';
+
+describe('UndoEmailChangePageComponent', () => {
+  let component: UndoEmailChangePageComponent;
+  let fixture: ComponentFixture<UndoEmailChangePageComponent>;
+
+  beforeEach(waitForAsync(() => {
+    TestBed.configureTestingModule({
+      declarations: [ UndoEmailChangePageComponent ]
+    })
+    .compileComponents();
+  }));
+
+  beforeEach(() => {
+    fixture = TestBed.createComponent(UndoEmailChangePageComponent);
+    component = fixture.componentInstance;
+    fixture.detectChanges();
+  });
+
+  it('should create', () => {
+    expect(component).toBeTruthy();
+  });
+});
@@ -5,6 +5,7 @@ import { ViewBlogPostDetailsPageComponent } from './pages/view-blog-post-details
 import { BlogMainComponent } from './components/blog-main/blog-main.component';
 import { NotFoundPageComponent } from '../shared/components/not-found-page/not-found-page.component';
 import { ViewBlogPostsCategoryPageComponent } from './pages/view-blog-posts-category-page/view-blog-posts-category-page.component';
+import { ViewSeriesPageComponent } from './pages/view-series-page/view-series-page.component';
 
 const routes: Routes = [
   {
@@ -14,6 +15,7 @@ const routes: Routes = [
     children: [
       { path: '', component: ViewBlogPostsPageComponent, pathMatch: 'full' },
       { path: 'category/:categoryName', component: ViewBlogPostsCategoryPageComponent, pathMatch: 'full' },
+      { path: 'series/:slug', component: ViewSeriesPageComponent, pathMatch: 'full' },
       { path: ':id', component: ViewBlogPostDetailsPageComponent, pathMatch: 'full' },
       { path: '**', component: NotFoundPageComponent, pathMatch: 'full' },
     ],
@@ -22,6 +22,8 @@ import { CommentPreviewPageComponent } from './components/comment-preview-page/c
 import { NgbModule } from '@ng-bootstrap/ng-bootstrap';
 import { BlogPostPreviewPlaceholderComponent } from './components/blog-post-preview-placeholder/blog-post-preview-placeholder.component';
 import { BlogPostDetailsPlaceholderComponent } from './components/blog-post-details-placeholder/blog-post-details-placeholder.component';
+import { ViewSeriesPageComponent } from './pages/view-series-page/view-series-page.component';
+import { SeriesService } from './services/series.service';
 import { BlogPostCommentPlaceholderComponent } from './components/blog-post-comment-placeholder/blog-post-comment-placeholder.component';
 
 @NgModule({
@@ -42,8 +44,9 @@ import { BlogPostCommentPlaceholderComponent } from './components/blog-post-comm
     BlogPostPreviewPlaceholderComponent,
     BlogPostDetailsPlaceholderComponent,
     BlogPostCommentPlaceholderComponent,
+    ViewSeriesPageComponent,
   ],
   imports: [CommonModule, BlogRoutingModule, SharedModule, ReactiveFormsModule, FormsModule, NgbModule],
-  providers: [BlogPostService, BlogPostCommentService],
+  providers: [BlogPostService, BlogPostCommentService, SeriesService],
 })
 export class BlogModule {}
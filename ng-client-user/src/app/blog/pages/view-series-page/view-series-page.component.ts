@@ -0,0 +1,75 @@
+import { Component, OnInit, OnDestroy } from '@angular/core';
+import { ActivatedRoute } from '@angular/router';
+import { Meta, Title } from '@angular/platform-browser';
+import { Subject } from 'rxjs';
+import { takeUntil } from 'rxjs/operators';
+import { DOCUMENT } from '@angular/common';
+import { Inject } from '@angular/core';
+import { websiteUrl } from 'src/environments/environment';
+import { Series } from 'src/app/models/api/series';
+import { SeriesService } from '../../services/series.service';
+
+@Component({
+  selector: 'app-view-series-page',
+  templateUrl: './view-series-page.component.html',
+  styleUrls: ['./view-series-page.component.scss'],
+})
+export class ViewSeriesPageComponent implements OnInit, OnDestroy {
+  ngUnsubscribe = new Subject<void>();
+  series: Series;
+  seriesSlug: string;
+  notFound = false;
+  loading = true;
+
+  constructor(
+    private seriesService: SeriesService,
+    private route: ActivatedRoute,
+    private title: Title,
+    private meta: Meta,
+    @Inject(DOCUMENT) private doc: Document,
+  ) {}
+
+  ngOnInit(): void {
+    this.title.setTitle(`Loading Series | Axmouth's Website`);
+    this.route.params.pipe(takeUntil(this.ngUnsubscribe)).subscribe((params) => {
+      this.seriesSlug = params.slug;
+      this.initialiseState(); // reset and set based on new parameter this time
+    });
+  }
+
+  initialiseState() {
+    this.loading = true;
+    this.notFound = false;
+    this.seriesService
+      .getSeries(this.seriesSlug, { useSlug: true })
+      .pipe(takeUntil(this.ngUnsubscribe))
+      .subscribe(
+        (result) => {
+          this.series = result.data;
+          this.loading = false;
+          this.title.setTitle(`${this.series.title} | Axmouth's Website`);
+          this.meta.updateTag({ name: `title`, content: this.title.getTitle() });
+          this.meta.updateTag({ name: `description`, content: this?.series?.description });
+          this.meta.updateTag({
+            property: `og:url`,
+            content: this.doc.location.href.replace(this.doc.location.origin, websiteUrl),
+          });
+          this.meta.updateTag({ property: `og:title`, content: this.title.getTitle() });
+          this.meta.updateTag({ property: `og:description`, content: this?.series?.description });
+        },
+        (error) => {
+          console.log(error);
+          if (error.status === 404) {
+            this.notFound = true;
+            this.title.setTitle('axmouth.dev - Series Not Found');
+            this.loading = false;
+          }
+        },
+      );
+  }
+
+  ngOnDestroy(): void {
+    this.ngUnsubscribe.next();
+    this.ngUnsubscribe.complete();
+  }
+}
@@ -0,0 +1,16 @@
+import { TestBed } from '@angular/core/testing';
+
+import { SeriesService } from './series.service';
+
+describe('SeriesService', () => {
+  let service: SeriesService;
+
+  beforeEach(() => {
+    TestBed.configureTestingModule({});
+    service = TestBed.inject(SeriesService);
+  });
+
+  it('should be created', () => {
+    expect(service).toBeTruthy();
+  });
+});
@@ -0,0 +1,26 @@
+import { Injectable, OnDestroy } from '@angular/core';
+import { RestApiService } from '../../shared/services/rest-api.service';
+import { apiRoot } from 'src/environments/environment';
+import { Series } from 'src/app/models/api/series';
+import { Observable, Subject } from 'rxjs';
+import { Response } from 'src/app/models/api/response';
+import { takeUntil } from 'rxjs/operators';
+
+@Injectable({
+  providedIn: 'root',
+})
+export class SeriesService implements OnDestroy {
+  url = `${apiRoot}/series`;
+  ngUnsubscribe = new Subject<void>();
+
+  constructor(private apiService: RestApiService) {}
+
+  getSeries(id: string, query: object): Observable<Response<Series>> {
+    return this.apiService.get<Response<Series>>(this.url, id, query, true, true).pipe(takeUntil(this.ngUnsubscribe));
+  }
+
+  ngOnDestroy(): void {
+    this.ngUnsubscribe.next();
+    this.ngUnsubscribe.complete();
+  }
+}
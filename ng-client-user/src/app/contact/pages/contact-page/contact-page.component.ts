@@ -23,6 +23,7 @@ export class ContactPageComponent implements OnInit, OnDestroy {
     message: new FormControl(''),
     subject: new FormControl(''),
     from_email: new FormControl(''),
+    website: new FormControl(''),
   });
   HCAPTCHA_SITE_KEY = HCAPTCHA_SITE_KEY;
   captchaConfig = {
@@ -74,6 +75,7 @@ export class ContactPageComponent implements OnInit, OnDestroy {
         this.contactForm.get('from_email').value,
         this.contactForm.get('message').value,
         this.contactForm.get('hcaptcha').value,
+        this.contactForm.get('website').value,
       )
       .pipe(takeUntil(this.ngUnsubscribe))
       .subscribe(
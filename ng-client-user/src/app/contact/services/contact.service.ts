@@ -19,9 +19,10 @@ export class ContactService implements OnDestroy {
     fromEmail: string,
     body: string,
     captchaToken: string,
+    website?: string,
   ): Observable<Response<number | undefined>> {
     return this.apiService
-      .create<Response<number | undefined>>(this.url, { subject, fromEmail, body, captchaToken }, {})
+      .create<Response<number | undefined>>(this.url, { subject, fromEmail, body, captchaToken, website }, {})
       .pipe(
         catchError((result) => {
           console.log('catchError');
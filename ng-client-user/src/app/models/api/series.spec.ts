@@ -0,0 +1,7 @@
+import { Series } from './series';
+
+describe('Series', () => {
+  it('should create an instance', () => {
+    expect(new Series()).toBeTruthy();
+  });
+});
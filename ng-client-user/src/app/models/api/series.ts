@@ -0,0 +1,18 @@
+export class SeriesPost {
+  id: number;
+  title: string;
+  slug: string;
+  position: number;
+  published: boolean;
+}
+
+export class Series {
+  id: number;
+  title: string;
+  slug: string;
+  description?: string;
+  published: boolean;
+  createdAt: Date;
+  updatedAt?: Date;
+  posts: SeriesPost[];
+}
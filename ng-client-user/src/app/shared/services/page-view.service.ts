@@ -21,6 +21,7 @@ interface LocationResponse {
 
 interface CreatePageViewRequest {
   pageUrl: string;
+  referrer: string | null;
 }
 
 @Injectable({
@@ -29,6 +30,8 @@ interface CreatePageViewRequest {
 export class PageViewService implements OnDestroy {
   url = `${apiRoot}/page-views`;
   ngUnsubscribe = new Subject<void>();
+  // document.referrer keeps pointing at the page that loaded the app, so only the first view has one
+  private referrerSent = false;
 
   constructor(
     private apiService: RestApiService,
@@ -47,7 +50,9 @@ export class PageViewService implements OnDestroy {
           // do something on end activity
           this.registerPageView({
             pageUrl: event.url,
+            referrer: this.referrerSent ? null : document.referrer || null,
           });
+          this.referrerSent = true;
         }
       });
     }
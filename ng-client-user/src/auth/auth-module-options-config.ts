@@ -154,6 +154,13 @@ export interface AuthModuleOptionsConfig {
    *
    */
   verifyEmailMethod?: HttpMethod;
+  /**
+   * Endpoint to use for undoing an email change(links to this should only be
+   * supplied through the notice sent to the old address), preceded by authEndpointPrefix.
+   * Defaults to 'undo-email-change'.
+   *
+   */
+  undoEmailChangeEndpoint?: string;
   /**
    * Endpoint to use for requesting a verification email, preceded by authEndpointPrefix.
    * Defaults to 'request-verification-email'.
@@ -190,7 +190,7 @@ export class AuthService implements OnDestroy {
    * Stores received token in the token storage
    *
    * Example:
-   * register('email', {email: 'email@example.com', name: 'Some Name', password: 'test'})
+   * register({email: 'email@example.com', displayName: 'Some Name', password: 'test', captchaToken: 'token'})
    *
    */
   register(data?: any): Observable<AuthResult> {
@@ -374,8 +374,7 @@ export class AuthService implements OnDestroy {
    * Sends forgot password request
    *
    * Example:
-   * requestPasswordReset({email: 'email@example.com'})
-   * requestPasswordReset({userName: 'username'})
+   * requestPasswordReset({email: 'email@example.com', captchaToken: 'token'})
    *
    */
   requestPasswordReset(data?: any): Observable<AuthResult> {
@@ -473,6 +472,32 @@ export class AuthService implements OnDestroy {
       .pipe(takeUntil(this.ngUnsubscribe));
   }
 
+  /**
+   * Uses the token sent to the old address after an email change to undo it
+   *
+   * Example:
+   * undoEmailChange()
+   *
+   */
+  undoEmailChange(): Observable<AuthResult> {
+    const data = {};
+    const url = `${this.authEndpointPrefix}${this.config.undoEmailChangeEndpoint ?? 'undo-email-change'}`;
+    // Undo tokens are email verification tokens, and arrive under the same query key
+    const tokenQueryKey = this.config?.verifyEmailConfig?.tokenQueryKey ?? 'email_confirm_token';
+    const tokenKey = this.config?.verifyEmailConfig?.tokenKey ?? 'token';
+    data[tokenKey] = this.route.snapshot.queryParams[tokenQueryKey];
+    return this.baseApiRequestWithAuth(this.http, url, {}, 'post', data)
+      .pipe(
+        map((res) => {
+          return new AuthResult(true, res, true, [], ['Your Email change has been undone!']);
+        }),
+        catchError((res) => {
+          return this.handleResponseError(res);
+        }),
+      )
+      .pipe(takeUntil(this.ngUnsubscribe));
+  }
+
   /**
    * Requests an email for email verification
    *
@@ -0,0 +1,5 @@
+DROP INDEX idx_api_tokens_user_id;
+DROP INDEX idx_api_tokens_token_hash;
+DROP INDEX idx_api_tokens_id;
+
+DROP TABLE api_tokens;
\ No newline at end of file
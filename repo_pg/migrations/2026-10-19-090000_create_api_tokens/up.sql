@@ -0,0 +1,24 @@
+CREATE TABLE api_tokens (
+  id SERIAL PRIMARY KEY,
+  user_id INTEGER NOT NULL,
+  name VARCHAR NOT NULL,
+  token_prefix VARCHAR NOT NULL,
+  token_hash VARCHAR NOT NULL,
+  scopes VARCHAR[] NOT NULL DEFAULT '{}',
+  revoked BOOLEAN NOT NULL DEFAULT 'f',
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  expires_at TIMESTAMP,
+  last_used_at TIMESTAMP,
+  CONSTRAINT api_token_user_fk
+    FOREIGN KEY(user_id) 
+	REFERENCES users(id)
+);
+
+CREATE UNIQUE INDEX idx_api_tokens_id 
+ON api_tokens(id);
+
+CREATE UNIQUE INDEX idx_api_tokens_token_hash
+ON api_tokens(token_hash);
+
+CREATE INDEX idx_api_tokens_user_id
+ON api_tokens(user_id);
@@ -0,0 +1,2 @@
+ALTER TABLE verify_email_tokens
+  ADD undo BOOLEAN NOT NULL DEFAULT FALSE;
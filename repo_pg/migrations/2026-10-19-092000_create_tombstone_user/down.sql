@@ -0,0 +1,2 @@
+DELETE FROM users
+  WHERE display_name = '[deleted]' AND email = '[deleted]';
@@ -0,0 +1,4 @@
+-- Comments of deleted accounts are moved to this user when kept.
+-- The empty password hash never verifies, so nobody can log in as it.
+INSERT INTO users (display_name, email, password, role)
+  VALUES ('[deleted]', '[deleted]', '', 'Ghost');
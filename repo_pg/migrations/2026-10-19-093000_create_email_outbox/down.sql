@@ -0,0 +1,6 @@
+DROP INDEX idx_email_outbox_status_next_attempt_at;
+DROP INDEX idx_email_outbox_id;
+
+DROP TABLE email_outbox;
+
+DROP TYPE email_status;
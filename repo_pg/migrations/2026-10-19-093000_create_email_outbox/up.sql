@@ -0,0 +1,22 @@
+CREATE TYPE email_status as enum('Pending', 'Sent', 'Failed');
+
+CREATE TABLE email_outbox (
+  id SERIAL PRIMARY KEY,
+  to_address VARCHAR NOT NULL,
+  to_name VARCHAR,
+  subject VARCHAR NOT NULL,
+  body_html TEXT NOT NULL,
+  body_text TEXT NOT NULL,
+  status email_status NOT NULL DEFAULT 'Pending',
+  attempts INTEGER NOT NULL DEFAULT 0,
+  last_error TEXT,
+  next_attempt_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  sent_at TIMESTAMP
+);
+
+CREATE UNIQUE INDEX idx_email_outbox_id 
+ON email_outbox(id);
+
+CREATE INDEX idx_email_outbox_status_next_attempt_at
+ON email_outbox(status, next_attempt_at);
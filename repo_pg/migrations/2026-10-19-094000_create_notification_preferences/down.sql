@@ -0,0 +1,12 @@
+DROP INDEX idx_notification_preferences_user_id;
+DROP INDEX idx_notification_preferences_id;
+
+DROP TABLE notification_preferences;
+
+ALTER TABLE email_outbox
+DROP COLUMN list_unsubscribe;
+
+DROP INDEX idx_blog_post_comments_parent_id;
+
+ALTER TABLE blog_post_comments
+DROP COLUMN parent_id;
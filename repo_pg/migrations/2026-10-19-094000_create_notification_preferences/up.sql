@@ -0,0 +1,31 @@
+ALTER TABLE blog_post_comments
+ADD COLUMN parent_id INTEGER
+  CONSTRAINT blog_post_comment_parent_fk
+    REFERENCES blog_post_comments(id)
+    ON DELETE SET NULL;
+
+CREATE INDEX idx_blog_post_comments_parent_id
+ON blog_post_comments(parent_id);
+
+ALTER TABLE email_outbox
+ADD COLUMN list_unsubscribe VARCHAR;
+
+CREATE TABLE notification_preferences (
+  id SERIAL PRIMARY KEY,
+  user_id INTEGER NOT NULL,
+  comment_replies BOOLEAN NOT NULL DEFAULT 't',
+  moderation_decisions BOOLEAN NOT NULL DEFAULT 't',
+  new_posts_digest BOOLEAN NOT NULL DEFAULT 'f',
+  last_digest_sent_at TIMESTAMP,
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  updated_at TIMESTAMP,
+  CONSTRAINT notification_preferences_user_fk
+    FOREIGN KEY(user_id) 
+	REFERENCES users(id)
+);
+
+CREATE UNIQUE INDEX idx_notification_preferences_id 
+ON notification_preferences(id);
+
+CREATE UNIQUE INDEX idx_notification_preferences_user_id
+ON notification_preferences(user_id);
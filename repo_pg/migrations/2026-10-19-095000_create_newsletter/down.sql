@@ -0,0 +1,15 @@
+DROP INDEX idx_newsletter_deliveries_email_outbox_id;
+DROP INDEX idx_newsletter_deliveries_issue_subscriber;
+DROP INDEX idx_newsletter_deliveries_id;
+
+DROP TABLE newsletter_deliveries;
+
+DROP INDEX idx_newsletter_issues_id;
+
+DROP TABLE newsletter_issues;
+
+DROP INDEX idx_newsletter_subscribers_token;
+DROP INDEX idx_newsletter_subscribers_email;
+DROP INDEX idx_newsletter_subscribers_id;
+
+DROP TABLE newsletter_subscribers;
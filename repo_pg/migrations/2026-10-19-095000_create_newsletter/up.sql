@@ -0,0 +1,61 @@
+CREATE TABLE newsletter_subscribers (
+  id SERIAL PRIMARY KEY,
+  email VARCHAR NOT NULL,
+  token VARCHAR NOT NULL,
+  confirmed BOOLEAN NOT NULL DEFAULT 'f',
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  confirmed_at TIMESTAMP,
+  unsubscribed_at TIMESTAMP
+);
+
+CREATE UNIQUE INDEX idx_newsletter_subscribers_id 
+ON newsletter_subscribers(id);
+
+CREATE UNIQUE INDEX idx_newsletter_subscribers_email
+ON newsletter_subscribers(email);
+
+CREATE UNIQUE INDEX idx_newsletter_subscribers_token
+ON newsletter_subscribers(token);
+
+CREATE TABLE newsletter_issues (
+  id SERIAL PRIMARY KEY,
+  subject VARCHAR NOT NULL,
+  intro TEXT NOT NULL,
+  blog_post_ids INTEGER[] NOT NULL DEFAULT '{}',
+  user_id INTEGER NOT NULL,
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  updated_at TIMESTAMP,
+  sent_at TIMESTAMP,
+  CONSTRAINT newsletter_issue_user_fk
+    FOREIGN KEY(user_id) 
+	REFERENCES users(id)
+);
+
+CREATE UNIQUE INDEX idx_newsletter_issues_id 
+ON newsletter_issues(id);
+
+CREATE TABLE newsletter_deliveries (
+  id SERIAL PRIMARY KEY,
+  newsletter_issue_id INTEGER NOT NULL,
+  newsletter_subscriber_id INTEGER NOT NULL,
+  email_outbox_id INTEGER,
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  CONSTRAINT newsletter_delivery_issue_fk
+    FOREIGN KEY(newsletter_issue_id) 
+	REFERENCES newsletter_issues(id),
+  CONSTRAINT newsletter_delivery_subscriber_fk
+    FOREIGN KEY(newsletter_subscriber_id) 
+	REFERENCES newsletter_subscribers(id),
+  CONSTRAINT newsletter_delivery_email_outbox_fk
+    FOREIGN KEY(email_outbox_id) 
+	REFERENCES email_outbox(id)
+);
+
+CREATE UNIQUE INDEX idx_newsletter_deliveries_id 
+ON newsletter_deliveries(id);
+
+CREATE UNIQUE INDEX idx_newsletter_deliveries_issue_subscriber
+ON newsletter_deliveries(newsletter_issue_id, newsletter_subscriber_id);
+
+CREATE INDEX idx_newsletter_deliveries_email_outbox_id
+ON newsletter_deliveries(email_outbox_id);
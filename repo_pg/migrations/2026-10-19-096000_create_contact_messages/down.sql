@@ -0,0 +1,2 @@
+DROP TABLE contact_message_replies;
+DROP TABLE contact_messages;
@@ -0,0 +1,45 @@
+CREATE TABLE contact_messages (
+  id SERIAL PRIMARY KEY,
+  from_email VARCHAR NOT NULL,
+  subject VARCHAR NOT NULL,
+  body TEXT NOT NULL,
+  ip_address VARCHAR,
+  user_agent VARCHAR,
+  spam_score INTEGER NOT NULL DEFAULT 0,
+  spam BOOLEAN NOT NULL DEFAULT 'f',
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  read_at TIMESTAMP,
+  archived_at TIMESTAMP,
+  replied_at TIMESTAMP
+);
+
+CREATE UNIQUE INDEX idx_contact_messages_id 
+ON contact_messages(id);
+
+CREATE INDEX idx_contact_messages_ip_address_created_at
+ON contact_messages(ip_address, created_at);
+
+CREATE TABLE contact_message_replies (
+  id SERIAL PRIMARY KEY,
+  contact_message_id INTEGER NOT NULL,
+  user_id INTEGER NOT NULL,
+  body TEXT NOT NULL,
+  email_outbox_id INTEGER,
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  CONSTRAINT contact_message_reply_message_fk
+    FOREIGN KEY(contact_message_id) 
+	REFERENCES contact_messages(id)
+	ON DELETE CASCADE,
+  CONSTRAINT contact_message_reply_user_fk
+    FOREIGN KEY(user_id) 
+	REFERENCES users(id),
+  CONSTRAINT contact_message_reply_email_outbox_fk
+    FOREIGN KEY(email_outbox_id) 
+	REFERENCES email_outbox(id)
+);
+
+CREATE UNIQUE INDEX idx_contact_message_replies_id 
+ON contact_message_replies(id);
+
+CREATE INDEX idx_contact_message_replies_contact_message_id
+ON contact_message_replies(contact_message_id);
@@ -0,0 +1,11 @@
+CREATE OR REPLACE FUNCTION refresh_search()
+  RETURNS TRIGGER LANGUAGE plpgsql
+  AS $$
+  BEGIN
+  REFRESH MATERIALIZED VIEW search_items;
+  RETURN NULL;
+  END $$;
+
+DROP MATERIALIZED VIEW search_title_words;
+
+DROP INDEX search_items_title_trgm_idx;
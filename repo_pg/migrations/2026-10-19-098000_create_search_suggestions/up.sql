@@ -0,0 +1,23 @@
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX search_items_title_trgm_idx ON search_items USING GIN (title gin_trgm_ops);
+
+-- Every word used in a title, to correct misspelled queries against.
+CREATE MATERIALIZED VIEW search_title_words AS
+  SELECT DISTINCT
+    lower(word)::VARCHAR as word,
+    item_type
+    FROM search_items, regexp_split_to_table(title, '[^[:alnum:]]+') as word
+    WHERE length(word) > 2
+  ;
+
+CREATE INDEX search_title_words_word_trgm_idx ON search_title_words USING GIN (word gin_trgm_ops);
+
+CREATE OR REPLACE FUNCTION refresh_search()
+  RETURNS TRIGGER LANGUAGE plpgsql
+  AS $$
+  BEGIN
+  REFRESH MATERIALIZED VIEW search_items;
+  REFRESH MATERIALIZED VIEW search_title_words;
+  RETURN NULL;
+  END $$;
@@ -0,0 +1,47 @@
+DROP TRIGGER request_post_search_refresh ON blog_posts;
+DROP TRIGGER request_post_category_search_refresh ON blog_posts_categories;
+DROP TRIGGER request_category_search_refresh ON categories;
+DROP TRIGGER request_project_search_refresh ON projects;
+DROP TRIGGER request_project_technology_search_refresh ON projects_technologies;
+DROP TRIGGER request_technology_search_refresh ON technologies;
+DROP TRIGGER request_link_search_refresh ON home_page_links;
+DROP TRIGGER request_static_page_search_refresh ON static_pages;
+DROP FUNCTION request_search_refresh();
+
+DROP INDEX search_title_words_word_item_type_idx;
+DROP INDEX search_items_id_idx;
+
+DROP TABLE search_refreshes;
+
+CREATE OR REPLACE FUNCTION refresh_search()
+  RETURNS TRIGGER LANGUAGE plpgsql
+  AS $$
+  BEGIN
+  REFRESH MATERIALIZED VIEW search_items;
+  REFRESH MATERIALIZED VIEW search_title_words;
+  RETURN NULL;
+  END $$;
+
+CREATE TRIGGER refresh_post_search
+  AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE
+  ON blog_posts
+  FOR EACH STATEMENT
+  EXECUTE PROCEDURE refresh_search();
+
+CREATE TRIGGER refresh_project_search
+  AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE
+  ON projects
+  FOR EACH STATEMENT
+  EXECUTE PROCEDURE refresh_search();
+
+CREATE TRIGGER refresh_link_search
+  AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE
+  ON home_page_links
+  FOR EACH STATEMENT
+  EXECUTE PROCEDURE refresh_search();
+
+CREATE TRIGGER refresh_static_page_search
+  AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE
+  ON static_pages
+  FOR EACH STATEMENT
+  EXECUTE PROCEDURE refresh_search();
@@ -0,0 +1,77 @@
+DROP TRIGGER refresh_post_search ON blog_posts;
+DROP TRIGGER refresh_project_search ON projects;
+DROP TRIGGER refresh_link_search ON home_page_links;
+DROP TRIGGER refresh_static_page_search ON static_pages;
+DROP FUNCTION refresh_search();
+
+-- A single row recording when search was last asked for and given a refresh. Writes
+-- only mark it, and the API refreshes the views once the writes have settled.
+CREATE TABLE search_refreshes (
+  id INTEGER PRIMARY KEY DEFAULT 1,
+  requested_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  refreshed_at TIMESTAMP,
+  refreshed_through TIMESTAMP,
+  CONSTRAINT search_refreshes_single_row CHECK (id = 1)
+);
+
+INSERT INTO search_refreshes(id) VALUES (1);
+
+-- Needed to refresh the views concurrently
+CREATE UNIQUE INDEX search_items_id_idx ON search_items(id);
+CREATE UNIQUE INDEX search_title_words_word_item_type_idx ON search_title_words(word, item_type);
+
+CREATE FUNCTION request_search_refresh()
+  RETURNS TRIGGER LANGUAGE plpgsql
+  AS $$
+  BEGIN
+  UPDATE search_refreshes SET requested_at = clock_timestamp() WHERE id = 1;
+  RETURN NULL;
+  END $$;
+
+CREATE TRIGGER request_post_search_refresh
+  AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE
+  ON blog_posts
+  FOR EACH STATEMENT
+  EXECUTE PROCEDURE request_search_refresh();
+
+CREATE TRIGGER request_post_category_search_refresh
+  AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE
+  ON blog_posts_categories
+  FOR EACH STATEMENT
+  EXECUTE PROCEDURE request_search_refresh();
+
+CREATE TRIGGER request_category_search_refresh
+  AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE
+  ON categories
+  FOR EACH STATEMENT
+  EXECUTE PROCEDURE request_search_refresh();
+
+CREATE TRIGGER request_project_search_refresh
+  AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE
+  ON projects
+  FOR EACH STATEMENT
+  EXECUTE PROCEDURE request_search_refresh();
+
+CREATE TRIGGER request_project_technology_search_refresh
+  AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE
+  ON projects_technologies
+  FOR EACH STATEMENT
+  EXECUTE PROCEDURE request_search_refresh();
+
+CREATE TRIGGER request_technology_search_refresh
+  AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE
+  ON technologies
+  FOR EACH STATEMENT
+  EXECUTE PROCEDURE request_search_refresh();
+
+CREATE TRIGGER request_link_search_refresh
+  AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE
+  ON home_page_links
+  FOR EACH STATEMENT
+  EXECUTE PROCEDURE request_search_refresh();
+
+CREATE TRIGGER request_static_page_search_refresh
+  AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE
+  ON static_pages
+  FOR EACH STATEMENT
+  EXECUTE PROCEDURE request_search_refresh();
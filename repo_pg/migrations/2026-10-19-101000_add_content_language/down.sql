@@ -0,0 +1,104 @@
+DROP MATERIALIZED VIEW search_title_words;
+DROP MATERIALIZED VIEW search_items;
+
+CREATE MATERIALIZED VIEW search_items AS
+  SELECT 
+    projects.id::VARCHAR || 'project'::VARCHAR as id,
+    setweight(to_tsvector('english', projects.name), 'A') ||
+    setweight(to_tsvector('english', COALESCE(description, '')), 'B') ||
+    setweight(to_tsvector('english', COALESCE(array_to_string(array_agg("technologies"."name"), ' '), '')), 'C') ||
+    setweight(to_tsvector('english', body), 'D') as search_vec,
+    projects.name as title,
+    projects.created_at as created_at,
+    projects.updated_at as updated_at,
+    projects.cover_image as image,
+    COALESCE(description, '') as description,
+    'Project'::search_item_type as item_type,
+    '/projects/' || slug::VARCHAR as link,
+    regexp_replace(body, '<[^>]*>', '', 'g') as body,
+    ARRAY[]::VARCHAR[] as categories,
+    array_remove(array_agg(DISTINCT "technologies"."name"), NULL)::VARCHAR[] as technologies
+    FROM projects
+    LEFT JOIN projects_technologies ON projects_technologies.project_id = projects.id
+    LEFT JOIN technologies ON technologies.id = projects_technologies.technology_id  
+    WHERE published =  TRUE
+    GROUP BY projects.id
+  UNION
+  SELECT 
+    blog_posts.id::VARCHAR || 'blog'::VARCHAR as id,
+    setweight(to_tsvector('english', blog_posts.title), 'A') ||
+    setweight(to_tsvector('english', COALESCE(description, '')), 'B') ||
+    setweight(to_tsvector('english', COALESCE(array_to_string(array_agg("categories"."name"), ' '), '')), 'C') ||
+    setweight(to_tsvector('english', body), 'D') as search_vec,
+    blog_posts.title as title,
+    blog_posts.created_at as created_at,
+    blog_posts.updated_at as updated_at,
+    NULL as image,
+    COALESCE(description, '') as description,
+    'Blog Post'::search_item_type as item_type,
+    '/blog/' || slug::VARCHAR as link,
+    regexp_replace(body, '<[^>]*>', '', 'g') as body,
+    array_remove(array_agg(DISTINCT "categories"."name"), NULL)::VARCHAR[] as categories,
+    ARRAY[]::VARCHAR[] as technologies
+    FROM blog_posts
+    LEFT JOIN blog_posts_categories ON blog_posts_categories.blog_post_id = blog_posts.id
+    LEFT JOIN categories ON categories.id = blog_posts_categories.category_id 
+    WHERE published =  TRUE
+    GROUP BY blog_posts.id
+  UNION
+    SELECT
+      id,
+      setweight(to_tsvector('english', title), 'A') ||
+      setweight(to_tsvector('english', description), 'B') as search_vec,
+      title,
+      NULL as created_at,
+      NULL as updated_at,
+      NULL as image,
+      description,
+      item_type,
+      link,
+      description as body,
+      ARRAY[]::VARCHAR[] as categories,
+      ARRAY[]::VARCHAR[] as technologies
+      FROM static_pages
+  UNION
+  SELECT 
+    home_page_links.id::VARCHAR || 'hpl'::VARCHAR as id,
+    setweight(to_tsvector('english', home_page_links.name), 'A') ||
+    setweight(to_tsvector('english', home_page_links.target || ' ' || home_page_links.image), 'D') as search_vec,
+    home_page_links.name as title,
+    NULL as created_at,
+    NULL as updated_at,
+    home_page_links.image as image,
+    home_page_links.name as description,
+    'External Link'::search_item_type as item_type,
+    home_page_links.target as link,
+    home_page_links.name as body,
+    ARRAY[]::VARCHAR[] as categories,
+    ARRAY[]::VARCHAR[] as technologies
+    FROM home_page_links
+  ;
+
+CREATE INDEX search_items_search_vec_idx ON search_items USING GIN (search_vec);
+CREATE INDEX search_items_title_trgm_idx ON search_items USING GIN (title gin_trgm_ops);
+CREATE UNIQUE INDEX search_items_id_idx ON search_items(id);
+
+-- Every word used in a title, to correct misspelled queries against.
+CREATE MATERIALIZED VIEW search_title_words AS
+  SELECT DISTINCT
+    lower(word)::VARCHAR as word,
+    item_type
+    FROM search_items, regexp_split_to_table(title, '[^[:alnum:]]+') as word
+    WHERE length(word) > 2
+  ;
+
+CREATE INDEX search_title_words_word_trgm_idx ON search_title_words USING GIN (word gin_trgm_ops);
+
+CREATE UNIQUE INDEX search_title_words_word_item_type_idx ON search_title_words(word, item_type);
+
+ALTER TABLE text_bodies DROP COLUMN language;
+ALTER TABLE projects DROP COLUMN language;
+ALTER TABLE blog_posts DROP COLUMN language;
+
+DROP FUNCTION search_config(content_language);
+DROP TYPE content_language;
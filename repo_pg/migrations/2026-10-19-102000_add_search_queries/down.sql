@@ -0,0 +1,2 @@
+DROP TABLE search_clicks;
+DROP TABLE search_queries;
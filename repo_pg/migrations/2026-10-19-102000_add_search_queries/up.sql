@@ -0,0 +1,22 @@
+-- Searches made on the site. Nothing identifying who searched is kept.
+CREATE TABLE search_queries (
+  id SERIAL PRIMARY KEY,
+  query VARCHAR NOT NULL,
+  item_type search_item_type,
+  result_count BIGINT NOT NULL,
+  created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX search_queries_created_at_idx ON search_queries(created_at);
+CREATE INDEX search_queries_query_idx ON search_queries(query);
+
+-- Results followed from a search.
+CREATE TABLE search_clicks (
+  id SERIAL PRIMARY KEY,
+  search_query_id INTEGER NOT NULL REFERENCES search_queries(id) ON DELETE CASCADE,
+  link VARCHAR NOT NULL,
+  position INTEGER,
+  created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX search_clicks_search_query_id_idx ON search_clicks(search_query_id);
@@ -0,0 +1 @@
+DROP INDEX idx_page_views_created_at;
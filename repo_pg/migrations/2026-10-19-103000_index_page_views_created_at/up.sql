@@ -0,0 +1,2 @@
+CREATE INDEX idx_page_views_created_at
+ON page_views(created_at);
@@ -0,0 +1,13 @@
+ALTER TABLE page_views
+  DROP COLUMN utm_content,
+  DROP COLUMN utm_term,
+  DROP COLUMN utm_campaign,
+  DROP COLUMN utm_medium,
+  DROP COLUMN utm_source,
+  DROP COLUMN referrer_type,
+  DROP COLUMN referrer_source,
+  DROP COLUMN referrer,
+  DROP COLUMN visit_id;
+
+DROP TABLE visits;
+DROP TYPE referrer_type;
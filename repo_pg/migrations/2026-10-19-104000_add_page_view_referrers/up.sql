@@ -0,0 +1,27 @@
+CREATE TYPE referrer_type AS ENUM ('Search', 'Social', 'Website');
+
+-- Views by the same visitor with no more than half an hour between them.
+CREATE TABLE visits (
+  id SERIAL PRIMARY KEY,
+  id_hash VARCHAR NOT NULL,
+  landing_page VARCHAR NOT NULL,
+  exit_page VARCHAR NOT NULL,
+  views INTEGER NOT NULL DEFAULT 1,
+  started_at TIMESTAMP NOT NULL DEFAULT NOW(),
+  last_viewed_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX visits_id_hash_last_viewed_at_idx ON visits(id_hash, last_viewed_at);
+
+ALTER TABLE page_views
+  ADD COLUMN visit_id INTEGER REFERENCES visits(id) ON DELETE SET NULL,
+  ADD COLUMN referrer VARCHAR,
+  ADD COLUMN referrer_source VARCHAR,
+  ADD COLUMN referrer_type referrer_type,
+  ADD COLUMN utm_source VARCHAR,
+  ADD COLUMN utm_medium VARCHAR,
+  ADD COLUMN utm_campaign VARCHAR,
+  ADD COLUMN utm_term VARCHAR,
+  ADD COLUMN utm_content VARCHAR;
+
+CREATE INDEX page_views_visit_id_idx ON page_views(visit_id);
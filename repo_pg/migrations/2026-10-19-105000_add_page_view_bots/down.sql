@@ -0,0 +1,3 @@
+DROP INDEX page_views_id_hash_created_at_idx;
+
+ALTER TABLE page_views DROP COLUMN is_bot;
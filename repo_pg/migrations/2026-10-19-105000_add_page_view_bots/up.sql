@@ -0,0 +1,9 @@
+ALTER TABLE page_views ADD COLUMN is_bot BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Earlier views are classified by the user agent list the api had at the time
+UPDATE page_views SET is_bot = TRUE
+  WHERE user_agent IS NULL
+    OR btrim(user_agent) = ''
+    OR user_agent ~* '(bot|crawl|spider|slurp|archiver|scraper|headlesschrome|phantomjs|puppeteer|playwright|selenium|lighthouse|pagespeed|gtmetrix|pingdom|uptimerobot|statuscake|site24x7|monitor|facebookexternalhit|facebookcatalog|embedly|whatsapp|skypeuripreview|bitlypreview|preview|feedfetcher|feedly|inoreader|newsblur|curl/|wget/|httpie/|python-requests|python-urllib|aiohttp|go-http-client|okhttp|java/|apache-httpclient|libwww-perl|node-fetch|axios/|scrapy)';
+
+CREATE INDEX page_views_id_hash_created_at_idx ON page_views(id_hash, created_at);
@@ -0,0 +1,3 @@
+DROP INDEX identification_cookies_expires_at_idx;
+DROP TABLE page_view_daily_stats;
+DROP TABLE daily_salts;
@@ -0,0 +1,20 @@
+-- Salts for counting visitors without cookies. Each one is deleted once its day is
+-- over, so hashes made with it can't be matched to an address again.
+CREATE TABLE daily_salts (
+  day DATE PRIMARY KEY,
+  salt VARCHAR NOT NULL
+);
+
+-- Page views past the retention period, rolled up per day. Rows without a page url
+-- hold the totals of their day.
+CREATE TABLE page_view_daily_stats (
+  id SERIAL PRIMARY KEY,
+  day DATE NOT NULL,
+  page_url VARCHAR,
+  is_bot BOOLEAN NOT NULL,
+  views BIGINT NOT NULL,
+  unique_visitors BIGINT NOT NULL
+);
+
+CREATE INDEX page_view_daily_stats_day_idx ON page_view_daily_stats(day);
+CREATE INDEX identification_cookies_expires_at_idx ON identification_cookies(expires_at);
@@ -0,0 +1,5 @@
+DROP INDEX page_view_daily_stats_page_path_idx;
+ALTER TABLE page_view_daily_stats DROP COLUMN page_path;
+
+DROP INDEX page_views_page_path_idx;
+ALTER TABLE page_views DROP COLUMN page_path;
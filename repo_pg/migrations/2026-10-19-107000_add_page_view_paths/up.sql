@@ -0,0 +1,10 @@
+-- The page's path without query or fragment, to match views against content slugs
+ALTER TABLE page_views ADD COLUMN page_path VARCHAR;
+UPDATE page_views SET page_path = split_part(split_part(page_url, '#', 1), '?', 1);
+ALTER TABLE page_views ALTER COLUMN page_path SET NOT NULL;
+CREATE INDEX page_views_page_path_idx ON page_views(page_path);
+
+ALTER TABLE page_view_daily_stats ADD COLUMN page_path VARCHAR;
+UPDATE page_view_daily_stats SET page_path = split_part(split_part(page_url, '#', 1), '?', 1)
+  WHERE page_url IS NOT NULL;
+CREATE INDEX page_view_daily_stats_page_path_idx ON page_view_daily_stats(page_path);
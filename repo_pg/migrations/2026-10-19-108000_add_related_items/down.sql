@@ -0,0 +1 @@
+DROP MATERIALIZED VIEW related_items;
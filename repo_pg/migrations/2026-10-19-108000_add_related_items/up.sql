@@ -0,0 +1,75 @@
+-- Up to ten related items for every published post and project, among items of the
+-- same type. Each shared category or technology scores a point, and the share of
+-- search terms the two have in common scores up to five more. Refreshed along with
+-- search_items, which it is built from.
+CREATE MATERIALIZED VIEW related_items AS
+  WITH items AS (
+    SELECT
+      substring(id FROM '^[0-9]+')::INTEGER as item_id,
+      item_type,
+      title,
+      created_at,
+      image,
+      description,
+      link,
+      language,
+      categories || technologies as tags,
+      tsvector_to_array(search_vec) as lexemes
+      FROM search_items
+      WHERE item_type IN ('Project', 'Blog Post')
+  ),
+  pairs AS (
+    SELECT
+      items.item_id,
+      items.item_type,
+      related.item_id as related_id,
+      related.title,
+      related.created_at,
+      related.image,
+      related.description,
+      related.link,
+      related.language,
+      shared.tags +
+        5 * shared.lexemes::FLOAT8 /
+        GREATEST(cardinality(items.lexemes) + cardinality(related.lexemes) - shared.lexemes, 1)
+        as score
+      FROM items
+      JOIN items as related
+        ON related.item_type = items.item_type AND related.item_id <> items.item_id
+      CROSS JOIN LATERAL (
+        SELECT
+          (SELECT count(*) FROM (
+            SELECT unnest(items.tags) INTERSECT SELECT unnest(related.tags)
+          ) as shared_tags) as tags,
+          (SELECT count(*) FROM (
+            SELECT unnest(items.lexemes) INTERSECT SELECT unnest(related.lexemes)
+          ) as shared_lexemes) as lexemes
+      ) as shared
+  ),
+  ranked AS (
+    SELECT
+      pairs.*,
+      row_number() OVER (
+        PARTITION BY item_type, item_id ORDER BY score DESC, created_at DESC, related_id
+      )::INTEGER as rank
+      FROM pairs
+      WHERE score >= 0.25
+  )
+  SELECT
+    item_id,
+    item_type,
+    rank,
+    related_id,
+    title,
+    created_at,
+    image,
+    description,
+    link,
+    language,
+    score
+    FROM ranked
+    WHERE rank <= 10
+  ;
+
+-- Needed to refresh the view concurrently
+CREATE UNIQUE INDEX related_items_item_type_item_id_rank_idx ON related_items(item_type, item_id, rank);
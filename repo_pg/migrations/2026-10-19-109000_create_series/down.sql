@@ -0,0 +1,5 @@
+-- Postgres can't drop enum values, so 'Series' stays in search_item_type, unused
+DELETE FROM search_queries WHERE item_type = 'Series';
+
+DROP TABLE series_blog_posts;
+DROP TABLE series;
@@ -0,0 +1,27 @@
+-- Posts written as parts of one whole, like a multi-part tutorial.
+CREATE TABLE series (
+  id SERIAL PRIMARY KEY,
+  title VARCHAR NOT NULL,
+  slug VARCHAR NOT NULL,
+  description VARCHAR,
+  published BOOLEAN NOT NULL DEFAULT FALSE,
+  language content_language NOT NULL DEFAULT 'English',
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  updated_at TIMESTAMP
+);
+
+CREATE UNIQUE INDEX idx_series_slug ON series(slug);
+
+-- The posts of each series, in order from position 1. A post is part of one series at most.
+CREATE TABLE series_blog_posts (
+  series_id INTEGER NOT NULL REFERENCES series(id) ON DELETE CASCADE,
+  blog_post_id INTEGER NOT NULL REFERENCES blog_posts(id) ON DELETE CASCADE,
+  position INTEGER NOT NULL,
+  PRIMARY KEY (series_id, blog_post_id),
+  CONSTRAINT series_blog_posts_blog_post_id_unique UNIQUE (blog_post_id),
+  CONSTRAINT series_blog_posts_position_unique UNIQUE (series_id, position)
+);
+
+-- Added here, as a new enum value can't be used in the transaction that adds it.
+-- It may be left over from an earlier rollback.
+ALTER TYPE search_item_type ADD VALUE IF NOT EXISTS 'Series';
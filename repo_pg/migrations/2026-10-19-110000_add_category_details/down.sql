@@ -0,0 +1,20 @@
+DROP TRIGGER IF EXISTS set_technologies_slug ON technologies;
+DROP TRIGGER IF EXISTS set_categories_slug ON categories;
+
+ALTER TABLE technologies
+    DROP COLUMN parent_id,
+    DROP COLUMN color,
+    DROP COLUMN icon,
+    DROP COLUMN description,
+    DROP COLUMN slug;
+
+ALTER TABLE categories
+    DROP COLUMN parent_id,
+    DROP COLUMN color,
+    DROP COLUMN icon,
+    DROP COLUMN description,
+    DROP COLUMN slug;
+
+DROP FUNCTION IF EXISTS set_slug_from_name();
+DROP FUNCTION IF EXISTS unique_slug(VARCHAR, VARCHAR, VARCHAR);
+DROP FUNCTION IF EXISTS slugify(VARCHAR);
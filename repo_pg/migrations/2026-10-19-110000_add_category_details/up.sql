@@ -0,0 +1,105 @@
+-- Slugs, descriptions, icons, colors and nesting for categories and technologies.
+
+CREATE OR REPLACE FUNCTION slugify(value VARCHAR) RETURNS VARCHAR AS $$
+    SELECT trim(BOTH '-' FROM regexp_replace(lower(value), '[^[:alnum:]]+', '-', 'g'));
+$$ LANGUAGE sql IMMUTABLE;
+
+-- A slug made from `name` that no row of `table_name` has yet, numbered if it is
+-- taken. Two transactions can still pick the same one at once; the unique index
+-- then fails the later insert with a conflict, which the client can retry.
+CREATE OR REPLACE FUNCTION unique_slug(table_name VARCHAR, name VARCHAR, fallback VARCHAR)
+RETURNS VARCHAR AS $$
+DECLARE
+    base VARCHAR;
+    candidate VARCHAR;
+    suffix INTEGER := 1;
+    taken BOOLEAN;
+BEGIN
+    base := slugify(name);
+    IF base = '' THEN
+        base := fallback;
+    END IF;
+    candidate := base;
+    LOOP
+        EXECUTE format('SELECT EXISTS (SELECT 1 FROM %I WHERE slug = $1)', table_name)
+            INTO taken
+            USING candidate;
+        EXIT WHEN NOT taken;
+        suffix := suffix + 1;
+        candidate := base || '-' || suffix;
+    END LOOP;
+    RETURN candidate;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Rows inserted by name only, as when a post or project names a new category or
+-- technology, get a slug made from the name.
+CREATE OR REPLACE FUNCTION set_slug_from_name() RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.slug IS NULL THEN
+        NEW.slug := unique_slug(TG_TABLE_NAME::VARCHAR, NEW.name, NEW.id::VARCHAR);
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Existing rows get their slugs one at a time, oldest first, so an earlier row keeps
+-- the plain slug and a numbered one never collides with a name like "Foo 2".
+CREATE OR REPLACE FUNCTION backfill_slugs(table_name VARCHAR) RETURNS VOID AS $$
+DECLARE
+    row RECORD;
+BEGIN
+    FOR row IN EXECUTE format('SELECT id, name FROM %I ORDER BY id', table_name) LOOP
+        EXECUTE format('UPDATE %I SET slug = $1 WHERE id = $2', table_name)
+            USING unique_slug(table_name, row.name, row.id::VARCHAR), row.id;
+    END LOOP;
+END;
+$$ LANGUAGE plpgsql;
+
+ALTER TABLE categories
+    ADD COLUMN slug VARCHAR,
+    ADD COLUMN description VARCHAR,
+    ADD COLUMN icon VARCHAR,
+    ADD COLUMN color VARCHAR,
+    ADD COLUMN parent_id INTEGER REFERENCES categories (id) ON DELETE SET NULL;
+
+SELECT backfill_slugs('categories');
+
+ALTER TABLE categories ALTER COLUMN slug SET NOT NULL;
+
+CREATE UNIQUE INDEX idx_categories_slug
+ON categories (slug);
+
+CREATE INDEX idx_categories_parent_id
+ON categories (parent_id);
+
+CREATE TRIGGER set_categories_slug
+  BEFORE INSERT
+  ON categories
+  FOR EACH ROW
+  EXECUTE PROCEDURE set_slug_from_name();
+
+ALTER TABLE technologies
+    ADD COLUMN slug VARCHAR,
+    ADD COLUMN description VARCHAR,
+    ADD COLUMN icon VARCHAR,
+    ADD COLUMN color VARCHAR,
+    ADD COLUMN parent_id INTEGER REFERENCES technologies (id) ON DELETE SET NULL;
+
+SELECT backfill_slugs('technologies');
+
+ALTER TABLE technologies ALTER COLUMN slug SET NOT NULL;
+
+CREATE UNIQUE INDEX idx_technologies_slug
+ON technologies (slug);
+
+CREATE INDEX idx_technologies_parent_id
+ON technologies (parent_id);
+
+CREATE TRIGGER set_technologies_slug
+  BEFORE INSERT
+  ON technologies
+  FOR EACH ROW
+  EXECUTE PROCEDURE set_slug_from_name();
+
+DROP FUNCTION backfill_slugs(VARCHAR);
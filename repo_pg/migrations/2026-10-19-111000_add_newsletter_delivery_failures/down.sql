@@ -0,0 +1,3 @@
+ALTER TABLE newsletter_deliveries
+    DROP COLUMN last_error,
+    DROP COLUMN failed_at;
@@ -0,0 +1,5 @@
+-- Deliveries whose email could not be rendered are marked failed so the worker
+-- stops picking them up.
+ALTER TABLE newsletter_deliveries
+    ADD COLUMN failed_at TIMESTAMP,
+    ADD COLUMN last_error VARCHAR;
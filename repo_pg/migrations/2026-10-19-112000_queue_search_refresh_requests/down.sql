@@ -0,0 +1,13 @@
+CREATE OR REPLACE FUNCTION request_search_refresh()
+  RETURNS TRIGGER LANGUAGE plpgsql
+  AS $$
+  BEGIN
+  UPDATE search_refreshes SET requested_at = clock_timestamp() WHERE id = 1;
+  RETURN NULL;
+  END $$;
+
+UPDATE search_refreshes
+SET requested_at = GREATEST(requested_at, (SELECT max(requested_at) FROM search_refresh_requests))
+WHERE EXISTS (SELECT 1 FROM search_refresh_requests);
+
+DROP TABLE search_refresh_requests;
@@ -0,0 +1,19 @@
+-- Content writes append a request instead of updating the single search_refreshes
+-- row, so concurrent writes no longer conflict on it. The refresh deletes the
+-- requests it covered.
+CREATE TABLE search_refresh_requests (
+  id BIGSERIAL PRIMARY KEY,
+  requested_at TIMESTAMP NOT NULL DEFAULT clock_timestamp()
+);
+
+INSERT INTO search_refresh_requests(requested_at)
+SELECT requested_at FROM search_refreshes
+WHERE refreshed_through IS NULL OR requested_at > refreshed_through;
+
+CREATE OR REPLACE FUNCTION request_search_refresh()
+  RETURNS TRIGGER LANGUAGE plpgsql
+  AS $$
+  BEGIN
+  INSERT INTO search_refresh_requests DEFAULT VALUES;
+  RETURN NULL;
+  END $$;
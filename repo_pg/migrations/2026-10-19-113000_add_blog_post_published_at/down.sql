@@ -0,0 +1,4 @@
+DROP TRIGGER IF EXISTS set_blog_posts_published_at ON blog_posts;
+DROP FUNCTION IF EXISTS set_published_at();
+
+ALTER TABLE blog_posts DROP COLUMN published_at;
@@ -0,0 +1,24 @@
+-- When a post was last published, so digests and newsletters pick up drafts that
+-- went out after they were created.
+
+ALTER TABLE blog_posts ADD COLUMN published_at TIMESTAMP;
+
+UPDATE blog_posts SET published_at = created_at WHERE published;
+
+CREATE OR REPLACE FUNCTION set_published_at() RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.published AND (TG_OP = 'INSERT' OR NOT OLD.published) THEN
+        NEW.published_at := NOW();
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER set_blog_posts_published_at
+  BEFORE INSERT OR UPDATE OF published
+  ON blog_posts
+  FOR EACH ROW
+  EXECUTE PROCEDURE set_published_at();
+
+CREATE INDEX idx_blog_posts_published_at
+ON blog_posts (published_at);
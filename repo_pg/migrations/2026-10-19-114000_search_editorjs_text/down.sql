@@ -0,0 +1,199 @@
+DROP MATERIALIZED VIEW related_items;
+DROP MATERIALIZED VIEW search_title_words;
+DROP MATERIALIZED VIEW search_items;
+
+CREATE MATERIALIZED VIEW search_items AS
+  SELECT 
+    projects.id::VARCHAR || 'project'::VARCHAR as id,
+    setweight(to_tsvector(search_config(projects.language), projects.name), 'A') ||
+    setweight(to_tsvector(search_config(projects.language), COALESCE(projects.description, '')), 'B') ||
+    setweight(to_tsvector(search_config(projects.language), COALESCE(array_to_string(array_agg("technologies"."name"), ' '), '')), 'C') ||
+    setweight(to_tsvector(search_config(projects.language), body), 'D') as search_vec,
+    projects.name as title,
+    projects.created_at as created_at,
+    projects.updated_at as updated_at,
+    projects.cover_image as image,
+    COALESCE(projects.description, '') as description,
+    'Project'::search_item_type as item_type,
+    '/projects/' || projects.slug::VARCHAR as link,
+    regexp_replace(body, '<[^>]*>', '', 'g') as body,
+    projects.language as language,
+    ARRAY[]::VARCHAR[] as categories,
+    array_remove(array_agg(DISTINCT "technologies"."name"), NULL)::VARCHAR[] as technologies
+    FROM projects
+    LEFT JOIN projects_technologies ON projects_technologies.project_id = projects.id
+    LEFT JOIN technologies ON technologies.id = projects_technologies.technology_id  
+    WHERE published =  TRUE
+    GROUP BY projects.id
+  UNION
+  SELECT 
+    blog_posts.id::VARCHAR || 'blog'::VARCHAR as id,
+    setweight(to_tsvector(search_config(blog_posts.language), blog_posts.title), 'A') ||
+    setweight(to_tsvector(search_config(blog_posts.language), COALESCE(blog_posts.description, '')), 'B') ||
+    setweight(to_tsvector(search_config(blog_posts.language), COALESCE(array_to_string(array_agg("categories"."name"), ' '), '')), 'C') ||
+    setweight(to_tsvector(search_config(blog_posts.language), body), 'D') as search_vec,
+    blog_posts.title as title,
+    blog_posts.created_at as created_at,
+    blog_posts.updated_at as updated_at,
+    NULL as image,
+    COALESCE(blog_posts.description, '') as description,
+    'Blog Post'::search_item_type as item_type,
+    '/blog/' || blog_posts.slug::VARCHAR as link,
+    regexp_replace(body, '<[^>]*>', '', 'g') as body,
+    blog_posts.language as language,
+    array_remove(array_agg(DISTINCT "categories"."name"), NULL)::VARCHAR[] as categories,
+    ARRAY[]::VARCHAR[] as technologies
+    FROM blog_posts
+    LEFT JOIN blog_posts_categories ON blog_posts_categories.blog_post_id = blog_posts.id
+    LEFT JOIN categories ON categories.id = blog_posts_categories.category_id 
+    WHERE published =  TRUE
+    GROUP BY blog_posts.id
+  UNION
+    SELECT
+      id,
+      setweight(to_tsvector('english', title), 'A') ||
+      setweight(to_tsvector('english', description), 'B') as search_vec,
+      title,
+      NULL as created_at,
+      NULL as updated_at,
+      NULL as image,
+      description,
+      item_type,
+      link,
+      description as body,
+      'English'::content_language as language,
+      ARRAY[]::VARCHAR[] as categories,
+      ARRAY[]::VARCHAR[] as technologies
+      FROM static_pages
+  UNION
+  SELECT 
+    home_page_links.id::VARCHAR || 'hpl'::VARCHAR as id,
+    setweight(to_tsvector('english', home_page_links.name), 'A') ||
+    setweight(to_tsvector('english', home_page_links.target || ' ' || home_page_links.image), 'D') as search_vec,
+    home_page_links.name as title,
+    NULL as created_at,
+    NULL as updated_at,
+    home_page_links.image as image,
+    home_page_links.name as description,
+    'External Link'::search_item_type as item_type,
+    home_page_links.target as link,
+    home_page_links.name as body,
+    'English'::content_language as language,
+    ARRAY[]::VARCHAR[] as categories,
+    ARRAY[]::VARCHAR[] as technologies
+    FROM home_page_links
+  UNION
+  SELECT
+    series.id::VARCHAR || 'series'::VARCHAR as id,
+    setweight(to_tsvector(search_config(series.language), series.title), 'A') ||
+    setweight(to_tsvector(search_config(series.language), COALESCE(series.description, '')), 'B') ||
+    setweight(to_tsvector(search_config(series.language), COALESCE(string_agg(blog_posts.title, ' '), '')), 'C') as search_vec,
+    series.title as title,
+    series.created_at as created_at,
+    series.updated_at as updated_at,
+    NULL as image,
+    COALESCE(series.description, '') as description,
+    'Series'::search_item_type as item_type,
+    '/series/' || series.slug::VARCHAR as link,
+    COALESCE(series.description, '') || ' ' ||
+      COALESCE(string_agg(blog_posts.title, ' ' ORDER BY series_blog_posts.position), '') as body,
+    series.language as language,
+    ARRAY[]::VARCHAR[] as categories,
+    ARRAY[]::VARCHAR[] as technologies
+    FROM series
+    LEFT JOIN series_blog_posts ON series_blog_posts.series_id = series.id
+    LEFT JOIN blog_posts ON blog_posts.id = series_blog_posts.blog_post_id AND blog_posts.published = TRUE
+    WHERE series.published = TRUE
+    GROUP BY series.id
+  ;
+
+CREATE INDEX search_items_search_vec_idx ON search_items USING GIN (search_vec);
+CREATE INDEX search_items_title_trgm_idx ON search_items USING GIN (title gin_trgm_ops);
+CREATE UNIQUE INDEX search_items_id_idx ON search_items(id);
+
+-- Every word used in a title, to correct misspelled queries against.
+CREATE MATERIALIZED VIEW search_title_words AS
+  SELECT DISTINCT
+    lower(word)::VARCHAR as word,
+    item_type
+    FROM search_items, regexp_split_to_table(title, '[^[:alnum:]]+') as word
+    WHERE length(word) > 2
+  ;
+
+CREATE INDEX search_title_words_word_trgm_idx ON search_title_words USING GIN (word gin_trgm_ops);
+
+CREATE UNIQUE INDEX search_title_words_word_item_type_idx ON search_title_words(word, item_type);
+
+CREATE MATERIALIZED VIEW related_items AS
+  WITH items AS (
+    SELECT
+      substring(id FROM '^[0-9]+')::INTEGER as item_id,
+      item_type,
+      title,
+      created_at,
+      image,
+      description,
+      link,
+      language,
+      categories || technologies as tags,
+      tsvector_to_array(search_vec) as lexemes
+      FROM search_items
+      WHERE item_type IN ('Project', 'Blog Post')
+  ),
+  pairs AS (
+    SELECT
+      items.item_id,
+      items.item_type,
+      related.item_id as related_id,
+      related.title,
+      related.created_at,
+      related.image,
+      related.description,
+      related.link,
+      related.language,
+      shared.tags +
+        5 * shared.lexemes::FLOAT8 /
+        GREATEST(cardinality(items.lexemes) + cardinality(related.lexemes) - shared.lexemes, 1)
+        as score
+      FROM items
+      JOIN items as related
+        ON related.item_type = items.item_type AND related.item_id <> items.item_id
+      CROSS JOIN LATERAL (
+        SELECT
+          (SELECT count(*) FROM (
+            SELECT unnest(items.tags) INTERSECT SELECT unnest(related.tags)
+          ) as shared_tags) as tags,
+          (SELECT count(*) FROM (
+            SELECT unnest(items.lexemes) INTERSECT SELECT unnest(related.lexemes)
+          ) as shared_lexemes) as lexemes
+      ) as shared
+  ),
+  ranked AS (
+    SELECT
+      pairs.*,
+      row_number() OVER (
+        PARTITION BY item_type, item_id ORDER BY score DESC, created_at DESC, related_id
+      )::INTEGER as rank
+      FROM pairs
+      WHERE score >= 0.25
+  )
+  SELECT
+    item_id,
+    item_type,
+    rank,
+    related_id,
+    title,
+    created_at,
+    image,
+    description,
+    link,
+    language,
+    score
+    FROM ranked
+    WHERE rank <= 10
+  ;
+
+-- Needed to refresh the view concurrently
+CREATE UNIQUE INDEX related_items_item_type_item_id_rank_idx ON related_items(item_type, item_id, rank);
+
+DROP FUNCTION editorjs_text(TEXT);
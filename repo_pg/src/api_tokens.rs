@@ -0,0 +1,151 @@
+use crate::filters::GetAllApiTokensFilter;
+use crate::models::{db_models, domain};
+use crate::options::{ApiTokenSortType, PaginationOptions};
+use crate::schema::api_tokens;
+use crate::{change_sets::UpdateApiToken, insertables::NewApiToken};
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::{QueryDsl, RunQueryDsl};
+
+pub struct ApiTokenRepo<'a> {
+    conn: &'a crate::pg_util::RepoConnection,
+}
+
+impl<'a> ApiTokenRepo<'a> {
+    pub fn new(conn: &'a crate::pg_util::RepoConnection) -> Self {
+        Self { conn }
+    }
+
+    pub fn insert_one(
+        &self,
+        new_token: NewApiToken,
+    ) -> Result<domain::ApiToken, diesel::result::Error> {
+        let conn = &self.conn.pg_conn;
+        let query = diesel::insert_into(api_tokens::table).values(&new_token);
+        let result = query.get_result(conn)?;
+        Ok(domain::ApiToken::from(result))
+    }
+
+    pub fn update_one(
+        &self,
+        id_value: i32,
+        updated_token: UpdateApiToken,
+    ) -> Result<domain::ApiToken, diesel::result::Error> {
+        use crate::schema::api_tokens::dsl::{api_tokens, id};
+        let conn = &self.conn.pg_conn;
+        let query = diesel::update(api_tokens.filter(id.eq(id_value))).set(&updated_token);
+        let result = query.get_result(conn)?;
+        Ok(domain::ApiToken::from(result))
+    }
+
+    pub fn revoke(&self, id_value: i32) -> Result<domain::ApiToken, diesel::result::Error> {
+        self.update_one(
+            id_value,
+            UpdateApiToken {
+                revoked: Some(true),
+                last_used_at: None,
+            },
+        )
+    }
+
+    pub fn touch(&self, id_value: i32) -> Result<domain::ApiToken, diesel::result::Error> {
+        self.update_one(
+            id_value,
+            UpdateApiToken {
+                revoked: None,
+                last_used_at: Some(Some(Utc::now().naive_utc())),
+            },
+        )
+    }
+
+    pub fn find_one(
+        &self,
+        id_value: i32,
+    ) -> Result<Option<domain::ApiToken>, diesel::result::Error> {
+        use crate::schema::api_tokens::dsl::{api_tokens, id};
+
+        let conn = &self.conn.pg_conn;
+        let query = api_tokens
+            .filter(id.eq(id_value))
+            .select(api_tokens::all_columns());
+        let token: db_models::ApiToken = match query.first(conn).optional()? {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+        Ok(Some(domain::ApiToken::from(token)))
+    }
+
+    pub fn find_one_by_hash(
+        &self,
+        hash_value: &str,
+    ) -> Result<Option<domain::ApiToken>, diesel::result::Error> {
+        use crate::schema::api_tokens::dsl::{api_tokens, token_hash};
+
+        let conn = &self.conn.pg_conn;
+        let query = api_tokens
+            .filter(token_hash.eq(hash_value))
+            .select(api_tokens::all_columns());
+        let token: db_models::ApiToken = match query.first(conn).optional()? {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+        Ok(Some(domain::ApiToken::from(token)))
+    }
+
+    pub fn find(
+        &self,
+        filter: GetAllApiTokensFilter,
+        sort: Option<ApiTokenSortType>,
+        pagination: PaginationOptions,
+    ) -> Result<(Vec<domain::ApiToken>, i64), diesel::result::Error> {
+        use crate::schema::api_tokens::dsl::{api_tokens as api_tokens_dsl, revoked, user_id};
+        let q = api_tokens_dsl
+            .select((
+                api_tokens_dsl::all_columns(),
+                diesel::dsl::sql::<diesel::sql_types::BigInt>("count(*) over()"),
+            ))
+            .into_boxed();
+
+        let q = if let Some(user_id_filter) = filter.user_id {
+            q.filter(user_id.eq(user_id_filter))
+        } else {
+            q
+        };
+
+        let q = if let Some(revoked_filter) = filter.revoked {
+            q.filter(revoked.eq(revoked_filter))
+        } else {
+            q
+        };
+
+        let q = if let Some(sort_type) = sort {
+            match sort_type {
+                ApiTokenSortType::CreatedAtAsc => q.order(api_tokens::created_at.asc()),
+                ApiTokenSortType::CreatedAtDesc => q.order(api_tokens::created_at.desc()),
+                ApiTokenSortType::LastUsedAtAsc => q.order(api_tokens::last_used_at.asc()),
+                ApiTokenSortType::LastUsedAtDesc => q.order(api_tokens::last_used_at.desc()),
+            }
+        } else {
+            q.order(api_tokens::created_at.desc())
+        };
+
+        let q = if let (Some(page), Some(page_size)) = (pagination.page, pagination.page_size) {
+            q.offset((page - 1) * page_size).limit(page_size)
+        } else {
+            q
+        };
+
+        let conn = &self.conn.pg_conn;
+        let results: Vec<(db_models::ApiToken, i64)> = q.load(conn)?;
+
+        let count = match results.first() {
+            Some((_, value)) => *value,
+            None => 0,
+        };
+        let api_tokens_list = results
+            .into_iter()
+            .map(|(token, _)| domain::ApiToken::from(token))
+            .collect::<Vec<_>>();
+        Ok((api_tokens_list, count))
+    }
+}
@@ -73,11 +73,17 @@ impl<'a> BlogPostCommentRatingRepo<'a> {
         sort: Option<BlogPostCommentRatingSortType>,
         pagination: PaginationOptions,
     ) -> Result<Vec<domain::BlogPostCommentRating>, diesel::result::Error> {
-        use crate::schema::blog_post_comment_ratings::dsl::blog_post_comment_ratings;
+        use crate::schema::blog_post_comment_ratings::dsl::{blog_post_comment_ratings, user_id};
         let q = blog_post_comment_ratings
             .select(blog_post_comment_ratings::all_columns())
             .into_boxed();
 
+        let q = if let Some(user_id_filter) = filter.user_id {
+            q.filter(user_id.eq(user_id_filter))
+        } else {
+            q
+        };
+
         let q = if let (Some(page), Some(page_size)) = (pagination.page, pagination.page_size) {
             q.offset((page - 1) * page_size).limit(page_size)
         } else {
@@ -1,6 +1,7 @@
 use crate::filters::GetAllBlogPostsFilter;
 use crate::models::{db_models, domain};
 use crate::options::PaginationOptions;
+use crate::page_views::view_count_sql;
 use crate::schema::{blog_posts, blog_posts_categories, categories};
 use crate::{
     change_sets::UpdateBlogPost,
@@ -11,6 +12,18 @@ use crate::{errors::PgRepoError, options::BlogPostSortType};
 use diesel::prelude::*;
 use diesel::{QueryDsl, RunQueryDsl};
 
+/// Where the site shows a post, to match its page views.
+pub(crate) const BLOG_POST_PATH: &str = "'/blog/' || blog_posts.slug";
+
+/// A post, its author, categories and view count, then the total count.
+type BlogPostRow = (
+    db_models::BlogPost,
+    db_models::User,
+    Vec<Option<String>>,
+    i64,
+    i64,
+);
+
 pub struct BlogPostRepo<'a> {
     conn: &'a crate::pg_util::RepoConnection,
 }
@@ -26,6 +39,9 @@ impl<'a> BlogPostRepo<'a> {
         Ok(query.execute(conn)?)
     }
 
+    /// Links the post to the categories with these names, adding the missing ones with a
+    /// slug made from the name. Names are matched against the current names, so a
+    /// renamed category keeps its posts and its slug.
     fn update_categories(
         &self,
         inserted_post_id: i32,
@@ -42,7 +58,14 @@ impl<'a> BlogPostRepo<'a> {
         let new_categories: Vec<NewCategory> = categories_list
             .clone()
             .into_iter()
-            .map(|name| NewCategory { name })
+            .map(|name| NewCategory {
+                name,
+                slug: None,
+                description: None,
+                icon: None,
+                color: None,
+                parent_id: None,
+            })
             .collect();
         let conn = &self.conn.pg_conn;
         let query = diesel::insert_into(categories::table)
@@ -165,11 +188,13 @@ impl<'a> BlogPostRepo<'a> {
                         diesel::sql_types::Nullable<diesel::sql_types::VarChar>,
                     >,
                 >("array_agg(\"categories\".\"name\")"),
+                diesel::dsl::sql::<diesel::sql_types::BigInt>(&view_count_sql(BLOG_POST_PATH)),
             ));
-        let (blog_post, user, categories_list): (
+        let (blog_post, user, categories_list, view_count): (
             db_models::BlogPost,
             db_models::User,
             Vec<Option<String>>,
+            i64,
         ) = match query.first(conn).optional()? {
             Some(value) => value,
             None => return Ok(None),
@@ -178,6 +203,7 @@ impl<'a> BlogPostRepo<'a> {
             blog_post,
             user,
             categories_list.into_iter().filter_map(|v| v).collect(),
+            view_count,
         )))
     }
 
@@ -214,11 +240,13 @@ impl<'a> BlogPostRepo<'a> {
                         diesel::sql_types::Nullable<diesel::sql_types::VarChar>,
                     >,
                 >("array_agg(\"categories\".\"name\")"),
+                diesel::dsl::sql::<diesel::sql_types::BigInt>(&view_count_sql(BLOG_POST_PATH)),
             ));
-        let (blog_post, user, categories_list): (
+        let (blog_post, user, categories_list, view_count): (
             db_models::BlogPost,
             db_models::User,
             Vec<Option<String>>,
+            i64,
         ) = match query.first(conn).optional()? {
             Some(value) => value,
             None => return Ok(None),
@@ -227,6 +255,7 @@ impl<'a> BlogPostRepo<'a> {
             blog_post,
             user,
             categories_list.into_iter().filter_map(|v| v).collect(),
+            view_count,
         )))
     }
 
@@ -261,6 +290,7 @@ impl<'a> BlogPostRepo<'a> {
                         diesel::sql_types::Nullable<diesel::sql_types::VarChar>,
                     >,
                 >("array_agg(\"categories\".\"name\")"),
+                diesel::dsl::sql::<diesel::sql_types::BigInt>(&view_count_sql(BLOG_POST_PATH)),
                 diesel::dsl::sql::<diesel::sql_types::BigInt>("Count(*) Over()"),
             ))
             .into_boxed();
@@ -298,18 +328,42 @@ impl<'a> BlogPostRepo<'a> {
             q
         };
 
+        let q = if let Some(category_ids_filter) = filter.category_ids {
+            q.filter(
+                blog_post_id.eq_any(
+                    blog_posts_categories_dsl
+                        .filter(blog_posts_categories_category_id.eq_any(category_ids_filter))
+                        .into_boxed()
+                        .select(blog_posts_categories_blog_post_id),
+                ),
+            )
+        } else {
+            q
+        };
+
         let q = if let Some(published) = filter.published {
             q.filter(blog_post_published.eq(published))
         } else {
             q
         };
 
+        let q = if let Some(published_after) = filter.published_after {
+            q.filter(blog_posts::published_at.gt(published_after))
+        } else {
+            q
+        };
+
         let q = if let Some(sort_type) = sort {
             match sort_type {
                 BlogPostSortType::CreatedAtAsc => q.order(blog_posts::created_at.asc()),
                 BlogPostSortType::CreatedAtDesc => q.order(blog_posts::created_at.desc()),
                 BlogPostSortType::TitleAsc => q.order(blog_posts::title.asc()),
                 BlogPostSortType::TitleDesc => q.order(blog_posts::title.desc()),
+                BlogPostSortType::Popular => q.order((
+                    diesel::dsl::sql::<diesel::sql_types::BigInt>(&view_count_sql(BLOG_POST_PATH))
+                        .desc(),
+                    blog_posts::created_at.desc(),
+                )),
             }
         } else {
             q
@@ -322,25 +376,21 @@ impl<'a> BlogPostRepo<'a> {
         };
 
         let conn = &self.conn.pg_conn;
-        let post_results: Vec<(
-            db_models::BlogPost,
-            db_models::User,
-            Vec<Option<String>>,
-            i64,
-        )> = q.load(conn)?;
+        let post_results: Vec<BlogPostRow> = q.load(conn)?;
 
         let count = match post_results.get(0) {
-            Some((_, _, _, value)) => *value,
+            Some((_, _, _, _, value)) => *value,
             None => 0,
         };
 
         let blog_posts_list = post_results
             .into_iter()
-            .map(|(post, user, categories_list, _)| {
+            .map(|(post, user, categories_list, view_count, _)| {
                 domain::BlogPost::from(
                     post,
                     user,
                     categories_list.into_iter().filter_map(|v| v).collect(),
+                    view_count,
                 )
             })
             .collect::<Vec<_>>();
@@ -4,7 +4,9 @@ use crate::models::{db_models, domain};
 use crate::options::{CategorySortType, PaginationOptions};
 use crate::schema::categories;
 use crate::{change_sets::UpdateCategory, insertables::NewCategory};
+use diesel::dsl::sql;
 use diesel::prelude::*;
+use diesel::sql_types::Bool;
 use diesel::{QueryDsl, RunQueryDsl};
 
 pub struct CategoryRepo<'a> {
@@ -62,6 +64,50 @@ impl<'a> CategoryRepo<'a> {
         Ok(Some(domain::Category::from(category)))
     }
 
+    pub fn find_one_by_slug(
+        &self,
+        slug_value: String,
+    ) -> Result<Option<domain::Category>, diesel::result::Error> {
+        use crate::schema::categories::dsl::{categories, slug};
+
+        let conn = &self.conn.pg_conn;
+        let query = categories
+            .filter(slug.eq(slug_value))
+            .select(categories::all_columns());
+        let category: db_models::Category = match query.first(conn).optional()? {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+
+        Ok(Some(domain::Category::from(category)))
+    }
+
+    /// The categories directly under this one, by name.
+    pub fn find_children(
+        &self,
+        id_value: i32,
+    ) -> Result<Vec<domain::Category>, diesel::result::Error> {
+        use crate::schema::categories::dsl::{categories, name, parent_id};
+
+        let conn = &self.conn.pg_conn;
+        let children: Vec<db_models::Category> = categories
+            .filter(parent_id.eq(id_value))
+            .order(name.asc())
+            .load(conn)?;
+        Ok(children.into_iter().map(domain::Category::from).collect())
+    }
+
+    /// The id of this category and of every category nested under it.
+    pub fn find_subtree_ids(&self, id_value: i32) -> Result<Vec<i32>, diesel::result::Error> {
+        use crate::schema::categories::dsl::{categories, id};
+
+        let conn = &self.conn.pg_conn;
+        categories
+            .select(id)
+            .filter(sql::<Bool>(&subtree_sql("categories", id_value)))
+            .load(conn)
+    }
+
     pub fn find(
         &self,
         filter: GetAllCategoriesFilter,
@@ -96,3 +142,15 @@ impl<'a> CategoryRepo<'a> {
         Ok((categories_list, count))
     }
 }
+
+/// Matches the rows of a table nested through `parent_id` under the given row,
+/// that row included.
+pub(crate) fn subtree_sql(table: &str, root_id: i32) -> String {
+    format!(
+        "{table}.id IN (WITH RECURSIVE subtree(id) AS (SELECT {root_id} UNION \
+         SELECT nested.id FROM {table} nested INNER JOIN subtree \
+         ON nested.parent_id = subtree.id) SELECT id FROM subtree)",
+        table = table,
+        root_id = root_id,
+    )
+}
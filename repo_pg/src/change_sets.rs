@@ -1,4 +1,4 @@
-use crate::extra::UserRole;
+use crate::extra::{ContentLanguage, EmailStatus, UserRole};
 use crate::schema::*;
 use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
@@ -19,6 +19,7 @@ pub struct UpdateBlogPost {
     pub updated_at: Option<Option<NaiveDateTime>>,
     pub description: Option<Option<String>>,
     pub slug: Option<String>,
+    pub language: Option<ContentLanguage>,
 }
 
 #[derive(AsChangeset, Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
@@ -31,6 +32,18 @@ pub struct UpdateProject {
     pub name: Option<String>,
     pub published: Option<bool>,
     pub slug: Option<String>,
+    pub language: Option<ContentLanguage>,
+}
+
+#[derive(AsChangeset, Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[table_name = "series"]
+pub struct UpdateSeries {
+    pub title: Option<String>,
+    pub slug: Option<String>,
+    pub description: Option<Option<String>>,
+    pub published: Option<bool>,
+    pub language: Option<ContentLanguage>,
+    pub updated_at: Option<Option<NaiveDateTime>>,
 }
 
 #[derive(AsChangeset, Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
@@ -62,6 +75,11 @@ pub struct UpdateHomePageLink {
 #[table_name = "categories"]
 pub struct UpdateCategory {
     pub name: Option<String>,
+    pub slug: Option<String>,
+    pub description: Option<Option<String>>,
+    pub icon: Option<Option<String>>,
+    pub color: Option<Option<String>>,
+    pub parent_id: Option<Option<i32>>,
 }
 
 #[derive(AsChangeset, Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
@@ -75,6 +93,11 @@ pub struct UpdateBlogPostCategory {
 #[table_name = "technologies"]
 pub struct UpdateTechnology {
     pub name: Option<String>,
+    pub slug: Option<String>,
+    pub description: Option<Option<String>>,
+    pub icon: Option<Option<String>>,
+    pub color: Option<Option<String>>,
+    pub parent_id: Option<Option<i32>>,
 }
 
 #[derive(AsChangeset, Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
@@ -85,6 +108,7 @@ pub struct UpdateTextBody {
     pub body: Option<String>,
     pub url_used: Option<Option<String>>,
     pub updated_at: Option<NaiveDateTime>,
+    pub language: Option<ContentLanguage>,
 }
 
 #[derive(AsChangeset, Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
@@ -107,3 +131,58 @@ pub struct UpdateChangePasswordToken {
     pub invalidated: Option<bool>,
     pub used: Option<bool>,
 }
+
+#[derive(AsChangeset, Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[table_name = "api_tokens"]
+pub struct UpdateApiToken {
+    pub revoked: Option<bool>,
+    pub last_used_at: Option<Option<NaiveDateTime>>,
+}
+
+#[derive(AsChangeset, Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[table_name = "email_outbox"]
+pub struct UpdateOutboxEmail {
+    pub status: Option<EmailStatus>,
+    pub attempts: Option<i32>,
+    pub last_error: Option<Option<String>>,
+    pub next_attempt_at: Option<NaiveDateTime>,
+    pub sent_at: Option<Option<NaiveDateTime>>,
+}
+
+#[derive(AsChangeset, Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[table_name = "notification_preferences"]
+pub struct UpdateNotificationPreferences {
+    pub comment_replies: Option<bool>,
+    pub moderation_decisions: Option<bool>,
+    pub new_posts_digest: Option<bool>,
+    pub last_digest_sent_at: Option<Option<NaiveDateTime>>,
+    pub updated_at: Option<Option<NaiveDateTime>>,
+}
+
+#[derive(AsChangeset, Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[table_name = "newsletter_subscribers"]
+pub struct UpdateNewsletterSubscriber {
+    pub token: Option<String>,
+    pub confirmed: Option<bool>,
+    pub confirmed_at: Option<Option<NaiveDateTime>>,
+    pub unsubscribed_at: Option<Option<NaiveDateTime>>,
+}
+
+#[derive(AsChangeset, Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[table_name = "newsletter_issues"]
+pub struct UpdateNewsletterIssue {
+    pub subject: Option<String>,
+    pub intro: Option<String>,
+    pub blog_post_ids: Option<Vec<i32>>,
+    pub updated_at: Option<Option<NaiveDateTime>>,
+    pub sent_at: Option<Option<NaiveDateTime>>,
+}
+
+#[derive(AsChangeset, Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[table_name = "contact_messages"]
+pub struct UpdateContactMessage {
+    pub spam: Option<bool>,
+    pub read_at: Option<Option<NaiveDateTime>>,
+    pub archived_at: Option<Option<NaiveDateTime>>,
+    pub replied_at: Option<Option<NaiveDateTime>>,
+}
@@ -0,0 +1,53 @@
+use crate::insertables::NewContactMessageReply;
+use crate::models::{db_models, domain};
+use crate::schema::{contact_message_replies, email_outbox, users};
+use diesel::prelude::*;
+use diesel::{QueryDsl, RunQueryDsl};
+
+pub struct ContactMessageReplyRepo<'a> {
+    conn: &'a crate::pg_util::RepoConnection,
+}
+
+impl<'a> ContactMessageReplyRepo<'a> {
+    pub fn new(conn: &'a crate::pg_util::RepoConnection) -> Self {
+        Self { conn }
+    }
+
+    pub fn insert_one(
+        &self,
+        new_reply: NewContactMessageReply,
+    ) -> Result<i32, diesel::result::Error> {
+        let conn = &self.conn.pg_conn;
+        let query = diesel::insert_into(contact_message_replies::table)
+            .values(&new_reply)
+            .returning(contact_message_replies::id);
+        query.get_result(conn)
+    }
+
+    /// Every reply to a message, oldest first, with the state of its email.
+    pub fn find_by_message(
+        &self,
+        contact_message_id_value: i32,
+    ) -> Result<Vec<domain::ContactMessageReply>, diesel::result::Error> {
+        let conn = &self.conn.pg_conn;
+        let results: Vec<(
+            db_models::ContactMessageReply,
+            db_models::User,
+            Option<db_models::OutboxEmail>,
+        )> = contact_message_replies::table
+            .inner_join(users::table)
+            .left_join(email_outbox::table)
+            .filter(contact_message_replies::contact_message_id.eq(contact_message_id_value))
+            .select((
+                contact_message_replies::all_columns,
+                users::all_columns,
+                email_outbox::all_columns.nullable(),
+            ))
+            .order(contact_message_replies::created_at.asc())
+            .load(conn)?;
+        Ok(results
+            .into_iter()
+            .map(|(reply, user, email)| domain::ContactMessageReply::from(reply, user, email))
+            .collect())
+    }
+}
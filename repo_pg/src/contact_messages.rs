@@ -0,0 +1,141 @@
+use crate::filters::GetAllContactMessagesFilter;
+use crate::models::{db_models, domain};
+use crate::options::{ContactMessageSortType, PaginationOptions};
+use crate::schema::contact_messages;
+use crate::{change_sets::UpdateContactMessage, insertables::NewContactMessage};
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel::{QueryDsl, RunQueryDsl};
+
+pub struct ContactMessageRepo<'a> {
+    conn: &'a crate::pg_util::RepoConnection,
+}
+
+impl<'a> ContactMessageRepo<'a> {
+    pub fn new(conn: &'a crate::pg_util::RepoConnection) -> Self {
+        Self { conn }
+    }
+
+    pub fn insert_one(
+        &self,
+        new_message: NewContactMessage,
+    ) -> Result<domain::ContactMessage, diesel::result::Error> {
+        let conn = &self.conn.pg_conn;
+        let query = diesel::insert_into(contact_messages::table).values(&new_message);
+        let result = query.get_result(conn)?;
+        Ok(domain::ContactMessage::from(result))
+    }
+
+    pub fn update_one(
+        &self,
+        id_value: i32,
+        updated_message: UpdateContactMessage,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::contact_messages::dsl::{contact_messages, id};
+        let conn = &self.conn.pg_conn;
+        let query = diesel::update(contact_messages.filter(id.eq(id_value))).set(&updated_message);
+        query.execute(conn)
+    }
+
+    pub fn find_one(
+        &self,
+        id_value: i32,
+    ) -> Result<Option<domain::ContactMessage>, diesel::result::Error> {
+        use crate::schema::contact_messages::dsl::{contact_messages, id};
+
+        let conn = &self.conn.pg_conn;
+        let query = contact_messages
+            .filter(id.eq(id_value))
+            .select(contact_messages::all_columns());
+        let message: db_models::ContactMessage = match query.first(conn).optional()? {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+        Ok(Some(domain::ContactMessage::from(message)))
+    }
+
+    /// How many messages were sent from an address since the given time.
+    pub fn count_recent_by_ip(
+        &self,
+        ip_address_value: &str,
+        since: NaiveDateTime,
+    ) -> Result<i64, diesel::result::Error> {
+        use crate::schema::contact_messages::dsl::{contact_messages, created_at, ip_address};
+
+        let conn = &self.conn.pg_conn;
+        contact_messages
+            .filter(ip_address.eq(ip_address_value))
+            .filter(created_at.ge(since))
+            .count()
+            .get_result(conn)
+    }
+
+    pub fn find(
+        &self,
+        filter: GetAllContactMessagesFilter,
+        sort: Option<ContactMessageSortType>,
+        pagination: PaginationOptions,
+    ) -> Result<(Vec<domain::ContactMessage>, i64), diesel::result::Error> {
+        use crate::schema::contact_messages::dsl::{
+            archived_at, contact_messages as contact_messages_dsl, read_at, spam,
+        };
+        let q = contact_messages_dsl
+            .select((
+                contact_messages_dsl::all_columns(),
+                diesel::dsl::sql::<diesel::sql_types::BigInt>("count(*) over()"),
+            ))
+            .into_boxed();
+
+        let q = match filter.read {
+            Some(true) => q.filter(read_at.is_not_null()),
+            Some(false) => q.filter(read_at.is_null()),
+            None => q,
+        };
+
+        let q = match filter.archived {
+            Some(true) => q.filter(archived_at.is_not_null()),
+            Some(false) => q.filter(archived_at.is_null()),
+            None => q,
+        };
+
+        let q = if let Some(spam_filter) = filter.spam {
+            q.filter(spam.eq(spam_filter))
+        } else {
+            q
+        };
+
+        let q = if let Some(sort_type) = sort {
+            match sort_type {
+                ContactMessageSortType::CreatedAtAsc => q.order(contact_messages::created_at.asc()),
+                ContactMessageSortType::CreatedAtDesc => {
+                    q.order(contact_messages::created_at.desc())
+                }
+                ContactMessageSortType::SpamScoreAsc => q.order(contact_messages::spam_score.asc()),
+                ContactMessageSortType::SpamScoreDesc => {
+                    q.order(contact_messages::spam_score.desc())
+                }
+            }
+        } else {
+            q.order(contact_messages::created_at.desc())
+        };
+
+        let q = if let (Some(page), Some(page_size)) = (pagination.page, pagination.page_size) {
+            q.offset((page - 1) * page_size).limit(page_size)
+        } else {
+            q
+        };
+
+        let conn = &self.conn.pg_conn;
+        let results: Vec<(db_models::ContactMessage, i64)> = q.load(conn)?;
+
+        let count = match results.first() {
+            Some((_, value)) => *value,
+            None => 0,
+        };
+        let messages_list = results
+            .into_iter()
+            .map(|(message, _)| domain::ContactMessage::from(message))
+            .collect::<Vec<_>>();
+        Ok((messages_list, count))
+    }
+}
@@ -0,0 +1,44 @@
+use crate::insertables::NewDailySalt;
+use crate::schema::daily_salts;
+use chrono::NaiveDate;
+use diesel::prelude::*;
+use diesel::{QueryDsl, RunQueryDsl};
+
+pub struct DailySaltRepo<'a> {
+    conn: &'a crate::pg_util::RepoConnection,
+}
+
+impl<'a> DailySaltRepo<'a> {
+    pub fn new(conn: &'a crate::pg_util::RepoConnection) -> Self {
+        Self { conn }
+    }
+
+    /// The salt of a day, stored as `new_salt` if the day has none yet.
+    pub fn find_or_insert(
+        &self,
+        day_value: NaiveDate,
+        new_salt: String,
+    ) -> Result<String, diesel::result::Error> {
+        use crate::schema::daily_salts::dsl::{day, salt};
+
+        let conn = &self.conn.pg_conn;
+        diesel::insert_into(daily_salts::table)
+            .values(&NewDailySalt {
+                day: day_value,
+                salt: new_salt,
+            })
+            .on_conflict_do_nothing()
+            .execute(conn)?;
+        daily_salts::table
+            .filter(day.eq(day_value))
+            .select(salt)
+            .first(conn)
+    }
+
+    pub fn delete_before(&self, day_value: NaiveDate) -> Result<usize, diesel::result::Error> {
+        use crate::schema::daily_salts::dsl::day;
+
+        let conn = &self.conn.pg_conn;
+        diesel::delete(daily_salts::table.filter(day.lt(day_value))).execute(conn)
+    }
+}
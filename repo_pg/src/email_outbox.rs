@@ -0,0 +1,187 @@
+use crate::extra::EmailStatus;
+use crate::filters::GetAllOutboxEmailsFilter;
+use crate::models::{db_models, domain};
+use crate::options::{OutboxEmailSortType, PaginationOptions};
+use crate::schema::email_outbox;
+use crate::{change_sets::UpdateOutboxEmail, insertables::NewOutboxEmail};
+use chrono::{Duration, Utc};
+use diesel::prelude::*;
+use diesel::{QueryDsl, RunQueryDsl};
+
+pub struct OutboxEmailRepo<'a> {
+    conn: &'a crate::pg_util::RepoConnection,
+}
+
+impl<'a> OutboxEmailRepo<'a> {
+    pub fn new(conn: &'a crate::pg_util::RepoConnection) -> Self {
+        Self { conn }
+    }
+
+    pub fn insert_one(
+        &self,
+        new_email: NewOutboxEmail,
+    ) -> Result<domain::OutboxEmail, diesel::result::Error> {
+        let conn = &self.conn.pg_conn;
+        let query = diesel::insert_into(email_outbox::table).values(&new_email);
+        let result = query.get_result(conn)?;
+        Ok(domain::OutboxEmail::from(result))
+    }
+
+    pub fn update_one(
+        &self,
+        id_value: i32,
+        updated_email: UpdateOutboxEmail,
+    ) -> Result<domain::OutboxEmail, diesel::result::Error> {
+        use crate::schema::email_outbox::dsl::{email_outbox, id};
+        let conn = &self.conn.pg_conn;
+        let query = diesel::update(email_outbox.filter(id.eq(id_value))).set(&updated_email);
+        let result = query.get_result(conn)?;
+        Ok(domain::OutboxEmail::from(result))
+    }
+
+    pub fn mark_sent(&self, id_value: i32) -> Result<domain::OutboxEmail, diesel::result::Error> {
+        self.update_one(
+            id_value,
+            UpdateOutboxEmail {
+                status: Some(EmailStatus::Sent),
+                attempts: None,
+                last_error: Some(None),
+                next_attempt_at: None,
+                sent_at: Some(Some(Utc::now().naive_utc())),
+            },
+        )
+    }
+
+    /// Puts a message back in the queue to be sent as soon as possible.
+    pub fn retry(&self, id_value: i32) -> Result<domain::OutboxEmail, diesel::result::Error> {
+        self.update_one(
+            id_value,
+            UpdateOutboxEmail {
+                status: Some(EmailStatus::Pending),
+                attempts: Some(0),
+                last_error: None,
+                next_attempt_at: Some(Utc::now().naive_utc()),
+                sent_at: None,
+            },
+        )
+    }
+
+    pub fn find_one(
+        &self,
+        id_value: i32,
+    ) -> Result<Option<domain::OutboxEmail>, diesel::result::Error> {
+        use crate::schema::email_outbox::dsl::{email_outbox, id};
+
+        let conn = &self.conn.pg_conn;
+        let query = email_outbox
+            .filter(id.eq(id_value))
+            .select(email_outbox::all_columns());
+        let email: db_models::OutboxEmail = match query.first(conn).optional()? {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+        Ok(Some(domain::OutboxEmail::from(email)))
+    }
+
+    /// Locks pending messages that are due, skipping the ones another worker holds.
+    /// Has to run inside a transaction for the locks to mean anything.
+    pub fn find_due(&self, limit: i64) -> Result<Vec<domain::OutboxEmail>, diesel::result::Error> {
+        use crate::schema::email_outbox::dsl::{email_outbox, id, next_attempt_at, status};
+
+        let conn = &self.conn.pg_conn;
+        let query = email_outbox
+            .filter(status.eq(EmailStatus::Pending))
+            .filter(next_attempt_at.le(Utc::now().naive_utc()))
+            .order(id.asc())
+            .limit(limit)
+            .for_update()
+            .skip_locked();
+        let results: Vec<db_models::OutboxEmail> = query.load(conn)?;
+        Ok(results
+            .into_iter()
+            .map(domain::OutboxEmail::from)
+            .collect::<Vec<_>>())
+    }
+
+    /// Takes the messages that are due for one attempt, counting it and holding them
+    /// until `lease` passes, so the sender can commit before talking to the mail
+    /// server. Messages whose sender died without recording an outcome become due
+    /// again once the lease is over.
+    pub fn claim_due(
+        &self,
+        limit: i64,
+        lease: Duration,
+    ) -> Result<Vec<domain::OutboxEmail>, diesel::result::Error> {
+        let leased_until = Utc::now().naive_utc() + lease;
+        self.find_due(limit)?
+            .into_iter()
+            .map(|email| {
+                self.update_one(
+                    email.id,
+                    UpdateOutboxEmail {
+                        status: None,
+                        attempts: Some(email.attempts + 1),
+                        last_error: None,
+                        next_attempt_at: Some(leased_until),
+                        sent_at: None,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    pub fn find(
+        &self,
+        filter: GetAllOutboxEmailsFilter,
+        sort: Option<OutboxEmailSortType>,
+        pagination: PaginationOptions,
+    ) -> Result<(Vec<domain::OutboxEmail>, i64), diesel::result::Error> {
+        use crate::schema::email_outbox::dsl::{email_outbox as email_outbox_dsl, status};
+        let q = email_outbox_dsl
+            .select((
+                email_outbox_dsl::all_columns(),
+                diesel::dsl::sql::<diesel::sql_types::BigInt>("count(*) over()"),
+            ))
+            .into_boxed();
+
+        let q = if let Some(status_filter) = filter.status {
+            q.filter(status.eq(status_filter))
+        } else {
+            q
+        };
+
+        let q = if let Some(sort_type) = sort {
+            match sort_type {
+                OutboxEmailSortType::CreatedAtAsc => q.order(email_outbox::created_at.asc()),
+                OutboxEmailSortType::CreatedAtDesc => q.order(email_outbox::created_at.desc()),
+                OutboxEmailSortType::NextAttemptAtAsc => {
+                    q.order(email_outbox::next_attempt_at.asc())
+                }
+                OutboxEmailSortType::NextAttemptAtDesc => {
+                    q.order(email_outbox::next_attempt_at.desc())
+                }
+            }
+        } else {
+            q.order(email_outbox::created_at.desc())
+        };
+
+        let q = if let (Some(page), Some(page_size)) = (pagination.page, pagination.page_size) {
+            q.offset((page - 1) * page_size).limit(page_size)
+        } else {
+            q
+        };
+
+        let conn = &self.conn.pg_conn;
+        let results: Vec<(db_models::OutboxEmail, i64)> = q.load(conn)?;
+
+        let count = match results.first() {
+            Some((_, value)) => *value,
+            None => 0,
+        };
+        let emails_list = results
+            .into_iter()
+            .map(|(email, _)| domain::OutboxEmail::from(email))
+            .collect::<Vec<_>>();
+        Ok((emails_list, count))
+    }
+}
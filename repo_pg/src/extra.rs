@@ -36,12 +36,116 @@ pub enum SearchItemType {
     BlogPost,
     Page,
     ExternalLink,
+    Series,
 }
 
 #[derive(SqlType)]
 #[postgres(type_name = "search_item_type")]
 pub struct SearchItemTypeType;
 
+#[derive(Deserialize, Serialize, Debug, Clone, Eq, PartialEq, FromSqlRow, AsExpression, TS)]
+#[ts(export, export_to = "bindings/misc/EmailStatus.ts")]
+#[sql_type = "EmailStatusType"]
+pub enum EmailStatus {
+    Pending,
+    Sent,
+    Failed,
+}
+
+#[derive(SqlType, QueryId)]
+#[postgres(type_name = "email_status")]
+pub struct EmailStatusType;
+
+/// The language of a piece of content, which decides how its words are stemmed for search.
+#[derive(
+    Deserialize, Serialize, Debug, Clone, Copy, Eq, PartialEq, FromSqlRow, AsExpression, TS,
+)]
+#[ts(export, export_to = "bindings/misc/ContentLanguage.ts")]
+#[sql_type = "ContentLanguageType"]
+pub enum ContentLanguage {
+    English,
+    Greek,
+}
+
+#[derive(SqlType, QueryId)]
+#[postgres(type_name = "content_language")]
+pub struct ContentLanguageType;
+
+/// What kind of site a visitor came from.
+#[derive(
+    Deserialize, Serialize, Debug, Clone, Copy, Eq, PartialEq, FromSqlRow, AsExpression, TS,
+)]
+#[ts(export, export_to = "bindings/misc/ReferrerType.ts")]
+#[sql_type = "ReferrerTypeType"]
+pub enum ReferrerType {
+    Search,
+    Social,
+    Website,
+}
+
+#[derive(SqlType, QueryId)]
+#[postgres(type_name = "referrer_type")]
+pub struct ReferrerTypeType;
+
+// Stored as plain strings in `api_tokens.scopes`, so no sql type is needed.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Eq, PartialEq, TS)]
+#[ts(export, export_to = "bindings/misc/ApiTokenScope.ts")]
+pub enum ApiTokenScope {
+    #[serde(rename = "posts:read")]
+    PostsRead,
+    #[serde(rename = "posts:write")]
+    PostsWrite,
+    #[serde(rename = "projects:read")]
+    ProjectsRead,
+    #[serde(rename = "projects:write")]
+    ProjectsWrite,
+    #[serde(rename = "pages:read")]
+    PagesRead,
+    #[serde(rename = "pages:write")]
+    PagesWrite,
+    #[serde(rename = "comments:read")]
+    CommentsRead,
+    #[serde(rename = "comments:write")]
+    CommentsWrite,
+    #[serde(rename = "media:upload")]
+    MediaUpload,
+}
+
+impl ApiTokenScope {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            ApiTokenScope::PostsRead => "posts:read",
+            ApiTokenScope::PostsWrite => "posts:write",
+            ApiTokenScope::ProjectsRead => "projects:read",
+            ApiTokenScope::ProjectsWrite => "projects:write",
+            ApiTokenScope::PagesRead => "pages:read",
+            ApiTokenScope::PagesWrite => "pages:write",
+            ApiTokenScope::CommentsRead => "comments:read",
+            ApiTokenScope::CommentsWrite => "comments:write",
+            ApiTokenScope::MediaUpload => "media:upload",
+        }
+    }
+}
+
+impl std::str::FromStr for ApiTokenScope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "posts:read" => Ok(ApiTokenScope::PostsRead),
+            "posts:write" => Ok(ApiTokenScope::PostsWrite),
+            "projects:read" => Ok(ApiTokenScope::ProjectsRead),
+            "projects:write" => Ok(ApiTokenScope::ProjectsWrite),
+            "pages:read" => Ok(ApiTokenScope::PagesRead),
+            "pages:write" => Ok(ApiTokenScope::PagesWrite),
+            "comments:read" => Ok(ApiTokenScope::CommentsRead),
+            "comments:write" => Ok(ApiTokenScope::CommentsWrite),
+            "media:upload" => Ok(ApiTokenScope::MediaUpload),
+            _ => Err(format!("Unrecognized api token scope: {}", s)),
+        }
+    }
+}
+
 use std::io::Write;
 
 use diesel::backend::Backend;
@@ -77,6 +181,39 @@ impl<Db: Backend> ToSql<SearchItemTypeType, Db> for SearchItemType {
             SearchItemType::BlogPost => out.write_all(b"Blog Post")?,
             SearchItemType::Page => out.write_all(b"Page")?,
             SearchItemType::ExternalLink => out.write_all(b"External Link")?,
+            SearchItemType::Series => out.write_all(b"Series")?,
+        }
+        Ok(IsNull::No)
+    }
+}
+
+impl<Db: Backend> ToSql<EmailStatusType, Db> for EmailStatus {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Db>) -> serialize::Result {
+        match *self {
+            EmailStatus::Pending => out.write_all(b"Pending")?,
+            EmailStatus::Sent => out.write_all(b"Sent")?,
+            EmailStatus::Failed => out.write_all(b"Failed")?,
+        }
+        Ok(IsNull::No)
+    }
+}
+
+impl<Db: Backend> ToSql<ContentLanguageType, Db> for ContentLanguage {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Db>) -> serialize::Result {
+        match *self {
+            ContentLanguage::English => out.write_all(b"English")?,
+            ContentLanguage::Greek => out.write_all(b"Greek")?,
+        }
+        Ok(IsNull::No)
+    }
+}
+
+impl<Db: Backend> ToSql<ReferrerTypeType, Db> for ReferrerType {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, Db>) -> serialize::Result {
+        match *self {
+            ReferrerType::Search => out.write_all(b"Search")?,
+            ReferrerType::Social => out.write_all(b"Social")?,
+            ReferrerType::Website => out.write_all(b"Website")?,
         }
         Ok(IsNull::No)
     }
@@ -115,6 +252,39 @@ impl FromSql<SearchItemTypeType, Pg> for SearchItemType {
             b"Blog Post" => Ok(SearchItemType::BlogPost),
             b"Page" => Ok(SearchItemType::Page),
             b"External Link" => Ok(SearchItemType::ExternalLink),
+            b"Series" => Ok(SearchItemType::Series),
+            _ => Err("Unrecognized enum variant".into()),
+        }
+    }
+}
+
+impl FromSql<EmailStatusType, Pg> for EmailStatus {
+    fn from_sql(bytes: Option<&<Pg as Backend>::RawValue>) -> deserialize::Result<Self> {
+        match not_none!(bytes) {
+            b"Pending" => Ok(EmailStatus::Pending),
+            b"Sent" => Ok(EmailStatus::Sent),
+            b"Failed" => Ok(EmailStatus::Failed),
+            _ => Err("Unrecognized enum variant".into()),
+        }
+    }
+}
+
+impl FromSql<ContentLanguageType, Pg> for ContentLanguage {
+    fn from_sql(bytes: Option<&<Pg as Backend>::RawValue>) -> deserialize::Result<Self> {
+        match not_none!(bytes) {
+            b"English" => Ok(ContentLanguage::English),
+            b"Greek" => Ok(ContentLanguage::Greek),
+            _ => Err("Unrecognized enum variant".into()),
+        }
+    }
+}
+
+impl FromSql<ReferrerTypeType, Pg> for ReferrerType {
+    fn from_sql(bytes: Option<&<Pg as Backend>::RawValue>) -> deserialize::Result<Self> {
+        match not_none!(bytes) {
+            b"Search" => Ok(ReferrerType::Search),
+            b"Social" => Ok(ReferrerType::Social),
+            b"Website" => Ok(ReferrerType::Website),
             _ => Err("Unrecognized enum variant".into()),
         }
     }
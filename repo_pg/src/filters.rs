@@ -1,15 +1,19 @@
 use crate::{
-    extra::{AdminLogAction, SearchItemType},
+    extra::{AdminLogAction, ContentLanguage, EmailStatus, ReferrerType, SearchItemType},
     models::queries::{
-        GetAllAdminLogsQuery, GetAllBlogPostCommentFlagsQuery, GetAllBlogPostCommentRatingsQuery,
-        GetAllBlogPostCommentsQuery, GetAllBlogPostsQuery, GetAllCategoriesQuery,
-        GetAllChangePasswordTokensQuery, GetAllHomePageLinksQuery,
-        GetAllIdentificationCookiesQuery, GetAllPageViewsQuery, GetAllProjectsQuery,
-        GetAllRefreshTokensQuery, GetAllSearchItemsQuery, GetAllTechnologiesQuery,
-        GetAllTextBodiesQuery, GetAllUploadedImagesQuery, GetAllUsersQuery,
-        GetAllVerifyEmailTokensQuery,
+        GetAllAdminLogsQuery, GetAllApiTokensQuery, GetAllBlogPostCommentFlagsQuery,
+        GetAllBlogPostCommentRatingsQuery, GetAllBlogPostCommentsQuery, GetAllBlogPostsQuery,
+        GetAllCategoriesQuery, GetAllChangePasswordTokensQuery, GetAllContactMessagesQuery,
+        GetAllHomePageLinksQuery, GetAllIdentificationCookiesQuery,
+        GetAllNewsletterDeliveriesQuery, GetAllNewsletterIssuesQuery,
+        GetAllNewsletterSubscribersQuery, GetAllOutboxEmailsQuery, GetAllPageViewsQuery,
+        GetAllProjectsQuery, GetAllRefreshTokensQuery, GetAllSearchItemsQuery, GetAllSeriesQuery,
+        GetAllTechnologiesQuery, GetAllTextBodiesQuery, GetAllUploadedImagesQuery,
+        GetAllUsersQuery, GetAllVerifyEmailTokensQuery, GetPageViewAnalyticsQuery,
+        GetSearchAnalyticsQuery,
     },
 };
+use chrono::{Duration, NaiveDate, NaiveDateTime};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -32,7 +36,12 @@ pub struct GetAllBlogPostsFilter {
     pub author_id: Option<i32>,
     pub category_id: Option<i32>,
     pub category_name: Option<String>,
+    /// Posts in any of these categories.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category_ids: Option<Vec<i32>>,
     pub published: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub published_after: Option<NaiveDateTime>,
 }
 
 impl GetAllBlogPostsFilter {
@@ -41,7 +50,9 @@ impl GetAllBlogPostsFilter {
             author_id: query.author,
             category_id: query.category_id,
             category_name: query.category_name,
+            category_ids: None,
             published: query.published,
+            published_after: None,
         }
     }
 }
@@ -51,6 +62,9 @@ pub struct GetAllProjectsFilter {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub technology_id: Option<i32>,
     pub technology_name: Option<String>,
+    /// Projects using any of these technologies.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub technology_ids: Option<Vec<i32>>,
     pub published: Option<bool>,
 }
 
@@ -59,6 +73,20 @@ impl GetAllProjectsFilter {
         Self {
             technology_id: query.technology_id,
             technology_name: query.technology_name,
+            technology_ids: None,
+            published: query.published,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct GetAllSeriesFilter {
+    pub published: Option<bool>,
+}
+
+impl GetAllSeriesFilter {
+    pub fn from_query(query: GetAllSeriesQuery) -> Self {
+        Self {
             published: query.published,
         }
     }
@@ -134,11 +162,13 @@ impl GetAllPageViewsFilter {
 }
 
 #[derive(Clone, Debug)]
-pub struct GetAllBlogPostCommentRatingsFilter {}
+pub struct GetAllBlogPostCommentRatingsFilter {
+    pub user_id: Option<i32>,
+}
 
 impl GetAllBlogPostCommentRatingsFilter {
     pub fn from_query(query: GetAllBlogPostCommentRatingsQuery) -> Self {
-        Self {}
+        Self { user_id: None }
     }
 }
 
@@ -204,6 +234,12 @@ impl GetAllChangePasswordTokensFilter {
 pub struct GetAllSearchItemsFilter {
     pub search_text: Option<String>,
     pub r#type: Option<SearchItemType>,
+    pub prefix: bool,
+    pub category: Option<String>,
+    pub technology: Option<String>,
+    pub created_after: Option<NaiveDateTime>,
+    pub created_before: Option<NaiveDateTime>,
+    pub language: Option<ContentLanguage>,
 }
 
 impl GetAllSearchItemsFilter {
@@ -211,6 +247,138 @@ impl GetAllSearchItemsFilter {
         Self {
             search_text: query.search_text,
             r#type: query.r#type,
+            prefix: query.prefix.unwrap_or(false),
+            category: query.category,
+            technology: query.technology,
+            created_after: query.created_from.map(|date| date.and_hms(0, 0, 0)),
+            // The range includes the whole last day
+            created_before: query
+                .created_to
+                .map(|date| (date + Duration::days(1)).and_hms(0, 0, 0)),
+            language: query.language,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GetAllApiTokensFilter {
+    pub user_id: Option<i32>,
+    pub revoked: Option<bool>,
+}
+
+impl GetAllApiTokensFilter {
+    pub fn from_query(query: GetAllApiTokensQuery) -> Self {
+        Self {
+            user_id: None,
+            revoked: query.revoked,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GetAllOutboxEmailsFilter {
+    pub status: Option<EmailStatus>,
+}
+
+impl GetAllOutboxEmailsFilter {
+    pub fn from_query(query: GetAllOutboxEmailsQuery) -> Self {
+        Self {
+            status: query.status,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GetAllNewsletterSubscribersFilter {
+    pub confirmed: Option<bool>,
+    pub unsubscribed: Option<bool>,
+}
+
+impl GetAllNewsletterSubscribersFilter {
+    pub fn from_query(query: GetAllNewsletterSubscribersQuery) -> Self {
+        Self {
+            confirmed: query.confirmed,
+            unsubscribed: query.unsubscribed,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GetAllNewsletterIssuesFilter {
+    pub sent: Option<bool>,
+}
+
+impl GetAllNewsletterIssuesFilter {
+    pub fn from_query(query: GetAllNewsletterIssuesQuery) -> Self {
+        Self { sent: query.sent }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GetAllNewsletterDeliveriesFilter {
+    pub newsletter_issue_id: Option<i32>,
+    pub status: Option<EmailStatus>,
+}
+
+impl GetAllNewsletterDeliveriesFilter {
+    pub fn from_query(newsletter_issue_id: i32, query: GetAllNewsletterDeliveriesQuery) -> Self {
+        Self {
+            newsletter_issue_id: Some(newsletter_issue_id),
+            status: query.status,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GetAllContactMessagesFilter {
+    pub read: Option<bool>,
+    pub archived: Option<bool>,
+    pub spam: Option<bool>,
+}
+
+impl GetAllContactMessagesFilter {
+    pub fn from_query(query: GetAllContactMessagesQuery) -> Self {
+        Self {
+            read: query.read,
+            archived: query.archived,
+            spam: query.spam,
+        }
+    }
+}
+
+/// A range of whole days, from the start of the first to the end of the last.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default)]
+pub struct DateRangeFilter {
+    pub from: Option<NaiveDateTime>,
+    pub to: Option<NaiveDateTime>,
+}
+
+impl DateRangeFilter {
+    pub fn from_dates(from: Option<NaiveDate>, to: Option<NaiveDate>) -> Self {
+        Self {
+            from: from.map(|date| date.and_hms(0, 0, 0)),
+            to: to.map(|date| (date + Duration::days(1)).and_hms(0, 0, 0)),
+        }
+    }
+
+    pub fn from_search_analytics_query(query: &GetSearchAnalyticsQuery) -> Self {
+        Self::from_dates(query.from, query.to)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct PageViewAnalyticsFilter {
+    pub range: DateRangeFilter,
+    pub referrer_type: Option<ReferrerType>,
+    pub include_bots: bool,
+}
+
+impl PageViewAnalyticsFilter {
+    pub fn from_query(query: &GetPageViewAnalyticsQuery) -> Self {
+        Self {
+            range: DateRangeFilter::from_dates(query.from, query.to),
+            referrer_type: query.referrer_type,
+            include_bots: query.include_bots.unwrap_or(false),
         }
     }
 }
@@ -116,4 +116,12 @@ impl<'a> IdentificationCookieRepo<'a> {
             .map(|text_body| domain::IdentificationCookie::from(text_body))
             .collect::<Vec<_>>())
     }
+
+    pub fn delete_expired(&self) -> Result<usize, diesel::result::Error> {
+        use crate::schema::identification_cookies::dsl::{expires_at, identification_cookies};
+
+        let conn = &self.conn.pg_conn;
+        diesel::delete(identification_cookies.filter(expires_at.lt(Utc::now().naive_utc())))
+            .execute(conn)
+    }
 }
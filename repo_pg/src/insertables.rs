@@ -1,8 +1,8 @@
-use crate::extra::{AdminLogAction, UserRole};
+use crate::extra::{AdminLogAction, ContentLanguage, ReferrerType, SearchItemType, UserRole};
 use crate::schema::*;
 use serde::Serialize;
 
-use chrono::NaiveDateTime;
+use chrono::{NaiveDate, NaiveDateTime};
 
 #[derive(Insertable, Clone, Serialize)]
 #[table_name = "blog_post_comments"]
@@ -10,6 +10,7 @@ pub struct NewBlogPostComment {
     pub body: Option<String>,
     pub author_id: i32,
     pub post_id: i32,
+    pub parent_id: Option<i32>,
 }
 
 #[derive(Insertable, Clone, Serialize)]
@@ -21,6 +22,7 @@ pub struct NewBlogPost {
     pub author_id: i32,
     pub description: Option<String>,
     pub slug: String,
+    pub language: ContentLanguage,
 }
 
 #[derive(Insertable, Clone, Serialize)]
@@ -31,6 +33,24 @@ pub struct NewProject {
     pub cover_image: Option<String>,
     pub name: String,
     pub slug: String,
+    pub language: ContentLanguage,
+}
+
+#[derive(Insertable, Clone, Serialize)]
+#[table_name = "series"]
+pub struct NewSeries {
+    pub title: String,
+    pub slug: String,
+    pub description: Option<String>,
+    pub language: ContentLanguage,
+}
+
+#[derive(Insertable, Clone, Serialize)]
+#[table_name = "series_blog_posts"]
+pub struct NewSeriesBlogPost {
+    pub series_id: i32,
+    pub blog_post_id: i32,
+    pub position: i32,
 }
 
 #[derive(Insertable, Clone, Serialize)]
@@ -64,6 +84,12 @@ pub struct NewHomePageLink {
 #[table_name = "categories"]
 pub struct NewCategory {
     pub name: String,
+    /// Made from the name when left out.
+    pub slug: Option<String>,
+    pub description: Option<String>,
+    pub icon: Option<String>,
+    pub color: Option<String>,
+    pub parent_id: Option<i32>,
 }
 
 #[derive(Insertable, Clone, Serialize)]
@@ -77,6 +103,12 @@ pub struct NewBlogPostCategory {
 #[table_name = "technologies"]
 pub struct NewTechnology {
     pub name: String,
+    /// Made from the name when left out.
+    pub slug: Option<String>,
+    pub description: Option<String>,
+    pub icon: Option<String>,
+    pub color: Option<String>,
+    pub parent_id: Option<i32>,
 }
 
 #[derive(Insertable, Clone, Serialize)]
@@ -105,6 +137,7 @@ pub struct NewTextBody {
     pub slug: String,
     pub body: String,
     pub url_used: Option<String>,
+    pub language: ContentLanguage,
 }
 
 #[derive(Insertable, Clone, Serialize)]
@@ -123,6 +156,7 @@ pub struct NewVerifyEmailToken {
     pub email: String,
     pub old_email: Option<String>,
     pub expires_at: NaiveDateTime,
+    pub undo: bool,
 }
 
 #[derive(Insertable, Clone, Serialize)]
@@ -135,6 +169,32 @@ pub struct NewPageView {
     pub latitude: Option<f64>,
     pub longitude: Option<f64>,
     pub country_code: Option<String>,
+    pub visit_id: Option<i32>,
+    pub referrer: Option<String>,
+    pub referrer_source: Option<String>,
+    pub referrer_type: Option<ReferrerType>,
+    pub utm_source: Option<String>,
+    pub utm_medium: Option<String>,
+    pub utm_campaign: Option<String>,
+    pub utm_term: Option<String>,
+    pub utm_content: Option<String>,
+    pub is_bot: bool,
+    pub page_path: String,
+}
+
+#[derive(Insertable, Clone, Serialize)]
+#[table_name = "daily_salts"]
+pub struct NewDailySalt {
+    pub day: NaiveDate,
+    pub salt: String,
+}
+
+#[derive(Insertable, Clone, Serialize)]
+#[table_name = "visits"]
+pub struct NewVisit {
+    pub id_hash: String,
+    pub landing_page: String,
+    pub exit_page: String,
 }
 
 #[derive(Insertable, Clone, Serialize)]
@@ -174,3 +234,94 @@ pub struct NewChangePasswordToken {
     pub user_id: i32,
     pub expires_at: NaiveDateTime,
 }
+
+#[derive(Insertable, Clone, Serialize)]
+#[table_name = "api_tokens"]
+pub struct NewApiToken {
+    pub user_id: i32,
+    pub name: String,
+    pub token_prefix: String,
+    pub token_hash: String,
+    pub scopes: Vec<String>,
+    pub expires_at: Option<NaiveDateTime>,
+}
+
+#[derive(Insertable, Clone, Serialize)]
+#[table_name = "email_outbox"]
+pub struct NewOutboxEmail {
+    pub to_address: String,
+    pub to_name: Option<String>,
+    pub subject: String,
+    pub body_html: String,
+    pub body_text: String,
+    pub list_unsubscribe: Option<String>,
+}
+
+#[derive(Insertable, Clone, Serialize)]
+#[table_name = "notification_preferences"]
+pub struct NewNotificationPreferences {
+    pub user_id: i32,
+    pub comment_replies: bool,
+    pub moderation_decisions: bool,
+    pub new_posts_digest: bool,
+}
+
+#[derive(Insertable, Clone, Serialize)]
+#[table_name = "newsletter_subscribers"]
+pub struct NewNewsletterSubscriber {
+    pub email: String,
+    pub token: String,
+}
+
+#[derive(Insertable, Clone, Serialize)]
+#[table_name = "newsletter_issues"]
+pub struct NewNewsletterIssue {
+    pub subject: String,
+    pub intro: String,
+    pub blog_post_ids: Vec<i32>,
+    pub user_id: i32,
+}
+
+#[derive(Insertable, Clone, Serialize)]
+#[table_name = "newsletter_deliveries"]
+pub struct NewNewsletterDelivery {
+    pub newsletter_issue_id: i32,
+    pub newsletter_subscriber_id: i32,
+}
+
+#[derive(Insertable, Clone, Serialize)]
+#[table_name = "contact_messages"]
+pub struct NewContactMessage {
+    pub from_email: String,
+    pub subject: String,
+    pub body: String,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub spam_score: i32,
+    pub spam: bool,
+}
+
+#[derive(Insertable, Clone, Serialize)]
+#[table_name = "contact_message_replies"]
+pub struct NewContactMessageReply {
+    pub contact_message_id: i32,
+    pub user_id: i32,
+    pub body: String,
+    pub email_outbox_id: Option<i32>,
+}
+
+#[derive(Insertable, Clone, Serialize)]
+#[table_name = "search_queries"]
+pub struct NewSearchQuery {
+    pub query: String,
+    pub item_type: Option<SearchItemType>,
+    pub result_count: i64,
+}
+
+#[derive(Insertable, Clone, Serialize)]
+#[table_name = "search_clicks"]
+pub struct NewSearchClick {
+    pub search_query_id: i32,
+    pub link: String,
+    pub position: Option<i32>,
+}
@@ -9,6 +9,7 @@ extern crate lazy_static;
 extern crate validator;
 
 pub mod admin_logs;
+pub mod api_tokens;
 pub mod blog_comments;
 pub mod blog_post_comment_flags;
 pub mod blog_post_comment_ratings;
@@ -16,6 +17,10 @@ pub mod blog_posts;
 pub mod categories;
 pub mod change_password_tokens;
 pub mod change_sets;
+pub mod contact_message_replies;
+pub mod contact_messages;
+pub mod daily_salts;
+pub mod email_outbox;
 pub mod entity;
 pub mod errors;
 pub mod extra;
@@ -25,24 +30,38 @@ pub mod home_page_links;
 pub mod identification_cookies;
 pub mod insertables;
 pub mod models;
+pub mod newsletter_deliveries;
+pub mod newsletter_issues;
+pub mod newsletter_subscribers;
+pub mod notification_preferences;
 pub mod options;
 pub mod page_views;
 pub mod passwords;
 pub mod pg_util;
 pub mod projects;
 pub mod refresh_tokens;
+pub mod related_items;
 pub mod schema;
 pub mod schema_extra;
+pub mod search_clicks;
 pub mod search_items;
+pub mod search_queries;
+pub mod search_refreshes;
+pub mod series;
 pub mod technologies;
 pub mod text_bodies;
+pub mod text_search;
 pub mod uploaded_images;
 pub mod users;
 pub mod verify_email_tokens;
+pub mod visits;
 
 pub mod exports {
     // we will use that a bit later
     pub use super::extra::AdminLogActionType as Admin_log_action;
+    pub use super::extra::ContentLanguageType as Content_language;
+    pub use super::extra::EmailStatusType as Email_status;
+    pub use super::extra::ReferrerTypeType as Referrer_type;
     pub use super::extra::SearchItemTypeType as Search_item_type;
     pub use super::extra::UserRoleType as User_role;
     pub use diesel_full_text_search::TsVector as Tsvector;
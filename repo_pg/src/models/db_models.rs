@@ -1,9 +1,13 @@
-use crate::extra::{AdminLogAction, SearchItemType, UserRole};
+use crate::extra::{
+    AdminLogAction, ContentLanguage, EmailStatus, ReferrerType, SearchItemType, UserRole,
+};
 use crate::schema::{
-    admin_logs, blog_post_comment_flags, blog_post_comment_ratings, blog_post_comments, blog_posts,
-    blog_posts_categories, categories, change_password_tokens, home_page_links,
-    identification_cookies, page_views, projects, projects_technologies, refresh_tokens,
-    technologies, text_bodies, uploaded_images, users, verify_email_tokens,
+    admin_logs, api_tokens, blog_post_comment_flags, blog_post_comment_ratings, blog_post_comments,
+    blog_posts, blog_posts_categories, categories, change_password_tokens, contact_message_replies,
+    contact_messages, email_outbox, home_page_links, identification_cookies, newsletter_deliveries,
+    newsletter_issues, newsletter_subscribers, notification_preferences, page_views, projects,
+    projects_technologies, refresh_tokens, search_refreshes, series, technologies, text_bodies,
+    uploaded_images, users, verify_email_tokens,
 };
 use crate::schema_extra::search_items;
 use chrono::NaiveDateTime;
@@ -22,6 +26,7 @@ pub struct BlogPostComment {
     pub updated_at: Option<NaiveDateTime>,
     pub author_id: i32,
     pub post_id: i32,
+    pub parent_id: Option<i32>,
 }
 
 #[derive(
@@ -39,6 +44,21 @@ pub struct BlogPost {
     pub author_id: i32,
     pub description: Option<String>,
     pub slug: String,
+    pub language: ContentLanguage,
+    pub published_at: Option<NaiveDateTime>,
+}
+
+#[derive(Queryable, Serialize, Deserialize, Debug, Clone, PartialEq, Identifiable)]
+#[table_name = "series"]
+pub struct Series {
+    pub id: i32,
+    pub title: String,
+    pub slug: String,
+    pub description: Option<String>,
+    pub published: bool,
+    pub language: ContentLanguage,
+    pub created_at: NaiveDateTime,
+    pub updated_at: Option<NaiveDateTime>,
 }
 
 #[derive(Queryable, Serialize, Deserialize, Debug, Clone, PartialEq, Identifiable)]
@@ -53,6 +73,7 @@ pub struct Project {
     pub name: String,
     pub published: bool,
     pub slug: String,
+    pub language: ContentLanguage,
 }
 
 #[derive(
@@ -102,6 +123,11 @@ pub struct HomePageLink {
 pub struct Category {
     pub id: i32,
     pub name: String,
+    pub slug: String,
+    pub description: Option<String>,
+    pub icon: Option<String>,
+    pub color: Option<String>,
+    pub parent_id: Option<i32>,
 }
 
 #[derive(
@@ -123,6 +149,11 @@ pub struct BlogPostCategory {
 pub struct Technology {
     pub id: i32,
     pub name: String,
+    pub slug: String,
+    pub description: Option<String>,
+    pub icon: Option<String>,
+    pub color: Option<String>,
+    pub parent_id: Option<i32>,
 }
 
 #[derive(
@@ -166,6 +197,7 @@ pub struct TextBody {
     pub url_used: Option<String>,
     pub created_at: NaiveDateTime,
     pub updated_at: Option<NaiveDateTime>,
+    pub language: ContentLanguage,
 }
 
 #[derive(
@@ -194,6 +226,7 @@ pub struct VerifyEmailToken {
     pub used: bool,
     pub created_at: NaiveDateTime,
     pub expires_at: NaiveDateTime,
+    pub undo: bool,
 }
 
 #[derive(
@@ -210,6 +243,17 @@ pub struct PageView {
     pub latitude: Option<f64>,
     pub longitude: Option<f64>,
     pub country_code: Option<String>,
+    pub visit_id: Option<i32>,
+    pub referrer: Option<String>,
+    pub referrer_source: Option<String>,
+    pub referrer_type: Option<ReferrerType>,
+    pub utm_source: Option<String>,
+    pub utm_medium: Option<String>,
+    pub utm_campaign: Option<String>,
+    pub utm_term: Option<String>,
+    pub utm_content: Option<String>,
+    pub is_bot: bool,
+    pub page_path: String,
 }
 
 #[derive(
@@ -286,4 +330,160 @@ pub struct SearchItem {
     pub description: String,
     pub item_type: SearchItemType,
     pub link: String,
+    pub language: ContentLanguage,
+}
+
+#[derive(Queryable, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RelatedItem {
+    pub item_id: i32,
+    pub item_type: SearchItemType,
+    pub rank: i32,
+    pub related_id: i32,
+    pub title: String,
+    pub created_at: Option<NaiveDateTime>,
+    pub image: Option<String>,
+    pub description: String,
+    pub link: String,
+    pub language: ContentLanguage,
+    pub score: f64,
+}
+
+#[derive(
+    Queryable, Serialize, Deserialize, Debug, Clone, PartialEq, Identifiable, Associations,
+)]
+#[belongs_to(parent = "User", foreign_key = "user_id")]
+#[table_name = "api_tokens"]
+pub struct ApiToken {
+    pub id: i32,
+    pub user_id: i32,
+    pub name: String,
+    pub token_prefix: String,
+    pub token_hash: String,
+    pub scopes: Vec<String>,
+    pub revoked: bool,
+    pub created_at: NaiveDateTime,
+    pub expires_at: Option<NaiveDateTime>,
+    pub last_used_at: Option<NaiveDateTime>,
+}
+
+#[derive(Queryable, Serialize, Deserialize, Debug, Clone, PartialEq, Identifiable)]
+#[table_name = "email_outbox"]
+pub struct OutboxEmail {
+    pub id: i32,
+    pub to_address: String,
+    pub to_name: Option<String>,
+    pub subject: String,
+    pub body_html: String,
+    pub body_text: String,
+    pub status: EmailStatus,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+    pub sent_at: Option<NaiveDateTime>,
+    pub list_unsubscribe: Option<String>,
+}
+
+#[derive(
+    Queryable, Serialize, Deserialize, Debug, Clone, PartialEq, Identifiable, Associations,
+)]
+#[belongs_to(parent = "User", foreign_key = "user_id")]
+#[table_name = "notification_preferences"]
+pub struct NotificationPreferences {
+    pub id: i32,
+    pub user_id: i32,
+    pub comment_replies: bool,
+    pub moderation_decisions: bool,
+    pub new_posts_digest: bool,
+    pub last_digest_sent_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+#[derive(Queryable, Serialize, Deserialize, Debug, Clone, PartialEq, Identifiable)]
+#[table_name = "newsletter_subscribers"]
+pub struct NewsletterSubscriber {
+    pub id: i32,
+    pub email: String,
+    pub token: String,
+    pub confirmed: bool,
+    pub created_at: NaiveDateTime,
+    pub confirmed_at: Option<NaiveDateTime>,
+    pub unsubscribed_at: Option<NaiveDateTime>,
+}
+
+#[derive(
+    Queryable, Serialize, Deserialize, Debug, Clone, PartialEq, Identifiable, Associations,
+)]
+#[belongs_to(parent = "User", foreign_key = "user_id")]
+#[table_name = "newsletter_issues"]
+pub struct NewsletterIssue {
+    pub id: i32,
+    pub subject: String,
+    pub intro: String,
+    pub blog_post_ids: Vec<i32>,
+    pub user_id: i32,
+    pub created_at: NaiveDateTime,
+    pub updated_at: Option<NaiveDateTime>,
+    pub sent_at: Option<NaiveDateTime>,
+}
+
+#[derive(
+    Queryable, Serialize, Deserialize, Debug, Clone, PartialEq, Identifiable, Associations,
+)]
+#[belongs_to(parent = "NewsletterIssue", foreign_key = "newsletter_issue_id")]
+#[belongs_to(
+    parent = "NewsletterSubscriber",
+    foreign_key = "newsletter_subscriber_id"
+)]
+#[table_name = "newsletter_deliveries"]
+pub struct NewsletterDelivery {
+    pub id: i32,
+    pub newsletter_issue_id: i32,
+    pub newsletter_subscriber_id: i32,
+    pub email_outbox_id: Option<i32>,
+    pub created_at: NaiveDateTime,
+    pub failed_at: Option<NaiveDateTime>,
+    pub last_error: Option<String>,
+}
+
+#[derive(Queryable, Serialize, Deserialize, Debug, Clone, PartialEq, Identifiable)]
+#[table_name = "contact_messages"]
+pub struct ContactMessage {
+    pub id: i32,
+    pub from_email: String,
+    pub subject: String,
+    pub body: String,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub spam_score: i32,
+    pub spam: bool,
+    pub created_at: NaiveDateTime,
+    pub read_at: Option<NaiveDateTime>,
+    pub archived_at: Option<NaiveDateTime>,
+    pub replied_at: Option<NaiveDateTime>,
+}
+
+#[derive(
+    Queryable, Serialize, Deserialize, Debug, Clone, PartialEq, Identifiable, Associations,
+)]
+#[belongs_to(parent = "ContactMessage", foreign_key = "contact_message_id")]
+#[belongs_to(parent = "User", foreign_key = "user_id")]
+#[table_name = "contact_message_replies"]
+pub struct ContactMessageReply {
+    pub id: i32,
+    pub contact_message_id: i32,
+    pub user_id: i32,
+    pub body: String,
+    pub email_outbox_id: Option<i32>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Queryable, Serialize, Deserialize, Debug, Clone, PartialEq, Identifiable)]
+#[table_name = "search_refreshes"]
+pub struct SearchRefresh {
+    pub id: i32,
+    pub requested_at: NaiveDateTime,
+    pub refreshed_at: Option<NaiveDateTime>,
+    pub refreshed_through: Option<NaiveDateTime>,
 }
@@ -1,4 +1,7 @@
-use crate::extra::{AdminLogAction, SearchItemType, UserRole};
+use crate::extra::{
+    AdminLogAction, ApiTokenScope, ContentLanguage, EmailStatus, ReferrerType, SearchItemType,
+    UserRole,
+};
 use crate::models::db_models;
 use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
@@ -12,6 +15,8 @@ pub struct BlogPostComment {
     pub body: String,
     pub author: User,
     pub post_id: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<i32>,
     pub created_at: NaiveDateTime,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub updated_at: Option<NaiveDateTime>,
@@ -28,6 +33,7 @@ impl BlogPostComment {
             id: comment.id,
             updated_at: comment.updated_at,
             post_id: comment.post_id,
+            parent_id: comment.parent_id,
         }
     }
 }
@@ -46,6 +52,12 @@ pub struct BlogPost {
     pub author: User,
     pub categories: Vec<String>,
     pub slug: String,
+    pub language: ContentLanguage,
+    /// Page views by people, bots left out.
+    pub view_count: i64,
+    /// Where the post is in its series, when it is part of one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub series: Option<SeriesNavigation>,
 }
 
 impl BlogPost {
@@ -53,6 +65,7 @@ impl BlogPost {
         post: db_models::BlogPost,
         author: db_models::User,
         categories_list: Vec<String>,
+        view_count: i64,
     ) -> Self {
         let mut author = User::from(author);
         author.email = None;
@@ -66,10 +79,75 @@ impl BlogPost {
             updated_at: post.updated_at,
             categories: categories_list,
             slug: post.slug,
+            language: post.language,
+            view_count,
+            series: None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "bindings/responses/Series.ts")]
+#[serde(rename_all = "camelCase")]
+pub struct Series {
+    pub id: i32,
+    pub title: String,
+    pub slug: String,
+    pub description: Option<String>,
+    pub published: bool,
+    pub language: ContentLanguage,
+    pub created_at: NaiveDateTime,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<NaiveDateTime>,
+    pub posts: Vec<SeriesPost>,
+    /// The ids of `posts`, in the form the create and update requests take them.
+    pub blog_post_ids: Vec<i32>,
+}
+
+impl Series {
+    pub fn from(series: db_models::Series, posts: Vec<SeriesPost>) -> Self {
+        Self {
+            id: series.id,
+            title: series.title,
+            slug: series.slug,
+            description: series.description,
+            published: series.published,
+            language: series.language,
+            created_at: series.created_at,
+            updated_at: series.updated_at,
+            blog_post_ids: posts.iter().map(|post| post.id).collect(),
+            posts,
         }
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "bindings/responses/SeriesPost.ts")]
+#[serde(rename_all = "camelCase")]
+pub struct SeriesPost {
+    pub id: i32,
+    pub title: String,
+    pub slug: String,
+    pub position: i32,
+    pub published: bool,
+}
+
+/// A post's place in its series, with the posts before and after it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "bindings/responses/SeriesNavigation.ts")]
+#[serde(rename_all = "camelCase")]
+pub struct SeriesNavigation {
+    pub id: i32,
+    pub title: String,
+    pub slug: String,
+    pub position: i32,
+    pub post_count: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous: Option<SeriesPost>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next: Option<SeriesPost>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
 #[ts(export, export_to = "bindings/responses/Project.ts")]
 #[serde(rename_all = "camelCase")]
@@ -85,10 +163,13 @@ pub struct Project {
     pub name: String,
     pub published: bool,
     pub slug: String,
+    pub language: ContentLanguage,
+    /// Page views by people, bots left out.
+    pub view_count: i64,
 }
 
 impl Project {
-    pub fn from(project: db_models::Project, technologies: Vec<String>) -> Self {
+    pub fn from(project: db_models::Project, technologies: Vec<String>, view_count: i64) -> Self {
         Self {
             body: project.body,
             created_at: project.created_at,
@@ -100,6 +181,8 @@ impl Project {
             name: project.name,
             published: project.published,
             slug: project.slug,
+            language: project.language,
+            view_count,
         }
     }
 }
@@ -157,6 +240,11 @@ impl HomePageLink {
 pub struct Technology {
     pub id: i32,
     pub name: String,
+    pub slug: String,
+    pub description: Option<String>,
+    pub icon: Option<String>,
+    pub color: Option<String>,
+    pub parent_id: Option<i32>,
 }
 
 impl Technology {
@@ -164,6 +252,11 @@ impl Technology {
         Self {
             id: tech.id,
             name: tech.name,
+            slug: tech.slug,
+            description: tech.description,
+            icon: tech.icon,
+            color: tech.color,
+            parent_id: tech.parent_id,
         }
     }
 }
@@ -174,6 +267,11 @@ impl Technology {
 pub struct Category {
     pub id: i32,
     pub name: String,
+    pub slug: String,
+    pub description: Option<String>,
+    pub icon: Option<String>,
+    pub color: Option<String>,
+    pub parent_id: Option<i32>,
 }
 
 impl Category {
@@ -181,10 +279,43 @@ impl Category {
         Self {
             id: category.id,
             name: category.name,
+            slug: category.slug,
+            description: category.description,
+            icon: category.icon,
+            color: category.color,
+            parent_id: category.parent_id,
         }
     }
 }
 
+/// A technology with its place in the tree and the published projects using it or
+/// any technology nested under it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "bindings/responses/TechnologyDetail.ts")]
+#[serde(rename_all = "camelCase")]
+pub struct TechnologyDetail {
+    #[serde(flatten)]
+    pub technology: Technology,
+    pub parent: Option<Technology>,
+    pub children: Vec<Technology>,
+    pub projects: Vec<Project>,
+    pub project_count: i64,
+}
+
+/// A category with its place in the tree and the published posts in it or in any
+/// category nested under it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "bindings/responses/CategoryDetail.ts")]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryDetail {
+    #[serde(flatten)]
+    pub category: Category,
+    pub parent: Option<Category>,
+    pub children: Vec<Category>,
+    pub blog_posts: Vec<BlogPost>,
+    pub blog_post_count: i64,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
 #[ts(export, export_to = "bindings/responses/RefreshToken.ts")]
 #[serde(rename_all = "camelCase")]
@@ -256,6 +387,7 @@ pub struct TextBody {
     pub created_at: NaiveDateTime,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub updated_at: Option<NaiveDateTime>,
+    pub language: ContentLanguage,
 }
 
 impl TextBody {
@@ -268,6 +400,7 @@ impl TextBody {
             url_used: text.url_used,
             created_at: text.created_at,
             updated_at: text.updated_at,
+            language: text.language,
         }
     }
 }
@@ -307,6 +440,7 @@ pub struct VerifyEmailToken {
     pub used: bool,
     pub created_at: NaiveDateTime,
     pub expires_at: NaiveDateTime,
+    pub undo: bool,
 }
 
 impl VerifyEmailToken {
@@ -320,7 +454,8 @@ impl VerifyEmailToken {
             invalidated: token.invalidated,
             used: token.used,
             created_at: token.created_at,
-            expires_at: token.created_at,
+            expires_at: token.expires_at,
+            undo: token.undo,
         }
     }
 }
@@ -338,6 +473,16 @@ pub struct PageView {
     pub id_hash: String,
     pub registered: bool,
     pub created_at: NaiveDateTime,
+    pub visit_id: Option<i32>,
+    pub referrer: Option<String>,
+    pub referrer_source: Option<String>,
+    pub referrer_type: Option<ReferrerType>,
+    pub utm_source: Option<String>,
+    pub utm_medium: Option<String>,
+    pub utm_campaign: Option<String>,
+    pub utm_term: Option<String>,
+    pub utm_content: Option<String>,
+    pub is_bot: bool,
 }
 
 impl PageView {
@@ -352,10 +497,118 @@ impl PageView {
             id_hash: view.id_hash,
             registered: view.registered,
             created_at: view.created_at,
+            visit_id: view.visit_id,
+            referrer: view.referrer,
+            referrer_source: view.referrer_source,
+            referrer_type: view.referrer_type,
+            utm_source: view.utm_source,
+            utm_medium: view.utm_medium,
+            utm_campaign: view.utm_campaign,
+            utm_term: view.utm_term,
+            utm_content: view.utm_content,
+            is_bot: view.is_bot,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "bindings/responses/PageViewTraffic.ts")]
+#[serde(rename_all = "camelCase")]
+pub struct PageViewTraffic {
+    pub period: NaiveDateTime,
+    pub views: i64,
+    pub unique_visitors: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "bindings/responses/PageStats.ts")]
+#[serde(rename_all = "camelCase")]
+pub struct PageStats {
+    pub page_url: String,
+    pub views: i64,
+    pub unique_visitors: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "bindings/responses/CountryStats.ts")]
+#[serde(rename_all = "camelCase")]
+pub struct CountryStats {
+    /// Missing for views that could not be located.
+    pub country_code: Option<String>,
+    pub views: i64,
+    pub unique_visitors: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "bindings/responses/VisitorTypeStats.ts")]
+#[serde(rename_all = "camelCase")]
+pub struct VisitorTypeStats {
+    pub registered_views: i64,
+    pub anonymous_views: i64,
+    pub registered_visitors: i64,
+    pub anonymous_visitors: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "bindings/responses/ReferrerStats.ts")]
+#[serde(rename_all = "camelCase")]
+pub struct ReferrerStats {
+    /// A known site's name, or the referring domain.
+    pub source: String,
+    pub referrer_type: ReferrerType,
+    pub visits: i64,
+    pub views: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "bindings/responses/CampaignStats.ts")]
+#[serde(rename_all = "camelCase")]
+pub struct CampaignStats {
+    pub utm_source: Option<String>,
+    pub utm_medium: Option<String>,
+    pub utm_campaign: Option<String>,
+    pub visits: i64,
+    pub views: i64,
+}
+
+/// A page view as sent to admins watching live, without anything identifying the visitor.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "bindings/responses/LivePageView.ts")]
+#[serde(rename_all = "camelCase")]
+pub struct LivePageView {
+    pub page_url: String,
+    pub country_code: Option<String>,
+    pub referrer_source: Option<String>,
+    pub referrer_type: Option<ReferrerType>,
+    pub registered: bool,
+    pub is_bot: bool,
+    pub created_at: NaiveDateTime,
+    /// Visitors other than bots with a view in the last 5 minutes.
+    pub active_visitors: i64,
+}
+
+impl LivePageView {
+    pub fn from(view: &PageView, active_visitors: i64) -> Self {
+        Self {
+            page_url: view.page_url.clone(),
+            country_code: view.country_code.clone(),
+            referrer_source: view.referrer_source.clone(),
+            referrer_type: view.referrer_type,
+            registered: view.registered,
+            is_bot: view.is_bot,
+            created_at: view.created_at,
+            active_visitors,
         }
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "bindings/responses/ActiveVisitors.ts")]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveVisitors {
+    pub active_visitors: i64,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
 #[ts(export, export_to = "bindings/responses/BlogPostCommentRating.ts")]
 #[serde(rename_all = "camelCase")]
@@ -475,10 +728,14 @@ pub struct SearchItem {
     pub description: String,
     pub item_type: SearchItemType,
     pub link: String,
+    pub language: ContentLanguage,
+    /// Fragments of the item around the matched terms, wrapped in `<mark>` tags.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headline: Option<String>,
 }
 
 impl SearchItem {
-    pub fn from(search_item: db_models::SearchItem) -> Self {
+    pub fn from(search_item: db_models::SearchItem, headline: Option<String>) -> Self {
         Self {
             title: search_item.title,
             created_at: search_item.created_at,
@@ -487,6 +744,503 @@ impl SearchItem {
             description: search_item.description,
             item_type: search_item.item_type,
             link: search_item.link,
+            language: search_item.language,
+            headline,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "bindings/responses/TrendingItem.ts")]
+#[serde(rename_all = "camelCase")]
+pub struct TrendingItem {
+    pub title: String,
+    pub created_at: Option<NaiveDateTime>,
+    pub image: Option<String>,
+    pub description: String,
+    pub item_type: SearchItemType,
+    pub link: String,
+    pub language: ContentLanguage,
+    /// Views by people over the trending period.
+    pub views: i64,
+}
+
+impl TrendingItem {
+    pub fn from(search_item: db_models::SearchItem, views: i64) -> Self {
+        Self {
+            title: search_item.title,
+            created_at: search_item.created_at,
+            image: search_item.image,
+            description: search_item.description,
+            item_type: search_item.item_type,
+            link: search_item.link,
+            language: search_item.language,
+            views,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "bindings/responses/RelatedItem.ts")]
+#[serde(rename_all = "camelCase")]
+pub struct RelatedItem {
+    pub id: i32,
+    pub title: String,
+    pub created_at: Option<NaiveDateTime>,
+    pub image: Option<String>,
+    pub description: String,
+    pub item_type: SearchItemType,
+    pub link: String,
+    pub language: ContentLanguage,
+    /// Shared categories or technologies, plus up to five for shared terms.
+    pub score: f64,
+}
+
+impl RelatedItem {
+    pub fn from(related_item: db_models::RelatedItem) -> Self {
+        Self {
+            id: related_item.related_id,
+            title: related_item.title,
+            created_at: related_item.created_at,
+            image: related_item.image,
+            description: related_item.description,
+            item_type: related_item.item_type,
+            link: related_item.link,
+            language: related_item.language,
+            score: related_item.score,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "bindings/responses/ApiToken.ts")]
+#[serde(rename_all = "camelCase")]
+pub struct ApiToken {
+    pub id: i32,
+    pub user_id: i32,
+    pub name: String,
+    pub token_prefix: String,
+    pub scopes: Vec<ApiTokenScope>,
+    pub revoked: bool,
+    pub created_at: NaiveDateTime,
+    pub expires_at: Option<NaiveDateTime>,
+    pub last_used_at: Option<NaiveDateTime>,
+}
+
+impl ApiToken {
+    pub fn from(token: db_models::ApiToken) -> Self {
+        Self {
+            id: token.id,
+            user_id: token.user_id,
+            name: token.name,
+            token_prefix: token.token_prefix,
+            scopes: token
+                .scopes
+                .iter()
+                .filter_map(|scope| scope.parse().ok())
+                .collect(),
+            revoked: token.revoked,
+            created_at: token.created_at,
+            expires_at: token.expires_at,
+            last_used_at: token.last_used_at,
+        }
+    }
+
+    pub fn is_expired(&self, now: NaiveDateTime) -> bool {
+        matches!(self.expires_at, Some(expires_at) if expires_at <= now)
+    }
+
+    pub fn has_scope(&self, scope: ApiTokenScope) -> bool {
+        self.scopes.contains(&scope)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "bindings/responses/OutboxEmail.ts")]
+#[serde(rename_all = "camelCase")]
+pub struct OutboxEmail {
+    pub id: i32,
+    pub to_address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_name: Option<String>,
+    pub subject: String,
+    pub body_html: String,
+    pub body_text: String,
+    pub status: EmailStatus,
+    pub attempts: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    pub next_attempt_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sent_at: Option<NaiveDateTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub list_unsubscribe: Option<String>,
+}
+
+impl OutboxEmail {
+    pub fn from(email: db_models::OutboxEmail) -> Self {
+        Self {
+            id: email.id,
+            to_address: email.to_address,
+            to_name: email.to_name,
+            subject: email.subject,
+            body_html: email.body_html,
+            body_text: email.body_text,
+            status: email.status,
+            attempts: email.attempts,
+            last_error: email.last_error,
+            next_attempt_at: email.next_attempt_at,
+            created_at: email.created_at,
+            sent_at: email.sent_at,
+            list_unsubscribe: email.list_unsubscribe,
+        }
+    }
+}
+
+/// The kinds of email a user can opt in or out of.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, TS)]
+#[ts(export, export_to = "bindings/responses/NotificationKind.ts")]
+pub enum NotificationKind {
+    CommentReplies,
+    ModerationDecisions,
+    NewPostsDigest,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "bindings/responses/NotificationPreferences.ts")]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationPreferences {
+    pub user_id: i32,
+    pub comment_replies: bool,
+    pub moderation_decisions: bool,
+    pub new_posts_digest: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_digest_sent_at: Option<NaiveDateTime>,
+}
+
+impl NotificationPreferences {
+    pub fn from(preferences: db_models::NotificationPreferences) -> Self {
+        Self {
+            user_id: preferences.user_id,
+            comment_replies: preferences.comment_replies,
+            moderation_decisions: preferences.moderation_decisions,
+            new_posts_digest: preferences.new_posts_digest,
+            last_digest_sent_at: preferences.last_digest_sent_at,
+        }
+    }
+
+    /// What a user gets before they change anything.
+    pub fn default_for(user_id: i32) -> Self {
+        Self {
+            user_id,
+            comment_replies: true,
+            moderation_decisions: true,
+            new_posts_digest: false,
+            last_digest_sent_at: None,
+        }
+    }
+
+    pub fn is_enabled(&self, kind: NotificationKind) -> bool {
+        match kind {
+            NotificationKind::CommentReplies => self.comment_replies,
+            NotificationKind::ModerationDecisions => self.moderation_decisions,
+            NotificationKind::NewPostsDigest => self.new_posts_digest,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "bindings/responses/NewsletterSubscriber.ts")]
+#[serde(rename_all = "camelCase")]
+pub struct NewsletterSubscriber {
+    pub id: i32,
+    pub email: String,
+    pub confirmed: bool,
+    pub created_at: NaiveDateTime,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmed_at: Option<NaiveDateTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unsubscribed_at: Option<NaiveDateTime>,
+}
+
+impl NewsletterSubscriber {
+    pub fn from(subscriber: db_models::NewsletterSubscriber) -> Self {
+        Self {
+            id: subscriber.id,
+            email: subscriber.email,
+            confirmed: subscriber.confirmed,
+            created_at: subscriber.created_at,
+            confirmed_at: subscriber.confirmed_at,
+            unsubscribed_at: subscriber.unsubscribed_at,
+        }
+    }
+
+    /// Whether issues should be sent to this address.
+    pub fn is_active(&self) -> bool {
+        self.confirmed && self.unsubscribed_at.is_none()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "bindings/responses/NewsletterIssue.ts")]
+#[serde(rename_all = "camelCase")]
+pub struct NewsletterIssue {
+    pub id: i32,
+    pub subject: String,
+    pub intro: String,
+    pub blog_post_ids: Vec<i32>,
+    pub author: User,
+    pub created_at: NaiveDateTime,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<NaiveDateTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sent_at: Option<NaiveDateTime>,
+}
+
+impl NewsletterIssue {
+    pub fn from(issue: db_models::NewsletterIssue, author: db_models::User) -> Self {
+        let mut author = User::from(author);
+        author.email = None;
+        Self {
+            id: issue.id,
+            subject: issue.subject,
+            intro: issue.intro,
+            blog_post_ids: issue.blog_post_ids,
+            author,
+            created_at: issue.created_at,
+            updated_at: issue.updated_at,
+            sent_at: issue.sent_at,
+        }
+    }
+}
+
+/// An issue going to one subscriber. The status comes from the outbox once the
+/// email has been queued, until then it is pending.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "bindings/responses/NewsletterDelivery.ts")]
+#[serde(rename_all = "camelCase")]
+pub struct NewsletterDelivery {
+    pub id: i32,
+    pub newsletter_issue_id: i32,
+    pub subscriber: NewsletterSubscriber,
+    pub status: EmailStatus,
+    pub attempts: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    pub created_at: NaiveDateTime,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sent_at: Option<NaiveDateTime>,
+}
+
+impl NewsletterDelivery {
+    pub fn from(
+        delivery: db_models::NewsletterDelivery,
+        subscriber: db_models::NewsletterSubscriber,
+        email: Option<db_models::OutboxEmail>,
+    ) -> Self {
+        let (status, attempts, last_error, sent_at) = match email {
+            Some(email) => (
+                email.status,
+                email.attempts,
+                email.last_error,
+                email.sent_at,
+            ),
+            None => match delivery.failed_at {
+                Some(_) => (EmailStatus::Failed, 1, delivery.last_error, None),
+                None => (EmailStatus::Pending, 0, None, None),
+            },
+        };
+        Self {
+            id: delivery.id,
+            newsletter_issue_id: delivery.newsletter_issue_id,
+            subscriber: NewsletterSubscriber::from(subscriber),
+            status,
+            attempts,
+            last_error,
+            created_at: delivery.created_at,
+            sent_at,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "bindings/responses/ContactMessage.ts")]
+#[serde(rename_all = "camelCase")]
+pub struct ContactMessage {
+    pub id: i32,
+    pub from_email: String,
+    pub subject: String,
+    pub body: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip_address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    pub spam_score: i32,
+    pub spam: bool,
+    pub created_at: NaiveDateTime,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_at: Option<NaiveDateTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived_at: Option<NaiveDateTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replied_at: Option<NaiveDateTime>,
+}
+
+impl ContactMessage {
+    pub fn from(message: db_models::ContactMessage) -> Self {
+        Self {
+            id: message.id,
+            from_email: message.from_email,
+            subject: message.subject,
+            body: message.body,
+            ip_address: message.ip_address,
+            user_agent: message.user_agent,
+            spam_score: message.spam_score,
+            spam: message.spam,
+            created_at: message.created_at,
+            read_at: message.read_at,
+            archived_at: message.archived_at,
+            replied_at: message.replied_at,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "bindings/responses/ContactMessageReply.ts")]
+#[serde(rename_all = "camelCase")]
+pub struct ContactMessageReply {
+    pub id: i32,
+    pub contact_message_id: i32,
+    pub author: User,
+    pub body: String,
+    pub status: EmailStatus,
+    pub created_at: NaiveDateTime,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sent_at: Option<NaiveDateTime>,
+}
+
+impl ContactMessageReply {
+    pub fn from(
+        reply: db_models::ContactMessageReply,
+        user: db_models::User,
+        email: Option<db_models::OutboxEmail>,
+    ) -> Self {
+        let (status, sent_at) = match email {
+            Some(email) => (email.status, email.sent_at),
+            None => (EmailStatus::Pending, None),
+        };
+        Self {
+            id: reply.id,
+            contact_message_id: reply.contact_message_id,
+            author: User::from(user),
+            body: reply.body,
+            status,
+            created_at: reply.created_at,
+            sent_at,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "bindings/responses/SearchCompletion.ts")]
+#[serde(rename_all = "camelCase")]
+pub struct SearchCompletion {
+    pub title: String,
+    pub item_type: SearchItemType,
+    pub link: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "bindings/responses/SearchSuggestions.ts")]
+#[serde(rename_all = "camelCase")]
+pub struct SearchSuggestions {
+    pub completions: Vec<SearchCompletion>,
+    /// A corrected query, only given when the original one finds nothing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub did_you_mean: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "bindings/responses/SearchRefresh.ts")]
+#[serde(rename_all = "camelCase")]
+pub struct SearchRefresh {
+    pub requested_at: NaiveDateTime,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refreshed_at: Option<NaiveDateTime>,
+    /// Whether content changed since the last refresh.
+    pub pending: bool,
+}
+
+impl SearchRefresh {
+    /// `last_pending` is when the newest request not refreshed yet was made.
+    pub fn from(refresh: db_models::SearchRefresh, last_pending: Option<NaiveDateTime>) -> Self {
+        Self {
+            requested_at: last_pending.unwrap_or(refresh.requested_at),
+            refreshed_at: refresh.refreshed_at,
+            pending: last_pending.is_some() || refresh.refreshed_through.is_none(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "bindings/responses/SearchTypeFacet.ts")]
+#[serde(rename_all = "camelCase")]
+pub struct SearchTypeFacet {
+    pub item_type: SearchItemType,
+    pub count: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "bindings/responses/SearchFacet.ts")]
+#[serde(rename_all = "camelCase")]
+pub struct SearchFacet {
+    pub name: String,
+    pub count: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "bindings/responses/SearchFacets.ts")]
+#[serde(rename_all = "camelCase")]
+pub struct SearchFacets {
+    pub types: Vec<SearchTypeFacet>,
+    pub categories: Vec<SearchFacet>,
+    pub technologies: Vec<SearchFacet>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "bindings/responses/SearchQueryStats.ts")]
+#[serde(rename_all = "camelCase")]
+pub struct SearchQueryStats {
+    pub query: String,
+    pub searches: i64,
+    /// Searches where at least one result was followed.
+    pub clicked_searches: i64,
+    pub average_results: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, TS)]
+#[ts(export, export_to = "bindings/responses/ClickThroughStats.ts")]
+#[serde(rename_all = "camelCase")]
+pub struct ClickThroughStats {
+    pub period: NaiveDateTime,
+    pub searches: i64,
+    pub clicked_searches: i64,
+    pub click_through_rate: f64,
+}
+
+impl ClickThroughStats {
+    pub fn from(period: NaiveDateTime, searches: i64, clicked_searches: i64) -> Self {
+        let click_through_rate = if searches > 0 {
+            clicked_searches as f64 / searches as f64
+        } else {
+            0.0
+        };
+        Self {
+            period,
+            searches,
+            clicked_searches,
+            click_through_rate,
         }
     }
 }
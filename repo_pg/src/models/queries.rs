@@ -1,14 +1,17 @@
 use axum_derive::ValidatedExtractedQuery;
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    extra::{AdminLogAction, SearchItemType},
+    extra::{AdminLogAction, ContentLanguage, EmailStatus, ReferrerType, SearchItemType},
     options::{
-        AdminLogSortType, BlogPostCommentFlagSortType, BlogPostCommentRatingSortType,
-        BlogPostCommentSortType, BlogPostSortType, CategorySortType, ChangePasswordTokenSortType,
-        HomePageLinkSortType, IdentificationCookieSortType, PageViewSortType, PaginationOptions,
-        ProjectSortType, RefreshTokenSortType, TechnologySortType, TextBodySortType,
-        UploadedImageSortType, UserSortType, VerifyEmailTokenSortType,
+        AdminLogSortType, ApiTokenSortType, BlogPostCommentFlagSortType,
+        BlogPostCommentRatingSortType, BlogPostCommentSortType, BlogPostSortType, CategorySortType,
+        ChangePasswordTokenSortType, ContactMessageSortType, HomePageLinkSortType,
+        IdentificationCookieSortType, NewsletterIssueSortType, NewsletterSubscriberSortType,
+        OutboxEmailSortType, PageViewSortType, PaginationOptions, ProjectSortType,
+        RefreshTokenSortType, ReportInterval, SearchItemsSortType, TechnologySortType,
+        TextBodySortType, UploadedImageSortType, UserSortType, VerifyEmailTokenSortType,
     },
 };
 
@@ -88,6 +91,29 @@ pub struct GetProjectQuery {
     pub use_slug: Option<bool>,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, Validate, ValidatedExtractedQuery)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAllSeriesQuery {
+    pub page: Option<i64>,
+    pub page_size: Option<i64>,
+    pub published: Option<bool>,
+}
+
+impl PaginatedQuery for GetAllSeriesQuery {
+    fn pagination_options(&self) -> PaginationOptions {
+        PaginationOptions {
+            page: self.page,
+            page_size: self.page_size,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Validate, ValidatedExtractedQuery)]
+#[serde(rename_all = "camelCase")]
+pub struct GetSeriesQuery {
+    pub use_slug: Option<bool>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, Validate, ValidatedExtractedQuery)]
 #[serde(rename_all = "camelCase")]
 pub struct GetAllUsersQuery {
@@ -213,6 +239,81 @@ pub struct GetPageViewsQuery {
     pub distinct: Option<bool>,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, Validate, ValidatedExtractedQuery)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAllNewsletterSubscribersQuery {
+    pub page: Option<i64>,
+    pub page_size: Option<i64>,
+    pub confirmed: Option<bool>,
+    pub unsubscribed: Option<bool>,
+    pub sort_type: Option<NewsletterSubscriberSortType>,
+}
+
+impl PaginatedQuery for GetAllNewsletterSubscribersQuery {
+    fn pagination_options(&self) -> PaginationOptions {
+        PaginationOptions {
+            page: self.page,
+            page_size: self.page_size,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Validate, ValidatedExtractedQuery)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAllNewsletterIssuesQuery {
+    pub page: Option<i64>,
+    pub page_size: Option<i64>,
+    pub sent: Option<bool>,
+    pub sort_type: Option<NewsletterIssueSortType>,
+}
+
+impl PaginatedQuery for GetAllNewsletterIssuesQuery {
+    fn pagination_options(&self) -> PaginationOptions {
+        PaginationOptions {
+            page: self.page,
+            page_size: self.page_size,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Validate, ValidatedExtractedQuery)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAllNewsletterDeliveriesQuery {
+    pub page: Option<i64>,
+    pub page_size: Option<i64>,
+    pub status: Option<EmailStatus>,
+}
+
+impl PaginatedQuery for GetAllNewsletterDeliveriesQuery {
+    fn pagination_options(&self) -> PaginationOptions {
+        PaginationOptions {
+            page: self.page,
+            page_size: self.page_size,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Validate, ValidatedExtractedQuery)]
+#[serde(rename_all = "camelCase")]
+pub struct UnsubscribeQuery {
+    #[validate(length(min = 1, max = 5000))]
+    pub token: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Validate, ValidatedExtractedQuery)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfirmNewsletterSubscriptionQuery {
+    #[validate(length(min = 1, max = 5000))]
+    pub token: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Validate, ValidatedExtractedQuery)]
+#[serde(rename_all = "camelCase")]
+pub struct PreviewEmailTemplateQuery {
+    #[validate(length(min = 2, max = 16))]
+    pub locale: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, Validate, ValidatedExtractedQuery)]
 #[serde(rename_all = "camelCase")]
 pub struct GetAllBlogPostCommentRatingsQuery {
@@ -282,6 +383,24 @@ impl PaginatedQuery for GetAllHomePageLinksQuery {
     }
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, Validate, ValidatedExtractedQuery)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTechnologyQuery {
+    pub use_slug: Option<bool>,
+    /// Paginates the published projects listed with the technology.
+    pub page: Option<i64>,
+    pub page_size: Option<i64>,
+}
+
+impl PaginatedQuery for GetTechnologyQuery {
+    fn pagination_options(&self) -> PaginationOptions {
+        PaginationOptions {
+            page: self.page,
+            page_size: self.page_size,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, Validate, ValidatedExtractedQuery)]
 #[serde(rename_all = "camelCase")]
 pub struct GetAllTechnologiesQuery {
@@ -299,6 +418,24 @@ impl PaginatedQuery for GetAllTechnologiesQuery {
     }
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, Validate, ValidatedExtractedQuery)]
+#[serde(rename_all = "camelCase")]
+pub struct GetCategoryQuery {
+    pub use_slug: Option<bool>,
+    /// Paginates the published posts listed with the category.
+    pub page: Option<i64>,
+    pub page_size: Option<i64>,
+}
+
+impl PaginatedQuery for GetCategoryQuery {
+    fn pagination_options(&self) -> PaginationOptions {
+        PaginationOptions {
+            page: self.page,
+            page_size: self.page_size,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, Validate, ValidatedExtractedQuery)]
 #[serde(rename_all = "camelCase")]
 pub struct GetAllCategoriesQuery {
@@ -340,6 +477,63 @@ pub struct GetAllSearchItemsQuery {
     pub page_size: Option<i64>,
     pub search_text: Option<String>,
     pub r#type: Option<SearchItemType>,
+    /// Also match words starting with the last term, for search as you type.
+    pub prefix: Option<bool>,
+    pub category: Option<String>,
+    pub technology: Option<String>,
+    pub created_from: Option<NaiveDate>,
+    pub created_to: Option<NaiveDate>,
+    /// Stems the search text for this language instead of detecting it.
+    pub language: Option<ContentLanguage>,
+    pub sort_type: Option<SearchItemsSortType>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Validate, ValidatedExtractedQuery)]
+#[serde(rename_all = "camelCase")]
+pub struct GetSearchSuggestionsQuery {
+    #[validate(length(min = 1, max = 200))]
+    pub search_text: String,
+    pub r#type: Option<SearchItemType>,
+    #[validate(range(min = 1, max = 20))]
+    pub limit: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Validate, ValidatedExtractedQuery)]
+#[serde(rename_all = "camelCase")]
+pub struct GetRelatedItemsQuery {
+    #[validate(range(min = 1, max = 10))]
+    pub limit: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Validate, ValidatedExtractedQuery)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTrendingQuery {
+    pub r#type: Option<SearchItemType>,
+    #[validate(range(min = 1, max = 20))]
+    pub limit: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Validate, ValidatedExtractedQuery)]
+#[serde(rename_all = "camelCase")]
+pub struct GetSearchAnalyticsQuery {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<i64>,
+    pub interval: Option<ReportInterval>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Validate, ValidatedExtractedQuery)]
+#[serde(rename_all = "camelCase")]
+pub struct GetPageViewAnalyticsQuery {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<i64>,
+    pub interval: Option<ReportInterval>,
+    pub referrer_type: Option<ReferrerType>,
+    /// Bots are left out unless this is set.
+    pub include_bots: Option<bool>,
 }
 
 impl PaginatedQuery for GetAllSearchItemsQuery {
@@ -350,3 +544,59 @@ impl PaginatedQuery for GetAllSearchItemsQuery {
         }
     }
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, Validate, ValidatedExtractedQuery)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAllApiTokensQuery {
+    pub page: Option<i64>,
+    pub page_size: Option<i64>,
+    pub revoked: Option<bool>,
+    pub sort_type: Option<ApiTokenSortType>,
+}
+
+impl PaginatedQuery for GetAllApiTokensQuery {
+    fn pagination_options(&self) -> PaginationOptions {
+        PaginationOptions {
+            page: self.page,
+            page_size: self.page_size,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Validate, ValidatedExtractedQuery)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAllOutboxEmailsQuery {
+    pub page: Option<i64>,
+    pub page_size: Option<i64>,
+    pub status: Option<EmailStatus>,
+    pub sort_type: Option<OutboxEmailSortType>,
+}
+
+impl PaginatedQuery for GetAllOutboxEmailsQuery {
+    fn pagination_options(&self) -> PaginationOptions {
+        PaginationOptions {
+            page: self.page,
+            page_size: self.page_size,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Validate, ValidatedExtractedQuery)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAllContactMessagesQuery {
+    pub page: Option<i64>,
+    pub page_size: Option<i64>,
+    pub read: Option<bool>,
+    pub archived: Option<bool>,
+    pub spam: Option<bool>,
+    pub sort_type: Option<ContactMessageSortType>,
+}
+
+impl PaginatedQuery for GetAllContactMessagesQuery {
+    fn pagination_options(&self) -> PaginationOptions {
+        PaginationOptions {
+            page: self.page,
+            page_size: self.page_size,
+        }
+    }
+}
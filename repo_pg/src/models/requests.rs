@@ -1,3 +1,4 @@
+use crate::extra::{ApiTokenScope, ContentLanguage};
 use regex::Regex;
 use serde::{Deserialize, Deserializer, Serialize};
 
@@ -10,6 +11,10 @@ lazy_static! {
     static ref HAS_NO_SPECIAL_CHAR: Regex =
         Regex::new("^[^!@#$%^&*()_+\\-=\\[\\]{};':\"\\|,.<>/?]+$").unwrap();
     static ref HAS_NO_SPACE_PREFFIX_OR_SUFFIX: Regex = Regex::new("^[^ ]+.*[^ ]+$").unwrap();
+    static ref HEX_COLOR: Regex = Regex::new("^#[0-9a-fA-F]{6}$").unwrap();
+    /// What the `slugify()` SQL function makes: lower case letters, in any script,
+    /// and digits, in words joined by single hyphens.
+    static ref SLUG: Regex = Regex::new(r"^[\p{Ll}\p{N}]+(-[\p{Ll}\p{N}]+)*$").unwrap();
 }
 
 // Any value that is present is considered Some value, including null.
@@ -41,6 +46,7 @@ pub struct UpdateBlogPostRequest {
     pub description: Option<Option<String>>,
     #[validate(length(min = 1, max = 200))]
     pub slug: Option<String>,
+    pub language: Option<ContentLanguage>,
 }
 
 #[derive(Serialize, Deserialize, Validate, Clone, Debug)]
@@ -59,12 +65,29 @@ pub struct UpdateProjectRequest {
     pub published: Option<bool>,
     #[validate(length(min = 1, max = 200))]
     pub slug: Option<String>,
+    pub language: Option<ContentLanguage>,
+}
+
+#[derive(Serialize, Deserialize, Validate, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateSeriesRequest {
+    #[validate(length(min = 1, max = 200))]
+    pub title: Option<String>,
+    #[validate(length(min = 1, max = 200))]
+    pub slug: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub description: Option<Option<String>>,
+    pub published: Option<bool>,
+    pub language: Option<ContentLanguage>,
+    /// Replaces the posts of the series, in this order.
+    pub blog_post_ids: Option<Vec<i32>>,
 }
 
 #[derive(Serialize, Deserialize, Validate, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateBlogPostCommentRequest {
     pub post_id: i32,
+    pub parent_id: Option<i32>,
     #[validate(length(min = 1, max = 2500))]
     pub body: String,
 }
@@ -79,6 +102,8 @@ pub struct CreateBlogPostRequest {
     pub categories: Vec<String>,
     pub description: Option<String>,
     pub slug: String,
+    /// Detected from the body when left out.
+    pub language: Option<ContentLanguage>,
 }
 
 #[derive(Serialize, Deserialize, Validate, Clone, Debug)]
@@ -92,7 +117,24 @@ pub struct CreateProjectRequest {
     pub cover_image: Option<String>,
     pub name: String,
     pub slug: String,
+    /// Detected from the body when left out.
+    pub language: Option<ContentLanguage>,
+}
+#[derive(Serialize, Deserialize, Validate, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSeriesRequest {
+    #[validate(length(min = 1, max = 200))]
+    pub title: String,
+    #[validate(length(min = 1, max = 200))]
+    pub slug: String,
+    pub description: Option<String>,
+    /// Detected from the title and description when left out.
+    pub language: Option<ContentLanguage>,
+    /// The posts of the series, in order. A post already in another series is moved.
+    #[serde(default)]
+    pub blog_post_ids: Vec<i32>,
 }
+
 #[derive(Serialize, Deserialize, Validate, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct LoginRequest {
@@ -144,6 +186,8 @@ pub struct RegisterRequest {
     #[validate(regex(path = "HAS_SPECIAL_CHAR", message = "requires a special character"))]
     #[validate(length(min = 6, max = 35))]
     pub password: String,
+    #[validate(length(min = 1, max = 5000))]
+    pub captcha_token: String,
 }
 
 #[derive(Serialize, Deserialize, Validate, Clone)]
@@ -191,13 +235,45 @@ pub struct UpdateHomePageLinkRequest {
 pub struct CreateTechnologyRequest {
     #[validate(length(min = 1, max = 55))]
     pub name: String,
+    /// Made from the name when left out.
+    #[validate(regex(
+        path = "SLUG",
+        message = "can only have lower case letters, digits and single hyphens between them"
+    ))]
+    #[validate(length(min = 1, max = 55))]
+    pub slug: Option<String>,
+    #[validate(length(min = 1, max = 500))]
+    pub description: Option<String>,
+    #[validate(length(min = 1, max = 200))]
+    pub icon: Option<String>,
+    #[validate(regex(path = "HEX_COLOR", message = "must be a color like #1a2b3c"))]
+    pub color: Option<String>,
+    pub parent_id: Option<i32>,
 }
 
 #[derive(Serialize, Deserialize, Validate, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateTechnologyRequest {
+    /// Renaming keeps the slug unless a new one is given.
     #[validate(length(min = 1, max = 55))]
     pub name: Option<String>,
+    #[validate(regex(
+        path = "SLUG",
+        message = "can only have lower case letters, digits and single hyphens between them"
+    ))]
+    #[validate(length(min = 1, max = 55))]
+    pub slug: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    #[validate(length(min = 1, max = 500))]
+    pub description: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    #[validate(length(min = 1, max = 200))]
+    pub icon: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    #[validate(regex(path = "HEX_COLOR", message = "must be a color like #1a2b3c"))]
+    pub color: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub parent_id: Option<Option<i32>>,
 }
 
 #[derive(Serialize, Deserialize, Validate, Clone)]
@@ -205,13 +281,45 @@ pub struct UpdateTechnologyRequest {
 pub struct CreateCategoryRequest {
     #[validate(length(min = 1, max = 55))]
     pub name: String,
+    /// Made from the name when left out.
+    #[validate(regex(
+        path = "SLUG",
+        message = "can only have lower case letters, digits and single hyphens between them"
+    ))]
+    #[validate(length(min = 1, max = 55))]
+    pub slug: Option<String>,
+    #[validate(length(min = 1, max = 500))]
+    pub description: Option<String>,
+    #[validate(length(min = 1, max = 200))]
+    pub icon: Option<String>,
+    #[validate(regex(path = "HEX_COLOR", message = "must be a color like #1a2b3c"))]
+    pub color: Option<String>,
+    pub parent_id: Option<i32>,
 }
 
 #[derive(Serialize, Deserialize, Validate, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateCategoryRequest {
+    /// Renaming keeps the slug unless a new one is given.
     #[validate(length(min = 1, max = 55))]
     pub name: Option<String>,
+    #[validate(regex(
+        path = "SLUG",
+        message = "can only have lower case letters, digits and single hyphens between them"
+    ))]
+    #[validate(length(min = 1, max = 55))]
+    pub slug: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    #[validate(length(min = 1, max = 500))]
+    pub description: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    #[validate(length(min = 1, max = 200))]
+    pub icon: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    #[validate(regex(path = "HEX_COLOR", message = "must be a color like #1a2b3c"))]
+    pub color: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub parent_id: Option<Option<i32>>,
 }
 
 #[derive(Serialize, Deserialize, Validate, Clone)]
@@ -221,6 +329,8 @@ pub struct CreateTextBodyRequest {
     pub slug: String,
     pub body: String,
     pub url_used: Option<String>,
+    /// Detected from the body when left out.
+    pub language: Option<ContentLanguage>,
 }
 
 #[derive(Serialize, Deserialize, Validate, Clone)]
@@ -232,12 +342,16 @@ pub struct UpdateTextBodyRequest {
     pub body: Option<String>,
     #[serde(default, deserialize_with = "deserialize_some")]
     pub url_used: Option<Option<String>>,
+    pub language: Option<ContentLanguage>,
 }
 
 #[derive(Serialize, Deserialize, Validate, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct CreatePageViewRequest {
+    /// UTM parameters in its query are recorded with the view and left out of the url.
     pub page_url: String,
+    #[validate(length(max = 2048))]
+    pub referrer: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Validate, Clone)]
@@ -251,6 +365,8 @@ pub struct SendContactEmailRequest {
     pub body: String,
     #[validate(length(min = 1, max = 5000))]
     pub captcha_token: String,
+    /// Hidden from people by the client, so anything in it came from a bot.
+    pub website: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Validate, Clone)]
@@ -269,11 +385,65 @@ pub struct VerifyEmailRequest {
     pub token: String,
 }
 
+#[derive(Serialize, Deserialize, Validate, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateProfileRequest {
+    #[validate(regex(
+        path = "HAS_NO_SPECIAL_CHAR",
+        message = "not allowed to have a special character"
+    ))]
+    #[validate(regex(
+        path = "HAS_NO_SPACE_PREFFIX_OR_SUFFIX",
+        message = "not allowed to start or end with whitespace"
+    ))]
+    #[validate(length(min = 3, max = 25))]
+    pub display_name: String,
+}
+
+#[derive(Serialize, Deserialize, Validate, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangePasswordRequest {
+    #[validate(length(min = 1, max = 5000))]
+    pub current_password: String,
+    #[validate(regex(path = "HAS_UPPER_CASE", message = "requires an upper case character"))]
+    #[validate(regex(path = "HAS_LOWER_CASE", message = "requires a lower case character"))]
+    #[validate(regex(path = "HAS_DIGIT", message = "requires a numeric character"))]
+    #[validate(regex(path = "HAS_SPECIAL_CHAR", message = "requires a special character"))]
+    #[validate(length(min = 6, max = 35))]
+    pub new_password: String,
+}
+
+#[derive(Serialize, Deserialize, Validate, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteAccountRequest {
+    #[validate(length(min = 1, max = 5000))]
+    pub password: String,
+    /// Deletes the comments instead of keeping them under the tombstone user.
+    #[serde(default)]
+    pub delete_comments: bool,
+}
+
+#[derive(Serialize, Deserialize, Validate, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeEmailRequest {
+    #[validate(email)]
+    pub email: String,
+}
+
+#[derive(Serialize, Deserialize, Validate, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UndoEmailChangeRequest {
+    #[validate(length(min = 1, max = 5000))]
+    pub token: String,
+}
+
 #[derive(Serialize, Deserialize, Validate, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct RequestResetPasswordEmailRequest {
     #[validate(email)]
     pub email: String,
+    #[validate(length(min = 1, max = 5000))]
+    pub captcha_token: String,
 }
 
 #[derive(Serialize, Deserialize, Validate, Clone)]
@@ -284,3 +454,103 @@ pub struct ResetPasswordRequest {
     #[validate(length(min = 1, max = 5000))]
     pub new_password: String,
 }
+
+#[derive(Serialize, Deserialize, Validate, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateApiTokenRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    #[validate(length(min = 1))]
+    pub scopes: Vec<ApiTokenScope>,
+    #[validate(range(min = 1, max = 3650))]
+    pub expires_in_days: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Validate, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateNotificationPreferencesRequest {
+    pub comment_replies: Option<bool>,
+    pub moderation_decisions: Option<bool>,
+    pub new_posts_digest: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Validate, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SubscribeNewsletterRequest {
+    #[validate(email)]
+    pub email: String,
+    #[validate(length(min = 1, max = 5000))]
+    pub captcha_token: String,
+}
+
+#[derive(Serialize, Deserialize, Validate, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateNewsletterIssueRequest {
+    #[validate(length(min = 1, max = 200))]
+    pub subject: String,
+    #[validate(length(max = 10000))]
+    pub intro: String,
+    pub blog_post_ids: Option<Vec<i32>>,
+}
+
+#[derive(Serialize, Deserialize, Validate, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateNewsletterIssueRequest {
+    #[validate(length(min = 1, max = 200))]
+    pub subject: Option<String>,
+    #[validate(length(max = 10000))]
+    pub intro: Option<String>,
+    pub blog_post_ids: Option<Vec<i32>>,
+}
+
+#[derive(Serialize, Deserialize, Validate, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateContactMessageRequest {
+    pub read: Option<bool>,
+    pub archived: Option<bool>,
+    pub spam: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Validate, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplyContactMessageRequest {
+    #[validate(length(min = 1, max = 10000))]
+    pub body: String,
+}
+
+#[derive(Serialize, Deserialize, Validate, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSearchClickRequest {
+    pub search_id: i32,
+    #[validate(length(min = 1, max = 500))]
+    pub link: String,
+    #[validate(range(min = 1))]
+    pub position: Option<i32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use validator::Validate;
+
+    fn category_with_slug(slug: &str) -> CreateCategoryRequest {
+        CreateCategoryRequest {
+            name: String::from("Category"),
+            slug: Some(slug.to_string()),
+            description: None,
+            icon: None,
+            color: None,
+            parent_id: None,
+        }
+    }
+
+    #[test]
+    fn slugs_must_look_like_slugify_made_them() {
+        for slug in ["rust", "async-rust", "web3", "ελληνικά-κείμενα"] {
+            assert!(category_with_slug(slug).validate().is_ok(), "{}", slug);
+        }
+        for slug in ["Rust", "two words", "a/b", "-rust", "rust-", "async--rust"] {
+            assert!(category_with_slug(slug).validate().is_err(), "{}", slug);
+        }
+    }
+}
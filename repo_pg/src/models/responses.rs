@@ -1,4 +1,5 @@
 use crate::extra::UserRole;
+use crate::models::domain::{ApiToken, BlogPostComment, BlogPostCommentRating, SearchFacets, User};
 use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 
@@ -23,6 +24,18 @@ pub struct BaseResponse<T: Serialize> {
     pub messages: Option<Vec<String>>,
 }
 
+/// A page of search results, with counts for narrowing them down further.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResponse<T: Serialize> {
+    #[serde(flatten)]
+    pub base: BaseResponse<T>,
+    pub facets: SearchFacets,
+    /// Identifies the logged search when reporting which result was followed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_id: Option<i32>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct Pagination {
@@ -66,14 +79,22 @@ pub struct HealthResponse {
     pub db_response_time: Option<String>,
     pub api_up: bool,
     pub api_response_time: Option<String>,
+    /// Whether the search refresh state could be read.
+    pub search_up: bool,
+    pub search_refreshed_at: Option<NaiveDateTime>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
-pub struct GeolocationDbResponse {
-    pub country_code: String,
-    pub country_name: String,
-    pub city: String,
-    pub postal: String,
-    pub latitude: String,
-    pub state: String,
+#[serde(rename_all = "camelCase")]
+pub struct CreatedApiTokenResponse {
+    pub token: String,
+    pub api_token: ApiToken,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UserDataExportResponse {
+    pub profile: User,
+    pub comments: Vec<BlogPostComment>,
+    pub ratings: Vec<BlogPostCommentRating>,
 }
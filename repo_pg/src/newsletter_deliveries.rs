@@ -0,0 +1,165 @@
+use crate::extra::EmailStatus;
+use crate::filters::GetAllNewsletterDeliveriesFilter;
+use crate::models::{db_models, domain};
+use crate::options::PaginationOptions;
+use crate::schema::{email_outbox, newsletter_deliveries, newsletter_subscribers};
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::{QueryDsl, RunQueryDsl};
+
+pub struct NewsletterDeliveryRepo<'a> {
+    conn: &'a crate::pg_util::RepoConnection,
+}
+
+impl<'a> NewsletterDeliveryRepo<'a> {
+    pub fn new(conn: &'a crate::pg_util::RepoConnection) -> Self {
+        Self { conn }
+    }
+
+    /// Creates a pending delivery of an issue for every confirmed subscriber that
+    /// has not unsubscribed, and returns how many were created.
+    pub fn insert_for_active_subscribers(
+        &self,
+        issue_id: i32,
+    ) -> Result<usize, diesel::result::Error> {
+        let conn = &self.conn.pg_conn;
+        let subscribers = newsletter_subscribers::table
+            .filter(newsletter_subscribers::confirmed.eq(true))
+            .filter(newsletter_subscribers::unsubscribed_at.is_null())
+            .select((
+                issue_id.into_sql::<diesel::sql_types::Int4>(),
+                newsletter_subscribers::id,
+            ));
+        diesel::insert_into(newsletter_deliveries::table)
+            .values(subscribers)
+            .into_columns((
+                newsletter_deliveries::newsletter_issue_id,
+                newsletter_deliveries::newsletter_subscriber_id,
+            ))
+            .on_conflict_do_nothing()
+            .execute(conn)
+    }
+
+    pub fn set_email(
+        &self,
+        id_value: i32,
+        email_outbox_id: i32,
+    ) -> Result<usize, diesel::result::Error> {
+        let conn = &self.conn.pg_conn;
+        diesel::update(newsletter_deliveries::table.filter(newsletter_deliveries::id.eq(id_value)))
+            .set(newsletter_deliveries::email_outbox_id.eq(Some(email_outbox_id)))
+            .execute(conn)
+    }
+
+    /// Marks a delivery that could not be queued, so it is not picked up again.
+    pub fn set_failed(&self, id_value: i32, error: String) -> Result<usize, diesel::result::Error> {
+        let conn = &self.conn.pg_conn;
+        diesel::update(newsletter_deliveries::table.filter(newsletter_deliveries::id.eq(id_value)))
+            .set((
+                newsletter_deliveries::failed_at.eq(Some(Utc::now().naive_utc())),
+                newsletter_deliveries::last_error.eq(Some(error)),
+            ))
+            .execute(conn)
+    }
+
+    pub fn delete_one(&self, id_value: i32) -> Result<usize, diesel::result::Error> {
+        let conn = &self.conn.pg_conn;
+        diesel::delete(newsletter_deliveries::table.filter(newsletter_deliveries::id.eq(id_value)))
+            .execute(conn)
+    }
+
+    /// Locks deliveries that have not been queued or failed yet, skipping the ones
+    /// another worker holds. Has to run inside a transaction for the locks to mean anything.
+    pub fn find_unqueued(
+        &self,
+        limit: i64,
+    ) -> Result<
+        Vec<(
+            db_models::NewsletterDelivery,
+            db_models::NewsletterSubscriber,
+        )>,
+        diesel::result::Error,
+    > {
+        let conn = &self.conn.pg_conn;
+        newsletter_deliveries::table
+            .inner_join(newsletter_subscribers::table)
+            .filter(newsletter_deliveries::email_outbox_id.is_null())
+            .filter(newsletter_deliveries::failed_at.is_null())
+            .order(newsletter_deliveries::id.asc())
+            .limit(limit)
+            .select((
+                newsletter_deliveries::all_columns,
+                newsletter_subscribers::all_columns,
+            ))
+            .for_update()
+            .skip_locked()
+            .load(conn)
+    }
+
+    pub fn find(
+        &self,
+        filter: GetAllNewsletterDeliveriesFilter,
+        pagination: PaginationOptions,
+    ) -> Result<(Vec<domain::NewsletterDelivery>, i64), diesel::result::Error> {
+        let q = newsletter_deliveries::table
+            .inner_join(newsletter_subscribers::table)
+            .left_join(email_outbox::table)
+            .select((
+                newsletter_deliveries::all_columns,
+                newsletter_subscribers::all_columns,
+                email_outbox::all_columns.nullable(),
+                diesel::dsl::sql::<diesel::sql_types::BigInt>("count(*) over()"),
+            ))
+            .into_boxed();
+
+        let q = if let Some(issue_id) = filter.newsletter_issue_id {
+            q.filter(newsletter_deliveries::newsletter_issue_id.eq(issue_id))
+        } else {
+            q
+        };
+
+        let q = match filter.status {
+            Some(EmailStatus::Pending) => q.filter(
+                newsletter_deliveries::email_outbox_id
+                    .is_null()
+                    .and(newsletter_deliveries::failed_at.is_null())
+                    .or(email_outbox::status.eq(EmailStatus::Pending)),
+            ),
+            Some(EmailStatus::Failed) => q.filter(
+                newsletter_deliveries::failed_at
+                    .is_not_null()
+                    .or(email_outbox::status.eq(EmailStatus::Failed)),
+            ),
+            Some(status) => q.filter(email_outbox::status.eq(status)),
+            None => q,
+        };
+
+        let q = q.order(newsletter_deliveries::id.asc());
+
+        let q = if let (Some(page), Some(page_size)) = (pagination.page, pagination.page_size) {
+            q.offset((page - 1) * page_size).limit(page_size)
+        } else {
+            q
+        };
+
+        let conn = &self.conn.pg_conn;
+        let results: Vec<(
+            db_models::NewsletterDelivery,
+            db_models::NewsletterSubscriber,
+            Option<db_models::OutboxEmail>,
+            i64,
+        )> = q.load(conn)?;
+
+        let count = match results.first() {
+            Some((_, _, _, value)) => *value,
+            None => 0,
+        };
+        let deliveries_list = results
+            .into_iter()
+            .map(|(delivery, subscriber, email, _)| {
+                domain::NewsletterDelivery::from(delivery, subscriber, email)
+            })
+            .collect::<Vec<_>>();
+        Ok((deliveries_list, count))
+    }
+}
@@ -0,0 +1,133 @@
+use crate::filters::GetAllNewsletterIssuesFilter;
+use crate::models::{db_models, domain};
+use crate::options::{NewsletterIssueSortType, PaginationOptions};
+use crate::schema::newsletter_issues;
+use crate::{change_sets::UpdateNewsletterIssue, insertables::NewNewsletterIssue};
+use diesel::prelude::*;
+use diesel::{QueryDsl, RunQueryDsl};
+
+pub struct NewsletterIssueRepo<'a> {
+    conn: &'a crate::pg_util::RepoConnection,
+}
+
+impl<'a> NewsletterIssueRepo<'a> {
+    pub fn new(conn: &'a crate::pg_util::RepoConnection) -> Self {
+        Self { conn }
+    }
+
+    pub fn insert_one(&self, new_issue: NewNewsletterIssue) -> Result<i32, diesel::result::Error> {
+        let conn = &self.conn.pg_conn;
+        let query = diesel::insert_into(newsletter_issues::table)
+            .values(&new_issue)
+            .returning(newsletter_issues::id);
+        query.get_result(conn)
+    }
+
+    pub fn update_one(
+        &self,
+        id_value: i32,
+        updated_issue: UpdateNewsletterIssue,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::newsletter_issues::dsl::{id, newsletter_issues};
+        let conn = &self.conn.pg_conn;
+        let query = diesel::update(newsletter_issues.filter(id.eq(id_value))).set(&updated_issue);
+        query.execute(conn)
+    }
+
+    pub fn find_one(
+        &self,
+        id_value: i32,
+    ) -> Result<Option<domain::NewsletterIssue>, diesel::result::Error> {
+        use crate::schema::newsletter_issues::dsl::{id, newsletter_issues};
+        use crate::schema::users::dsl::users;
+
+        let conn = &self.conn.pg_conn;
+        let query = newsletter_issues
+            .filter(id.eq(id_value))
+            .inner_join(users)
+            .select((newsletter_issues::all_columns(), users::all_columns()));
+        let (issue, user): (db_models::NewsletterIssue, db_models::User) =
+            match query.first(conn).optional()? {
+                Some(value) => value,
+                None => return Ok(None),
+            };
+        Ok(Some(domain::NewsletterIssue::from(issue, user)))
+    }
+
+    /// The issue that went out most recently, if any.
+    pub fn find_last_sent(&self) -> Result<Option<domain::NewsletterIssue>, diesel::result::Error> {
+        use crate::schema::newsletter_issues::dsl::{newsletter_issues, sent_at};
+        use crate::schema::users::dsl::users;
+
+        let conn = &self.conn.pg_conn;
+        let query = newsletter_issues
+            .filter(sent_at.is_not_null())
+            .order(sent_at.desc())
+            .inner_join(users)
+            .select((newsletter_issues::all_columns(), users::all_columns()));
+        let (issue, user): (db_models::NewsletterIssue, db_models::User) =
+            match query.first(conn).optional()? {
+                Some(value) => value,
+                None => return Ok(None),
+            };
+        Ok(Some(domain::NewsletterIssue::from(issue, user)))
+    }
+
+    pub fn find(
+        &self,
+        filter: GetAllNewsletterIssuesFilter,
+        sort: Option<NewsletterIssueSortType>,
+        pagination: PaginationOptions,
+    ) -> Result<(Vec<domain::NewsletterIssue>, i64), diesel::result::Error> {
+        use crate::schema::newsletter_issues::dsl::{
+            newsletter_issues as newsletter_issues_dsl, sent_at,
+        };
+        use crate::schema::users::dsl::users;
+        let q = newsletter_issues_dsl
+            .inner_join(users)
+            .select((
+                newsletter_issues_dsl::all_columns(),
+                users::all_columns(),
+                diesel::dsl::sql::<diesel::sql_types::BigInt>("count(*) over()"),
+            ))
+            .into_boxed();
+
+        let q = match filter.sent {
+            Some(true) => q.filter(sent_at.is_not_null()),
+            Some(false) => q.filter(sent_at.is_null()),
+            None => q,
+        };
+
+        let q = if let Some(sort_type) = sort {
+            match sort_type {
+                NewsletterIssueSortType::CreatedAtAsc => {
+                    q.order(newsletter_issues::created_at.asc())
+                }
+                NewsletterIssueSortType::CreatedAtDesc => {
+                    q.order(newsletter_issues::created_at.desc())
+                }
+            }
+        } else {
+            q.order(newsletter_issues::created_at.desc())
+        };
+
+        let q = if let (Some(page), Some(page_size)) = (pagination.page, pagination.page_size) {
+            q.offset((page - 1) * page_size).limit(page_size)
+        } else {
+            q
+        };
+
+        let conn = &self.conn.pg_conn;
+        let results: Vec<(db_models::NewsletterIssue, db_models::User, i64)> = q.load(conn)?;
+
+        let count = match results.first() {
+            Some((_, _, value)) => *value,
+            None => 0,
+        };
+        let issues_list = results
+            .into_iter()
+            .map(|(issue, user, _)| domain::NewsletterIssue::from(issue, user))
+            .collect::<Vec<_>>();
+        Ok((issues_list, count))
+    }
+}
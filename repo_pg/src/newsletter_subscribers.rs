@@ -0,0 +1,151 @@
+use crate::filters::GetAllNewsletterSubscribersFilter;
+use crate::models::{db_models, domain};
+use crate::options::{NewsletterSubscriberSortType, PaginationOptions};
+use crate::schema::newsletter_subscribers;
+use crate::{change_sets::UpdateNewsletterSubscriber, insertables::NewNewsletterSubscriber};
+use diesel::prelude::*;
+use diesel::{QueryDsl, RunQueryDsl};
+
+pub struct NewsletterSubscriberRepo<'a> {
+    conn: &'a crate::pg_util::RepoConnection,
+}
+
+impl<'a> NewsletterSubscriberRepo<'a> {
+    pub fn new(conn: &'a crate::pg_util::RepoConnection) -> Self {
+        Self { conn }
+    }
+
+    pub fn insert_one(
+        &self,
+        new_subscriber: NewNewsletterSubscriber,
+    ) -> Result<db_models::NewsletterSubscriber, diesel::result::Error> {
+        let conn = &self.conn.pg_conn;
+        let query = diesel::insert_into(newsletter_subscribers::table).values(&new_subscriber);
+        query.get_result(conn)
+    }
+
+    pub fn update_one(
+        &self,
+        id_value: i32,
+        updated_subscriber: UpdateNewsletterSubscriber,
+    ) -> Result<db_models::NewsletterSubscriber, diesel::result::Error> {
+        use crate::schema::newsletter_subscribers::dsl::{id, newsletter_subscribers};
+        let conn = &self.conn.pg_conn;
+        let query =
+            diesel::update(newsletter_subscribers.filter(id.eq(id_value))).set(&updated_subscriber);
+        query.get_result(conn)
+    }
+
+    pub fn find_one(
+        &self,
+        id_value: i32,
+    ) -> Result<Option<domain::NewsletterSubscriber>, diesel::result::Error> {
+        use crate::schema::newsletter_subscribers::dsl::{id, newsletter_subscribers};
+
+        let conn = &self.conn.pg_conn;
+        let query = newsletter_subscribers
+            .filter(id.eq(id_value))
+            .select(newsletter_subscribers::all_columns());
+        let subscriber: db_models::NewsletterSubscriber = match query.first(conn).optional()? {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+        Ok(Some(domain::NewsletterSubscriber::from(subscriber)))
+    }
+
+    /// Includes the token, which is never sent to clients.
+    pub fn find_one_by_email(
+        &self,
+        email_value: &str,
+    ) -> Result<Option<db_models::NewsletterSubscriber>, diesel::result::Error> {
+        use crate::schema::newsletter_subscribers::dsl::{email, newsletter_subscribers};
+
+        let conn = &self.conn.pg_conn;
+        newsletter_subscribers
+            .filter(email.eq(email_value.to_lowercase()))
+            .select(newsletter_subscribers::all_columns())
+            .first(conn)
+            .optional()
+    }
+
+    pub fn find_one_by_token(
+        &self,
+        token_value: &str,
+    ) -> Result<Option<db_models::NewsletterSubscriber>, diesel::result::Error> {
+        use crate::schema::newsletter_subscribers::dsl::{newsletter_subscribers, token};
+
+        let conn = &self.conn.pg_conn;
+        newsletter_subscribers
+            .filter(token.eq(token_value))
+            .select(newsletter_subscribers::all_columns())
+            .first(conn)
+            .optional()
+    }
+
+    pub fn find(
+        &self,
+        filter: GetAllNewsletterSubscribersFilter,
+        sort: Option<NewsletterSubscriberSortType>,
+        pagination: PaginationOptions,
+    ) -> Result<(Vec<domain::NewsletterSubscriber>, i64), diesel::result::Error> {
+        use crate::schema::newsletter_subscribers::dsl::{
+            confirmed, newsletter_subscribers as newsletter_subscribers_dsl, unsubscribed_at,
+        };
+        let q = newsletter_subscribers_dsl
+            .select((
+                newsletter_subscribers_dsl::all_columns(),
+                diesel::dsl::sql::<diesel::sql_types::BigInt>("count(*) over()"),
+            ))
+            .into_boxed();
+
+        let q = if let Some(confirmed_filter) = filter.confirmed {
+            q.filter(confirmed.eq(confirmed_filter))
+        } else {
+            q
+        };
+
+        let q = match filter.unsubscribed {
+            Some(true) => q.filter(unsubscribed_at.is_not_null()),
+            Some(false) => q.filter(unsubscribed_at.is_null()),
+            None => q,
+        };
+
+        let q = if let Some(sort_type) = sort {
+            match sort_type {
+                NewsletterSubscriberSortType::CreatedAtAsc => {
+                    q.order(newsletter_subscribers::created_at.asc())
+                }
+                NewsletterSubscriberSortType::CreatedAtDesc => {
+                    q.order(newsletter_subscribers::created_at.desc())
+                }
+                NewsletterSubscriberSortType::EmailAsc => {
+                    q.order(newsletter_subscribers::email.asc())
+                }
+                NewsletterSubscriberSortType::EmailDesc => {
+                    q.order(newsletter_subscribers::email.desc())
+                }
+            }
+        } else {
+            q.order(newsletter_subscribers::created_at.desc())
+        };
+
+        let q = if let (Some(page), Some(page_size)) = (pagination.page, pagination.page_size) {
+            q.offset((page - 1) * page_size).limit(page_size)
+        } else {
+            q
+        };
+
+        let conn = &self.conn.pg_conn;
+        let results: Vec<(db_models::NewsletterSubscriber, i64)> = q.load(conn)?;
+
+        let count = match results.first() {
+            Some((_, value)) => *value,
+            None => 0,
+        };
+        let subscribers_list = results
+            .into_iter()
+            .map(|(subscriber, _)| domain::NewsletterSubscriber::from(subscriber))
+            .collect::<Vec<_>>();
+        Ok((subscribers_list, count))
+    }
+}
@@ -0,0 +1,143 @@
+use crate::models::db_models;
+use crate::models::domain::{self, NotificationKind};
+use crate::schema::notification_preferences;
+use crate::{change_sets::UpdateNotificationPreferences, insertables::NewNotificationPreferences};
+use chrono::{NaiveDateTime, Utc};
+use diesel::pg::upsert::excluded;
+use diesel::prelude::*;
+use diesel::{QueryDsl, RunQueryDsl};
+
+pub struct NotificationPreferencesRepo<'a> {
+    conn: &'a crate::pg_util::RepoConnection,
+}
+
+impl<'a> NotificationPreferencesRepo<'a> {
+    pub fn new(conn: &'a crate::pg_util::RepoConnection) -> Self {
+        Self { conn }
+    }
+
+    /// Inserts the preferences of a user, or replaces them if they already exist.
+    pub fn upsert(
+        &self,
+        new_preferences: NewNotificationPreferences,
+    ) -> Result<domain::NotificationPreferences, diesel::result::Error> {
+        use crate::schema::notification_preferences::dsl::{
+            comment_replies, moderation_decisions, new_posts_digest, updated_at, user_id,
+        };
+        let conn = &self.conn.pg_conn;
+        let query = diesel::insert_into(notification_preferences::table)
+            .values(&new_preferences)
+            .on_conflict(user_id)
+            .do_update()
+            .set((
+                comment_replies.eq(excluded(comment_replies)),
+                moderation_decisions.eq(excluded(moderation_decisions)),
+                new_posts_digest.eq(excluded(new_posts_digest)),
+                updated_at.eq(Some(Utc::now().naive_utc())),
+            ));
+        let result = query.get_result(conn)?;
+        Ok(domain::NotificationPreferences::from(result))
+    }
+
+    pub fn update_one_by_user(
+        &self,
+        user_id_value: i32,
+        updated_preferences: UpdateNotificationPreferences,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::notification_preferences::dsl::{notification_preferences, user_id};
+        let conn = &self.conn.pg_conn;
+        let query = diesel::update(notification_preferences.filter(user_id.eq(user_id_value)))
+            .set(&updated_preferences);
+        query.execute(conn)
+    }
+
+    /// Turns off one kind of email for a user, storing the defaults for everything else
+    /// if they had never saved their preferences.
+    pub fn disable(
+        &self,
+        user_id_value: i32,
+        kind: NotificationKind,
+    ) -> Result<domain::NotificationPreferences, diesel::result::Error> {
+        let current = self.find_one_by_user(user_id_value)?;
+        let new_preferences = NewNotificationPreferences {
+            user_id: user_id_value,
+            comment_replies: current.comment_replies && kind != NotificationKind::CommentReplies,
+            moderation_decisions: current.moderation_decisions
+                && kind != NotificationKind::ModerationDecisions,
+            new_posts_digest: current.new_posts_digest && kind != NotificationKind::NewPostsDigest,
+        };
+        self.upsert(new_preferences)
+    }
+
+    pub fn mark_digest_sent(
+        &self,
+        user_id_value: i32,
+        sent_at: NaiveDateTime,
+    ) -> Result<usize, diesel::result::Error> {
+        self.update_one_by_user(
+            user_id_value,
+            UpdateNotificationPreferences {
+                comment_replies: None,
+                moderation_decisions: None,
+                new_posts_digest: None,
+                last_digest_sent_at: Some(Some(sent_at)),
+                updated_at: None,
+            },
+        )
+    }
+
+    /// The saved preferences of a user, or the defaults if there are none.
+    pub fn find_one_by_user(
+        &self,
+        user_id_value: i32,
+    ) -> Result<domain::NotificationPreferences, diesel::result::Error> {
+        use crate::schema::notification_preferences::dsl::{notification_preferences, user_id};
+
+        let conn = &self.conn.pg_conn;
+        let query = notification_preferences
+            .filter(user_id.eq(user_id_value))
+            .select(notification_preferences::all_columns());
+        let preferences: Option<db_models::NotificationPreferences> =
+            query.first(conn).optional()?;
+        Ok(match preferences {
+            Some(value) => domain::NotificationPreferences::from(value),
+            None => domain::NotificationPreferences::default_for(user_id_value),
+        })
+    }
+
+    /// Users who opted in to the new post digest and have not received one since `before`.
+    pub fn find_digest_due(
+        &self,
+        before: NaiveDateTime,
+    ) -> Result<Vec<(domain::NotificationPreferences, domain::User)>, diesel::result::Error> {
+        use crate::schema::notification_preferences::dsl::{
+            last_digest_sent_at, new_posts_digest, notification_preferences,
+        };
+        use crate::schema::users::dsl::users;
+
+        let conn = &self.conn.pg_conn;
+        let results: Vec<(db_models::NotificationPreferences, db_models::User)> =
+            notification_preferences
+                .inner_join(users)
+                .filter(new_posts_digest.eq(true))
+                .filter(
+                    last_digest_sent_at
+                        .is_null()
+                        .or(last_digest_sent_at.lt(before)),
+                )
+                .select((
+                    notification_preferences::all_columns(),
+                    users::all_columns(),
+                ))
+                .load(conn)?;
+        Ok(results
+            .into_iter()
+            .map(|(preferences, user)| {
+                (
+                    domain::NotificationPreferences::from(preferences),
+                    domain::User::from(user),
+                )
+            })
+            .collect())
+    }
+}
@@ -12,6 +12,8 @@ pub enum BlogPostSortType {
     CreatedAtDesc,
     TitleAsc,
     TitleDesc,
+    /// Most viewed first.
+    Popular,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -26,6 +28,8 @@ pub enum ProjectSortType {
     CreatedAtDesc,
     NameAsc,
     NameDesc,
+    /// Most viewed first.
+    Popular,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -115,7 +119,51 @@ pub enum ChangePasswordTokenSortType {
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
-pub enum SearchItemsSortType {}
+pub enum SearchItemsSortType {
+    Relevance,
+    CreatedAtAsc,
+    CreatedAtDesc,
+    TitleAsc,
+    TitleDesc,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub enum ApiTokenSortType {
+    CreatedAtAsc,
+    CreatedAtDesc,
+    LastUsedAtAsc,
+    LastUsedAtDesc,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub enum OutboxEmailSortType {
+    CreatedAtAsc,
+    CreatedAtDesc,
+    NextAttemptAtAsc,
+    NextAttemptAtDesc,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub enum NewsletterSubscriberSortType {
+    CreatedAtAsc,
+    CreatedAtDesc,
+    EmailAsc,
+    EmailDesc,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub enum NewsletterIssueSortType {
+    CreatedAtAsc,
+    CreatedAtDesc,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub enum ContactMessageSortType {
+    CreatedAtAsc,
+    CreatedAtDesc,
+    SpamScoreAsc,
+    SpamScoreDesc,
+}
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct PaginationOptions {
@@ -128,3 +176,22 @@ impl PaginationOptions {
         Self { page, page_size }
     }
 }
+
+/// How report rows are grouped over time.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportInterval {
+    Day,
+    Week,
+    Month,
+}
+
+impl ReportInterval {
+    /// The field name `date_trunc` takes for this interval.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportInterval::Day => "day",
+            ReportInterval::Week => "week",
+            ReportInterval::Month => "month",
+        }
+    }
+}
@@ -1,11 +1,84 @@
 use crate::errors::PgRepoError;
-use crate::filters::GetAllPageViewsFilter;
+use crate::extra::ReferrerType;
+use crate::filters::{GetAllPageViewsFilter, PageViewAnalyticsFilter};
 use crate::insertables::NewPageView;
 use crate::models::{db_models, domain};
-use crate::options::{PageViewSortType, PaginationOptions};
+use crate::options::{PageViewSortType, PaginationOptions, ReportInterval};
 use crate::schema::page_views;
+use chrono::NaiveDateTime;
+use diesel::dsl::sql;
+use diesel::pg::Pg;
 use diesel::prelude::*;
-use diesel::{QueryDsl, RunQueryDsl};
+use diesel::query_builder::BoxedSelectStatement;
+use diesel::sql_types::{BigInt, Text, Timestamp};
+use diesel::{GroupByDsl, QueryDsl, RunQueryDsl};
+
+const VIEWS: &str = "count(*)";
+const UNIQUE_VISITORS: &str = "count(DISTINCT page_views.id_hash)";
+const VISITS: &str = "count(DISTINCT page_views.visit_id)";
+
+/// Rolls page views from before a day into `page_view_daily_stats`, a row per page
+/// and a total per day, each for bots and for people.
+const ARCHIVE_SQL: &str = "INSERT INTO page_view_daily_stats \
+    (day, page_url, page_path, is_bot, views, unique_visitors) \
+    SELECT created_at::date, page_url, page_path, is_bot, count(*), count(DISTINCT id_hash) \
+    FROM page_views WHERE created_at < $1 \
+    GROUP BY GROUPING SETS \
+    ((created_at::date, is_bot), (created_at::date, is_bot, page_url, page_path))";
+
+/// Views of a page by people, rolled up days included. `path` is the SQL for the
+/// page's path, like `'/blog/' || blog_posts.slug`.
+pub(crate) fn view_count_sql(path: &str) -> String {
+    format!(
+        "((SELECT count(*) FROM page_views \
+        WHERE page_views.page_path = {path} AND NOT page_views.is_bot) \
+        + COALESCE((SELECT sum(views) FROM page_view_daily_stats \
+        WHERE page_view_daily_stats.page_path = {path} AND NOT page_view_daily_stats.is_bot), 0))::int8",
+        path = path
+    )
+}
+
+/// Views of a page by people since `days` days ago.
+pub(crate) fn recent_view_count_sql(path: &str, days: i64) -> String {
+    format!(
+        "(SELECT count(*) FROM page_views \
+        WHERE page_views.page_path = {path} AND NOT page_views.is_bot \
+        AND page_views.created_at >= now() - interval '{days} days')",
+        path = path,
+        days = days
+    )
+}
+
+/// UTM source, medium and campaign, then visits and views.
+type CampaignRow = (Option<String>, Option<String>, Option<String>, i64, i64);
+
+fn apply_filter<'q, ST>(
+    q: BoxedSelectStatement<'q, ST, page_views::table, Pg>,
+    filter: &PageViewAnalyticsFilter,
+) -> BoxedSelectStatement<'q, ST, page_views::table, Pg> {
+    use crate::schema::page_views::dsl::{created_at, is_bot, referrer_type};
+
+    let q = if let Some(from) = filter.range.from {
+        q.filter(created_at.ge(from))
+    } else {
+        q
+    };
+    let q = if let Some(to) = filter.range.to {
+        q.filter(created_at.lt(to))
+    } else {
+        q
+    };
+    let q = if filter.include_bots {
+        q
+    } else {
+        q.filter(is_bot.eq(false))
+    };
+    if let Some(referrer_type_value) = filter.referrer_type {
+        q.filter(referrer_type.eq(referrer_type_value))
+    } else {
+        q
+    }
+}
 
 pub struct PageViewRepo<'a> {
     conn: &'a crate::pg_util::RepoConnection,
@@ -68,11 +141,12 @@ impl<'a> PageViewRepo<'a> {
     }
 
     pub fn count_by_url(&self, url_value: &str) -> Result<i64, diesel::result::Error> {
-        use crate::schema::page_views::dsl::{id_hash, page_url, page_views};
+        use crate::schema::page_views::dsl::{id_hash, is_bot, page_url, page_views};
 
         let conn = &self.conn.pg_conn;
         let query = page_views
             .filter(page_url.eq(url_value))
+            .filter(is_bot.eq(false))
             .select(diesel::dsl::sql::<diesel::sql_types::BigInt>(
                 "Count(DISTINCT page_views.id_hash) ",
             ));
@@ -82,12 +156,29 @@ impl<'a> PageViewRepo<'a> {
         Ok(count)
     }
 
+    /// Views by one visitor since `since`, bots or not.
+    pub fn count_recent_by_id_hash(
+        &self,
+        id_hash_value: &str,
+        since: NaiveDateTime,
+    ) -> Result<i64, diesel::result::Error> {
+        use crate::schema::page_views::dsl::{created_at, id_hash, page_views};
+
+        let conn = &self.conn.pg_conn;
+        page_views
+            .filter(id_hash.eq(id_hash_value))
+            .filter(created_at.ge(since))
+            .count()
+            .get_result(conn)
+    }
+
     pub fn count_by_root_url(&self, url_value: String) -> Result<i64, diesel::result::Error> {
-        use crate::schema::page_views::dsl::{id_hash, page_url, page_views};
+        use crate::schema::page_views::dsl::{id_hash, is_bot, page_url, page_views};
 
         let conn = &self.conn.pg_conn;
         let query = page_views
             .filter(page_url.like(format!("{}%", url_value)))
+            .filter(is_bot.eq(false))
             .select(diesel::dsl::sql::<diesel::sql_types::BigInt>(
                 "Count(DISTINCT page_views.id_hash) ",
             ));
@@ -119,4 +210,285 @@ impl<'a> PageViewRepo<'a> {
             .map(|text_body| domain::PageView::from(text_body))
             .collect::<Vec<_>>())
     }
+
+    /// Views and unique visitors per period, oldest first. Days past the retention
+    /// period come from their daily totals, so a visitor seen on several of them is
+    /// counted once per day.
+    pub fn find_traffic(
+        &self,
+        filter: &PageViewAnalyticsFilter,
+        interval: ReportInterval,
+    ) -> Result<Vec<domain::PageViewTraffic>, diesel::result::Error> {
+        use crate::schema::page_views::dsl::page_views;
+
+        let conn = &self.conn.pg_conn;
+        let period_sql = format!("date_trunc('{}', created_at)", interval.as_str());
+        let q = page_views
+            .into_boxed()
+            .select((
+                sql::<Timestamp>(&period_sql),
+                sql::<BigInt>(VIEWS),
+                sql::<BigInt>(UNIQUE_VISITORS),
+            ))
+            .group_by(sql::<Text>("1"));
+        let results: Vec<(NaiveDateTime, i64, i64)> = apply_filter(q, filter)
+            .order(sql::<Text>("1").asc())
+            .load(conn)?;
+
+        // Archived days all come before the views still kept
+        let mut traffic = self.find_archived_traffic(filter, interval)?;
+        for (period, views, unique_visitors) in results {
+            match traffic.last_mut() {
+                Some(last) if last.period == period => {
+                    last.views += views;
+                    last.unique_visitors += unique_visitors;
+                }
+                _ => traffic.push(domain::PageViewTraffic {
+                    period,
+                    views,
+                    unique_visitors,
+                }),
+            }
+        }
+        Ok(traffic)
+    }
+
+    fn find_archived_traffic(
+        &self,
+        filter: &PageViewAnalyticsFilter,
+        interval: ReportInterval,
+    ) -> Result<Vec<domain::PageViewTraffic>, diesel::result::Error> {
+        use crate::schema::page_view_daily_stats::dsl::{
+            day, is_bot, page_url, page_view_daily_stats,
+        };
+
+        // Referrers are not kept in the daily totals
+        if filter.referrer_type.is_some() {
+            return Ok(Vec::new());
+        }
+        let conn = &self.conn.pg_conn;
+        let period_sql = format!("date_trunc('{}', day)::timestamp", interval.as_str());
+        let q = page_view_daily_stats
+            .into_boxed()
+            .select((
+                sql::<Timestamp>(&period_sql),
+                sql::<BigInt>("sum(views)::int8"),
+                sql::<BigInt>("sum(unique_visitors)::int8"),
+            ))
+            .filter(page_url.is_null())
+            .group_by(sql::<Text>("1"));
+        let q = if let Some(from) = filter.range.from {
+            q.filter(day.ge(from.date()))
+        } else {
+            q
+        };
+        let q = if let Some(to) = filter.range.to {
+            q.filter(day.lt(to.date()))
+        } else {
+            q
+        };
+        let q = if filter.include_bots {
+            q
+        } else {
+            q.filter(is_bot.eq(false))
+        };
+        let results: Vec<(NaiveDateTime, i64, i64)> = q.order(sql::<Text>("1").asc()).load(conn)?;
+        Ok(results
+            .into_iter()
+            .map(|(period, views, unique_visitors)| domain::PageViewTraffic {
+                period,
+                views,
+                unique_visitors,
+            })
+            .collect())
+    }
+
+    /// Rolls the views from before `before` up into daily stats and deletes them,
+    /// returning how many were deleted. `before` should be the start of a day, so
+    /// that each day is rolled up once.
+    pub fn archive_before(&self, before: NaiveDateTime) -> Result<usize, diesel::result::Error> {
+        use crate::schema::page_views::dsl::{created_at, page_views};
+
+        let conn = &self.conn.pg_conn;
+        diesel::sql_query(ARCHIVE_SQL)
+            .bind::<Timestamp, _>(before)
+            .execute(conn)?;
+        diesel::delete(page_views.filter(created_at.lt(before))).execute(conn)
+    }
+
+    /// The most viewed pages.
+    pub fn find_top_pages(
+        &self,
+        filter: &PageViewAnalyticsFilter,
+        limit: i64,
+    ) -> Result<Vec<domain::PageStats>, diesel::result::Error> {
+        use crate::schema::page_views::dsl::{page_url, page_views};
+
+        let conn = &self.conn.pg_conn;
+        let q = page_views
+            .into_boxed()
+            .select((
+                page_url,
+                sql::<BigInt>(VIEWS),
+                sql::<BigInt>(UNIQUE_VISITORS),
+            ))
+            .group_by(page_url);
+        let results: Vec<(String, i64, i64)> = apply_filter(q, filter)
+            .order((sql::<BigInt>(VIEWS).desc(), page_url.asc()))
+            .limit(limit)
+            .load(conn)?;
+        Ok(results
+            .into_iter()
+            .map(
+                |(page_url_value, views, unique_visitors)| domain::PageStats {
+                    page_url: page_url_value,
+                    views,
+                    unique_visitors,
+                },
+            )
+            .collect())
+    }
+
+    /// The countries most views came from, views that could not be located included.
+    pub fn find_top_countries(
+        &self,
+        filter: &PageViewAnalyticsFilter,
+        limit: i64,
+    ) -> Result<Vec<domain::CountryStats>, diesel::result::Error> {
+        use crate::schema::page_views::dsl::{country_code, page_views};
+
+        let conn = &self.conn.pg_conn;
+        let q = page_views
+            .into_boxed()
+            .select((
+                country_code,
+                sql::<BigInt>(VIEWS),
+                sql::<BigInt>(UNIQUE_VISITORS),
+            ))
+            .group_by(country_code);
+        let results: Vec<(Option<String>, i64, i64)> = apply_filter(q, filter)
+            .order((sql::<BigInt>(VIEWS).desc(), country_code.asc()))
+            .limit(limit)
+            .load(conn)?;
+        Ok(results
+            .into_iter()
+            .map(
+                |(country_code_value, views, unique_visitors)| domain::CountryStats {
+                    country_code: country_code_value,
+                    views,
+                    unique_visitors,
+                },
+            )
+            .collect())
+    }
+
+    /// Views and visitors split by whether they were signed in.
+    pub fn find_visitor_types(
+        &self,
+        filter: &PageViewAnalyticsFilter,
+    ) -> Result<domain::VisitorTypeStats, diesel::result::Error> {
+        use crate::schema::page_views::dsl::page_views;
+
+        let conn = &self.conn.pg_conn;
+        let q = page_views.into_boxed().select((
+            sql::<BigInt>("count(*) FILTER (WHERE registered)"),
+            sql::<BigInt>("count(*) FILTER (WHERE NOT registered)"),
+            sql::<BigInt>("count(DISTINCT id_hash) FILTER (WHERE registered)"),
+            sql::<BigInt>("count(DISTINCT id_hash) FILTER (WHERE NOT registered)"),
+        ));
+        let (registered_views, anonymous_views, registered_visitors, anonymous_visitors) =
+            apply_filter(q, filter).first(conn)?;
+        Ok(domain::VisitorTypeStats {
+            registered_views,
+            anonymous_views,
+            registered_visitors,
+            anonymous_visitors,
+        })
+    }
+
+    /// The sites visitors came from, with search engines and social networks grouped by name.
+    pub fn find_top_referrers(
+        &self,
+        filter: &PageViewAnalyticsFilter,
+        limit: i64,
+    ) -> Result<Vec<domain::ReferrerStats>, diesel::result::Error> {
+        use crate::schema::page_views::dsl::{page_views, referrer_source, referrer_type};
+
+        let conn = &self.conn.pg_conn;
+        let q = page_views
+            .into_boxed()
+            .select((
+                referrer_source,
+                referrer_type,
+                sql::<BigInt>(VISITS),
+                sql::<BigInt>(VIEWS),
+            ))
+            .filter(referrer_source.is_not_null())
+            .filter(referrer_type.is_not_null())
+            .group_by((referrer_source, referrer_type));
+        let results: Vec<(Option<String>, Option<ReferrerType>, i64, i64)> =
+            apply_filter(q, filter)
+                .order((sql::<BigInt>(VISITS).desc(), referrer_source.asc()))
+                .limit(limit)
+                .load(conn)?;
+        Ok(results
+            .into_iter()
+            .filter_map(|(source, referrer_type_value, visits, views)| {
+                match (source, referrer_type_value) {
+                    (Some(source), Some(referrer_type_value)) => Some(domain::ReferrerStats {
+                        source,
+                        referrer_type: referrer_type_value,
+                        visits,
+                        views,
+                    }),
+                    _ => None,
+                }
+            })
+            .collect())
+    }
+
+    /// The campaigns visitors came from, by their UTM source, medium and name.
+    pub fn find_top_campaigns(
+        &self,
+        filter: &PageViewAnalyticsFilter,
+        limit: i64,
+    ) -> Result<Vec<domain::CampaignStats>, diesel::result::Error> {
+        use crate::schema::page_views::dsl::{page_views, utm_campaign, utm_medium, utm_source};
+
+        let conn = &self.conn.pg_conn;
+        let q = page_views
+            .into_boxed()
+            .select((
+                utm_source,
+                utm_medium,
+                utm_campaign,
+                sql::<BigInt>(VISITS),
+                sql::<BigInt>(VIEWS),
+            ))
+            .filter(
+                utm_source
+                    .is_not_null()
+                    .or(utm_medium.is_not_null())
+                    .or(utm_campaign.is_not_null()),
+            )
+            .group_by((utm_source, utm_medium, utm_campaign));
+        let results: Vec<CampaignRow> = apply_filter(q, filter)
+            .order((sql::<BigInt>(VISITS).desc(), utm_campaign.asc()))
+            .limit(limit)
+            .load(conn)?;
+        Ok(results
+            .into_iter()
+            .map(
+                |(utm_source_value, utm_medium_value, utm_campaign_value, visits, views)| {
+                    domain::CampaignStats {
+                        utm_source: utm_source_value,
+                        utm_medium: utm_medium_value,
+                        utm_campaign: utm_campaign_value,
+                        visits,
+                        views,
+                    }
+                },
+            )
+            .collect())
+    }
 }
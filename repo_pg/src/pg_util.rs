@@ -7,7 +7,7 @@ use std::sync::Arc;
 
 use diesel::pg::PgConnection;
 use diesel::r2d2::ConnectionManager;
-use diesel::QueryResult;
+pub use diesel::QueryResult;
 use r2d2::{Pool, PooledConnection};
 use tokio::task;
 
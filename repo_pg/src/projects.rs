@@ -2,6 +2,7 @@ use crate::errors::PgRepoError;
 use crate::filters::GetAllProjectsFilter;
 use crate::models::{db_models, domain};
 use crate::options::{PaginationOptions, ProjectSortType};
+use crate::page_views::view_count_sql;
 use crate::schema::{projects, projects_technologies, technologies};
 use crate::{
     change_sets::UpdateProject,
@@ -10,6 +11,9 @@ use crate::{
 use diesel::prelude::*;
 use diesel::{QueryDsl, RunQueryDsl};
 
+/// Where the site shows a project, to match its page views.
+pub(crate) const PROJECT_PATH: &str = "'/projects/' || projects.slug";
+
 pub struct ProjectRepo<'a> {
     conn: &'a crate::pg_util::RepoConnection,
 }
@@ -26,9 +30,12 @@ impl<'a> ProjectRepo<'a> {
         let conn = &self.conn.pg_conn;
         let query = diesel::insert_into(projects::table).values(&new_project);
         let result = query.get_result(conn)?;
-        Ok(domain::Project::from(result, vec![]))
+        Ok(domain::Project::from(result, vec![], 0))
     }
 
+    /// Links the project to the technologies with these names, adding the missing ones with a
+    /// slug made from the name. Names are matched against the current names, so a
+    /// renamed technology keeps its projects and its slug.
     fn update_technologies(
         &self,
         inserted_project_id: i32,
@@ -47,7 +54,14 @@ impl<'a> ProjectRepo<'a> {
         let new_technologies: Vec<NewTechnology> = technologies_list
             .clone()
             .into_iter()
-            .map(|name| NewTechnology { name })
+            .map(|name| NewTechnology {
+                name,
+                slug: None,
+                description: None,
+                icon: None,
+                color: None,
+                parent_id: None,
+            })
             .collect();
         let conn = &self.conn.pg_conn;
         let query = diesel::insert_into(technologies::table)
@@ -59,7 +73,7 @@ impl<'a> ProjectRepo<'a> {
             .into_boxed();
         let query = query.filter(technology_name.eq_any(technologies_list));
         let conn = &self.conn.pg_conn;
-        let inserted_technologies: Vec<db_models::Category> = query.load(conn)?;
+        let inserted_technologies: Vec<db_models::Technology> = query.load(conn)?;
         let new_projects_technologies: Vec<NewProjectTechnology> = inserted_technologies
             .into_iter()
             .map(|technology| NewProjectTechnology {
@@ -87,7 +101,11 @@ impl<'a> ProjectRepo<'a> {
             Some(value) => value,
         };
         let _ = self.update_technologies(inserted_project.id, &technologies_list)?;
-        Ok(domain::Project::from(inserted_project, technologies_list))
+        Ok(domain::Project::from(
+            inserted_project,
+            technologies_list,
+            0,
+        ))
     }
 
     pub fn update_one(
@@ -99,7 +117,7 @@ impl<'a> ProjectRepo<'a> {
         let conn = &self.conn.pg_conn;
         let query = diesel::update(projects.filter(id.eq(id_value))).set(updated_project);
         let result = query.get_result(conn)?;
-        Ok(domain::Project::from(result, vec![]))
+        Ok(domain::Project::from(result, vec![], 0))
     }
 
     pub fn update_one_with_technologies(
@@ -154,15 +172,20 @@ impl<'a> ProjectRepo<'a> {
                         diesel::sql_types::Nullable<diesel::sql_types::VarChar>,
                     >,
                 >("array_agg(\"technologies\".\"name\")"),
+                diesel::dsl::sql::<diesel::sql_types::BigInt>(&view_count_sql(PROJECT_PATH)),
             ));
-        let (project, technologies_list): (db_models::Project, Vec<Option<String>>) =
-            match query.first(conn).optional()? {
-                Some(value) => value,
-                None => return Ok(None),
-            };
+        let (project, technologies_list, view_count): (
+            db_models::Project,
+            Vec<Option<String>>,
+            i64,
+        ) = match query.first(conn).optional()? {
+            Some(value) => value,
+            None => return Ok(None),
+        };
         Ok(Some(domain::Project::from(
             project,
             technologies_list.into_iter().filter_map(|v| v).collect(),
+            view_count,
         )))
     }
 
@@ -191,15 +214,20 @@ impl<'a> ProjectRepo<'a> {
                         diesel::sql_types::Nullable<diesel::sql_types::VarChar>,
                     >,
                 >("array_agg(\"technologies\".\"name\")"),
+                diesel::dsl::sql::<diesel::sql_types::BigInt>(&view_count_sql(PROJECT_PATH)),
             ));
-        let (project, technologies_list): (db_models::Project, Vec<Option<String>>) =
-            match query.first(conn).optional()? {
-                Some(value) => value,
-                None => return Ok(None),
-            };
+        let (project, technologies_list, view_count): (
+            db_models::Project,
+            Vec<Option<String>>,
+            i64,
+        ) = match query.first(conn).optional()? {
+            Some(value) => value,
+            None => return Ok(None),
+        };
         Ok(Some(domain::Project::from(
             project,
             technologies_list.into_iter().filter_map(|v| v).collect(),
+            view_count,
         )))
     }
 
@@ -231,6 +259,7 @@ impl<'a> ProjectRepo<'a> {
                         diesel::sql_types::Nullable<diesel::sql_types::VarChar>,
                     >,
                 >("array_agg(\"technologies\".\"name\")"),
+                diesel::dsl::sql::<diesel::sql_types::BigInt>(&view_count_sql(PROJECT_PATH)),
                 diesel::dsl::sql::<diesel::sql_types::BigInt>("Count(*) Over()"),
             ))
             .into_boxed();
@@ -264,6 +293,19 @@ impl<'a> ProjectRepo<'a> {
             q
         };
 
+        let q = if let Some(technology_ids_filter) = filter.technology_ids {
+            q.filter(
+                project_id.eq_any(
+                    projects_technologies_dsl
+                        .filter(projects_technologies_technology_id.eq_any(technology_ids_filter))
+                        .into_boxed()
+                        .select(projects_technologies_project_id),
+                ),
+            )
+        } else {
+            q
+        };
+
         let q = if let Some(published) = filter.published {
             q.filter(project_published.eq(published))
         } else {
@@ -276,6 +318,11 @@ impl<'a> ProjectRepo<'a> {
                 ProjectSortType::CreatedAtDesc => q.order(projects::created_at.desc()),
                 ProjectSortType::NameAsc => q.order(projects::name.asc()),
                 ProjectSortType::NameDesc => q.order(projects::name.desc()),
+                ProjectSortType::Popular => q.order((
+                    diesel::dsl::sql::<diesel::sql_types::BigInt>(&view_count_sql(PROJECT_PATH))
+                        .desc(),
+                    projects::created_at.desc(),
+                )),
             }
         } else {
             q
@@ -288,19 +335,20 @@ impl<'a> ProjectRepo<'a> {
         };
 
         let conn = &self.conn.pg_conn;
-        let results: Vec<(db_models::Project, Vec<Option<String>>, i64)> = q.load(conn)?;
+        let results: Vec<(db_models::Project, Vec<Option<String>>, i64, i64)> = q.load(conn)?;
 
         let count = match results.get(0) {
-            Some((_, _, value)) => *value,
+            Some((_, _, _, value)) => *value,
             None => 0,
         };
 
         let projects_list = results
             .into_iter()
-            .map(|(project, technologies_list, _)| {
+            .map(|(project, technologies_list, view_count, _)| {
                 domain::Project::from(
                     project,
                     technologies_list.into_iter().filter_map(|v| v).collect(),
+                    view_count,
                 )
             })
             .collect::<Vec<_>>();
@@ -63,6 +63,28 @@ impl<'a> RefreshTokenRepo<'a> {
         )
     }
 
+    /// Invalidates every refresh token of the user, except the one of the given jwt.
+    pub fn invalidate_all_for_user(
+        &self,
+        user_id_value: i32,
+        except_jwt_id: Option<uuid::Uuid>,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::refresh_tokens::dsl::{invalidated, jwt_id, refresh_tokens, user_id};
+        let conn = &self.conn.pg_conn;
+        let user_tokens = refresh_tokens
+            .filter(user_id.eq(user_id_value))
+            .filter(invalidated.eq(false));
+        let updated = match except_jwt_id {
+            Some(jwt_id_value) => diesel::update(user_tokens.filter(jwt_id.ne(jwt_id_value)))
+                .set(invalidated.eq(true))
+                .execute(conn)?,
+            None => diesel::update(user_tokens)
+                .set(invalidated.eq(true))
+                .execute(conn)?,
+        };
+        Ok(updated)
+    }
+
     pub fn delete_one(&self, id_value: uuid::Uuid) -> Result<usize, diesel::result::Error> {
         use crate::schema::refresh_tokens::dsl::{id, refresh_tokens};
         let conn = &self.conn.pg_conn;
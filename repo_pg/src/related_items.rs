@@ -0,0 +1,36 @@
+use crate::extra::SearchItemType;
+use crate::models::{db_models, domain};
+use crate::schema_extra::related_items;
+use diesel::prelude::*;
+use diesel::{QueryDsl, RunQueryDsl};
+
+pub struct RelatedItemRepo<'a> {
+    conn: &'a crate::pg_util::RepoConnection,
+}
+
+impl<'a> RelatedItemRepo<'a> {
+    pub fn new(conn: &'a crate::pg_util::RepoConnection) -> Self {
+        Self { conn }
+    }
+
+    /// The published items of the same type most related to an item, best first.
+    /// Kept up to date with the search views.
+    pub fn find(
+        &self,
+        item_type_value: SearchItemType,
+        item_id_value: i32,
+        limit: i64,
+    ) -> Result<Vec<domain::RelatedItem>, diesel::result::Error> {
+        use crate::schema_extra::related_items::dsl::{item_id, item_type, rank};
+
+        let conn = &self.conn.pg_conn;
+        let results: Vec<db_models::RelatedItem> = related_items::table
+            .into_boxed()
+            .filter(item_type.eq(item_type_value))
+            .filter(item_id.eq(item_id_value))
+            .order(rank.asc())
+            .limit(limit)
+            .load(conn)?;
+        Ok(results.into_iter().map(domain::RelatedItem::from).collect())
+    }
+}
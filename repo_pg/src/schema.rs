@@ -16,6 +16,24 @@ table! {
     }
 }
 
+table! {
+    use diesel::sql_types::*;
+    use crate::exports::*;
+
+    api_tokens (id) {
+        id -> Int4,
+        user_id -> Int4,
+        name -> Varchar,
+        token_prefix -> Varchar,
+        token_hash -> Varchar,
+        scopes -> Array<Varchar>,
+        revoked -> Bool,
+        created_at -> Timestamp,
+        expires_at -> Nullable<Timestamp>,
+        last_used_at -> Nullable<Timestamp>,
+    }
+}
+
 table! {
     use diesel::sql_types::*;
     use crate::exports::*;
@@ -53,6 +71,7 @@ table! {
         updated_at -> Nullable<Timestamp>,
         author_id -> Int4,
         post_id -> Int4,
+        parent_id -> Nullable<Int4>,
     }
 }
 
@@ -70,6 +89,8 @@ table! {
         author_id -> Int4,
         description -> Nullable<Varchar>,
         slug -> Varchar,
+        language -> Content_language,
+        published_at -> Nullable<Timestamp>,
     }
 }
 
@@ -91,6 +112,11 @@ table! {
     categories (id) {
         id -> Int4,
         name -> Varchar,
+        slug -> Varchar,
+        description -> Nullable<Varchar>,
+        icon -> Nullable<Varchar>,
+        color -> Nullable<Varchar>,
+        parent_id -> Nullable<Int4>,
     }
 }
 
@@ -109,6 +135,71 @@ table! {
     }
 }
 
+table! {
+    use diesel::sql_types::*;
+    use crate::exports::*;
+
+    contact_message_replies (id) {
+        id -> Int4,
+        contact_message_id -> Int4,
+        user_id -> Int4,
+        body -> Text,
+        email_outbox_id -> Nullable<Int4>,
+        created_at -> Timestamp,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use crate::exports::*;
+
+    contact_messages (id) {
+        id -> Int4,
+        from_email -> Varchar,
+        subject -> Varchar,
+        body -> Text,
+        ip_address -> Nullable<Varchar>,
+        user_agent -> Nullable<Varchar>,
+        spam_score -> Int4,
+        spam -> Bool,
+        created_at -> Timestamp,
+        read_at -> Nullable<Timestamp>,
+        archived_at -> Nullable<Timestamp>,
+        replied_at -> Nullable<Timestamp>,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use crate::exports::*;
+
+    daily_salts (day) {
+        day -> Date,
+        salt -> Varchar,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use crate::exports::*;
+
+    email_outbox (id) {
+        id -> Int4,
+        to_address -> Varchar,
+        to_name -> Nullable<Varchar>,
+        subject -> Varchar,
+        body_html -> Text,
+        body_text -> Text,
+        status -> Email_status,
+        attempts -> Int4,
+        last_error -> Nullable<Text>,
+        next_attempt_at -> Timestamp,
+        created_at -> Timestamp,
+        sent_at -> Nullable<Timestamp>,
+        list_unsubscribe -> Nullable<Varchar>,
+    }
+}
+
 table! {
     use diesel::sql_types::*;
     use crate::exports::*;
@@ -133,6 +224,83 @@ table! {
     }
 }
 
+table! {
+    use diesel::sql_types::*;
+    use crate::exports::*;
+
+    newsletter_deliveries (id) {
+        id -> Int4,
+        newsletter_issue_id -> Int4,
+        newsletter_subscriber_id -> Int4,
+        email_outbox_id -> Nullable<Int4>,
+        created_at -> Timestamp,
+        failed_at -> Nullable<Timestamp>,
+        last_error -> Nullable<Varchar>,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use crate::exports::*;
+
+    newsletter_issues (id) {
+        id -> Int4,
+        subject -> Varchar,
+        intro -> Text,
+        blog_post_ids -> Array<Int4>,
+        user_id -> Int4,
+        created_at -> Timestamp,
+        updated_at -> Nullable<Timestamp>,
+        sent_at -> Nullable<Timestamp>,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use crate::exports::*;
+
+    newsletter_subscribers (id) {
+        id -> Int4,
+        email -> Varchar,
+        token -> Varchar,
+        confirmed -> Bool,
+        created_at -> Timestamp,
+        confirmed_at -> Nullable<Timestamp>,
+        unsubscribed_at -> Nullable<Timestamp>,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use crate::exports::*;
+
+    notification_preferences (id) {
+        id -> Int4,
+        user_id -> Int4,
+        comment_replies -> Bool,
+        moderation_decisions -> Bool,
+        new_posts_digest -> Bool,
+        last_digest_sent_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        updated_at -> Nullable<Timestamp>,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use crate::exports::*;
+
+    page_view_daily_stats (id) {
+        id -> Int4,
+        day -> Date,
+        page_url -> Nullable<Varchar>,
+        is_bot -> Bool,
+        views -> Int8,
+        unique_visitors -> Int8,
+        page_path -> Nullable<Varchar>,
+    }
+}
+
 table! {
     use diesel::sql_types::*;
     use crate::exports::*;
@@ -147,6 +315,17 @@ table! {
         latitude -> Nullable<Float8>,
         longitude -> Nullable<Float8>,
         country_code -> Nullable<Varchar>,
+        visit_id -> Nullable<Int4>,
+        referrer -> Nullable<Varchar>,
+        referrer_source -> Nullable<Varchar>,
+        referrer_type -> Nullable<Referrer_type>,
+        utm_source -> Nullable<Varchar>,
+        utm_medium -> Nullable<Varchar>,
+        utm_campaign -> Nullable<Varchar>,
+        utm_term -> Nullable<Varchar>,
+        utm_content -> Nullable<Varchar>,
+        is_bot -> Bool,
+        page_path -> Varchar,
     }
 }
 
@@ -164,6 +343,7 @@ table! {
         name -> Varchar,
         published -> Bool,
         slug -> Varchar,
+        language -> Content_language,
     }
 }
 
@@ -193,6 +373,81 @@ table! {
     }
 }
 
+table! {
+    use diesel::sql_types::*;
+    use crate::exports::*;
+
+    search_clicks (id) {
+        id -> Int4,
+        search_query_id -> Int4,
+        link -> Varchar,
+        position -> Nullable<Int4>,
+        created_at -> Timestamp,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use crate::exports::*;
+
+    search_queries (id) {
+        id -> Int4,
+        query -> Varchar,
+        item_type -> Nullable<Search_item_type>,
+        result_count -> Int8,
+        created_at -> Timestamp,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use crate::exports::*;
+
+    search_refresh_requests (id) {
+        id -> Int8,
+        requested_at -> Timestamp,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use crate::exports::*;
+
+    search_refreshes (id) {
+        id -> Int4,
+        requested_at -> Timestamp,
+        refreshed_at -> Nullable<Timestamp>,
+        refreshed_through -> Nullable<Timestamp>,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use crate::exports::*;
+
+    series (id) {
+        id -> Int4,
+        title -> Varchar,
+        slug -> Varchar,
+        description -> Nullable<Varchar>,
+        published -> Bool,
+        language -> Content_language,
+        created_at -> Timestamp,
+        updated_at -> Nullable<Timestamp>,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use crate::exports::*;
+
+    series_blog_posts (series_id, blog_post_id) {
+        series_id -> Int4,
+        blog_post_id -> Int4,
+        position -> Int4,
+    }
+}
+
 table! {
     use diesel::sql_types::*;
     use crate::exports::*;
@@ -214,6 +469,11 @@ table! {
     technologies (id) {
         id -> Int4,
         name -> Varchar,
+        slug -> Varchar,
+        description -> Nullable<Varchar>,
+        icon -> Nullable<Varchar>,
+        color -> Nullable<Varchar>,
+        parent_id -> Nullable<Int4>,
     }
 }
 
@@ -229,6 +489,7 @@ table! {
         url_used -> Nullable<Varchar>,
         created_at -> Timestamp,
         updated_at -> Nullable<Timestamp>,
+        language -> Content_language,
     }
 }
 
@@ -278,10 +539,27 @@ table! {
         used -> Bool,
         created_at -> Timestamp,
         expires_at -> Timestamp,
+        undo -> Bool,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use crate::exports::*;
+
+    visits (id) {
+        id -> Int4,
+        id_hash -> Varchar,
+        landing_page -> Varchar,
+        exit_page -> Varchar,
+        views -> Int4,
+        started_at -> Timestamp,
+        last_viewed_at -> Timestamp,
     }
 }
 
 joinable!(admin_logs -> users (user_id));
+joinable!(api_tokens -> users (user_id));
 joinable!(blog_post_comment_flags -> blog_post_comments (blog_post_comment_id));
 joinable!(blog_post_comment_flags -> users (user_id));
 joinable!(blog_post_comment_ratings -> blog_post_comments (blog_post_comment_id));
@@ -292,14 +570,27 @@ joinable!(blog_posts -> users (author_id));
 joinable!(blog_posts_categories -> blog_posts (blog_post_id));
 joinable!(blog_posts_categories -> categories (category_id));
 joinable!(change_password_tokens -> users (user_id));
+joinable!(contact_message_replies -> contact_messages (contact_message_id));
+joinable!(contact_message_replies -> email_outbox (email_outbox_id));
+joinable!(contact_message_replies -> users (user_id));
+joinable!(newsletter_deliveries -> email_outbox (email_outbox_id));
+joinable!(newsletter_deliveries -> newsletter_issues (newsletter_issue_id));
+joinable!(newsletter_deliveries -> newsletter_subscribers (newsletter_subscriber_id));
+joinable!(newsletter_issues -> users (user_id));
+joinable!(notification_preferences -> users (user_id));
+joinable!(page_views -> visits (visit_id));
 joinable!(projects_technologies -> projects (project_id));
 joinable!(projects_technologies -> technologies (technology_id));
 joinable!(refresh_tokens -> users (user_id));
+joinable!(search_clicks -> search_queries (search_query_id));
+joinable!(series_blog_posts -> blog_posts (blog_post_id));
+joinable!(series_blog_posts -> series (series_id));
 joinable!(uploaded_images -> users (user_id));
 joinable!(verify_email_tokens -> users (user_id));
 
 allow_tables_to_appear_in_same_query!(
     admin_logs,
+    api_tokens,
     blog_post_comment_flags,
     blog_post_comment_ratings,
     blog_post_comments,
@@ -307,16 +598,32 @@ allow_tables_to_appear_in_same_query!(
     blog_posts_categories,
     categories,
     change_password_tokens,
+    contact_message_replies,
+    contact_messages,
+    daily_salts,
+    email_outbox,
     home_page_links,
     identification_cookies,
+    newsletter_deliveries,
+    newsletter_issues,
+    newsletter_subscribers,
+    notification_preferences,
+    page_view_daily_stats,
     page_views,
     projects,
     projects_technologies,
     refresh_tokens,
+    search_clicks,
+    search_queries,
+    search_refresh_requests,
+    search_refreshes,
+    series,
+    series_blog_posts,
     static_pages,
     technologies,
     text_bodies,
     uploaded_images,
     users,
     verify_email_tokens,
+    visits,
 );
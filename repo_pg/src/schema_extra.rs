@@ -1,3 +1,22 @@
+table! {
+    use diesel::sql_types::*;
+    use crate::exports::*;
+
+    related_items (item_type, item_id, rank) {
+        item_id -> Int4,
+        item_type -> Search_item_type,
+        rank -> Int4,
+        related_id -> Int4,
+        title -> Varchar,
+        created_at -> Nullable<Timestamp>,
+        image -> Nullable<Varchar>,
+        description -> Varchar,
+        link -> Varchar,
+        language -> Content_language,
+        score -> Float8,
+    }
+}
+
 table! {
     use diesel::sql_types::*;
     use diesel_full_text_search::TsVector;
@@ -13,5 +32,19 @@ table! {
         description -> Varchar,
         item_type -> Search_item_type,
         link -> Varchar,
+        body -> Text,
+        language -> Content_language,
+        categories -> Array<Varchar>,
+        technologies -> Array<Varchar>,
+    }
+}
+
+table! {
+    use diesel::sql_types::*;
+    use crate::exports::*;
+
+    search_title_words (word, item_type) {
+        word -> Varchar,
+        item_type -> Search_item_type,
     }
 }
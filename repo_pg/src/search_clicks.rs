@@ -0,0 +1,21 @@
+use crate::insertables::NewSearchClick;
+use crate::schema::search_clicks;
+use diesel::RunQueryDsl;
+
+pub struct SearchClickRepo<'a> {
+    conn: &'a crate::pg_util::RepoConnection,
+}
+
+impl<'a> SearchClickRepo<'a> {
+    pub fn new(conn: &'a crate::pg_util::RepoConnection) -> Self {
+        Self { conn }
+    }
+
+    pub fn insert_one(&self, new_click: NewSearchClick) -> Result<i32, diesel::result::Error> {
+        let conn = &self.conn.pg_conn;
+        let query = diesel::insert_into(search_clicks::table)
+            .values(&new_click)
+            .returning(search_clicks::id);
+        query.get_result(conn)
+    }
+}
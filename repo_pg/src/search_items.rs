@@ -1,8 +1,115 @@
+use crate::extra::{ContentLanguage, SearchItemType};
 use crate::filters::GetAllSearchItemsFilter;
 use crate::models::{db_models, domain};
 use crate::options::{PaginationOptions, SearchItemsSortType};
-use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
-use diesel_full_text_search::{plainto_tsquery, TsVectorExtensions};
+use crate::page_views::recent_view_count_sql;
+use crate::schema_extra::search_items;
+use crate::schema_extra::search_title_words;
+use crate::text_search::{
+    detect_language, escape_like, search_config, similarity, split_prefix_term, to_tsquery_with,
+    ts_headline, websearch_to_tsquery, word_similarity, HEADLINE_OPTIONS, SIMILARITY_THRESHOLD,
+};
+use diesel::dsl::sql;
+use diesel::expression::BoxableExpression;
+use diesel::pg::Pg;
+use diesel::query_builder::BoxedSelectStatement;
+use diesel::sql_types::{BigInt, Bool, Nullable, Text};
+use diesel::{
+    BoolExpressionMethods, ExpressionMethods, GroupByDsl, NullableExpressionMethods,
+    OptionalExtension, PgArrayExpressionMethods, PgTextExpressionMethods, QueryDsl, RunQueryDsl,
+};
+use diesel_full_text_search::{ts_rank_cd, TsQuery, TsQueryExtensions, TsVectorExtensions};
+
+type SearchQuery = Box<dyn BoxableExpression<search_items::table, Pg, SqlType = TsQuery>>;
+
+/// Parses search text with the `websearch_to_tsquery` syntax, optionally matching the
+/// last word as a prefix.
+fn search_query(search_text: &str, prefix: bool, language: ContentLanguage) -> SearchQuery {
+    let (terms, prefix_term) = split_prefix_term(search_text, prefix);
+    let query = websearch_to_tsquery(search_config(language), terms);
+    match prefix_term {
+        Some(term) => Box::new(query.and(to_tsquery_with(search_config(language), term))),
+        None => Box::new(query),
+    }
+}
+
+type BoxedSearchItems<'q, ST> = BoxedSelectStatement<'q, ST, search_items::table, Pg>;
+
+/// A refinement whose own filter is left out when counting its values, so the counts
+/// show what picking another value would return.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Facet {
+    Type,
+    Category,
+    Technology,
+}
+
+fn search_text(filter: &GetAllSearchItemsFilter) -> Option<&str> {
+    filter
+        .search_text
+        .as_deref()
+        .map(str::trim)
+        .filter(|search_text| !search_text.is_empty())
+}
+
+/// The language asked for, or the one the search text looks to be written in.
+fn search_language(filter: &GetAllSearchItemsFilter, search_text: &str) -> ContentLanguage {
+    match filter.language {
+        Some(language) => language,
+        None => detect_language(search_text),
+    }
+}
+
+fn apply_filters<'q, ST>(
+    q: BoxedSearchItems<'q, ST>,
+    filter: &GetAllSearchItemsFilter,
+    facet: Option<Facet>,
+) -> BoxedSearchItems<'q, ST> {
+    use crate::schema_extra::search_items::dsl::{
+        categories, created_at, item_type, search_vec, technologies,
+    };
+
+    let q = if let Some(search_text) = search_text(filter) {
+        q.filter(search_vec.matches(search_query(
+            search_text,
+            filter.prefix,
+            search_language(filter, search_text),
+        )))
+    } else {
+        q
+    };
+
+    let q = match &filter.r#type {
+        Some(r#type) if facet != Some(Facet::Type) => q.filter(item_type.eq(r#type.clone())),
+        _ => q,
+    };
+
+    let q = match &filter.category {
+        Some(category) if facet != Some(Facet::Category) => {
+            q.filter(categories.contains(vec![category.clone()]))
+        }
+        _ => q,
+    };
+
+    let q = match &filter.technology {
+        Some(technology) if facet != Some(Facet::Technology) => {
+            q.filter(technologies.contains(vec![technology.clone()]))
+        }
+        _ => q,
+    };
+
+    let q = if let Some(created_after) = filter.created_after {
+        q.filter(created_at.ge(created_after))
+    } else {
+        q
+    };
+
+    if let Some(created_before) = filter.created_before {
+        q.filter(created_at.lt(created_before))
+    } else {
+        q
+    }
+}
 
 pub struct SearchItemRepo<'a> {
     conn: &'a crate::pg_util::RepoConnection,
@@ -13,6 +120,7 @@ impl<'a> SearchItemRepo<'a> {
         Self { conn }
     }
 
+    /// Searches everything, best matches first unless another order is asked for.
     pub fn find(
         &self,
         filter: GetAllSearchItemsFilter,
@@ -20,9 +128,208 @@ impl<'a> SearchItemRepo<'a> {
         pagination: PaginationOptions,
     ) -> Result<(Vec<domain::SearchItem>, i64), diesel::result::Error> {
         use crate::schema_extra::search_items::dsl::{
-            created_at, description, id, image, item_type, link, search_items as search_items_dsl,
-            search_vec, title, updated_at,
+            body, created_at, description, id, image, item_type, language, link,
+            search_items as search_items_dsl, search_vec, title, updated_at,
+        };
+        let search_text = search_text(&filter);
+        let columns = (
+            id,
+            title,
+            created_at,
+            updated_at,
+            image,
+            description,
+            item_type,
+            link,
+            language,
+        );
+        let total = sql::<BigInt>("count(*) over()");
+        let q = search_items_dsl.into_boxed();
+
+        let q = if let Some(search_text) = search_text {
+            q.select((
+                columns,
+                // Each item is highlighted with the stemming it was indexed with
+                ts_headline(
+                    search_config(language),
+                    body,
+                    search_query(
+                        search_text,
+                        filter.prefix,
+                        search_language(&filter, search_text),
+                    ),
+                    HEADLINE_OPTIONS,
+                )
+                .nullable(),
+                total,
+            ))
+        } else {
+            q.select((columns, sql::<Nullable<Text>>("NULL"), total))
+        };
+        let q = apply_filters(q, &filter, None);
+
+        let sort = match (sort, search_text) {
+            (Some(sort_type), _) => sort_type,
+            (None, Some(_)) => SearchItemsSortType::Relevance,
+            (None, None) => SearchItemsSortType::CreatedAtDesc,
+        };
+        let q = match (sort, search_text) {
+            (SearchItemsSortType::Relevance, Some(search_text)) => q
+                .order(
+                    ts_rank_cd(
+                        search_vec,
+                        search_query(
+                            search_text,
+                            filter.prefix,
+                            search_language(&filter, search_text),
+                        ),
+                    )
+                    .desc(),
+                )
+                .then_order_by(title.asc()),
+            (SearchItemsSortType::Relevance, None) => q.order(title.asc()),
+            (SearchItemsSortType::CreatedAtAsc, _) => q.order(created_at.asc()),
+            (SearchItemsSortType::CreatedAtDesc, _) => q.order(created_at.desc()),
+            (SearchItemsSortType::TitleAsc, _) => q.order(title.asc()),
+            (SearchItemsSortType::TitleDesc, _) => q.order(title.desc()),
+        };
+
+        let q = if let (Some(page), Some(page_size)) = (pagination.page, pagination.page_size) {
+            q.offset((page - 1) * page_size).limit(page_size)
+        } else {
+            q
+        };
+
+        let conn = &self.conn.pg_conn;
+        let results: Vec<(db_models::SearchItem, Option<String>, i64)> = q.load(conn)?;
+
+        let count = match results.first() {
+            Some((_, _, value)) => *value,
+            None => 0,
         };
+        let search_item_results = results
+            .into_iter()
+            .map(|(search_item, headline, _)| domain::SearchItem::from(search_item, headline))
+            .collect::<Vec<_>>();
+        Ok((search_item_results, count))
+    }
+
+    /// How many results each type, category and technology has, most first.
+    pub fn find_facets(
+        &self,
+        filter: &GetAllSearchItemsFilter,
+    ) -> Result<domain::SearchFacets, diesel::result::Error> {
+        use crate::schema_extra::search_items::dsl::{item_type, search_items as search_items_dsl};
+
+        let conn = &self.conn.pg_conn;
+        let count = || sql::<BigInt>("count(*)");
+
+        let q = search_items_dsl
+            .into_boxed()
+            .select((item_type, count()))
+            .group_by(item_type);
+        let types: Vec<(SearchItemType, i64)> = apply_filters(q, filter, Some(Facet::Type))
+            .order((count().desc(), item_type.asc()))
+            .load(conn)?;
+
+        let mut names = Vec::new();
+        for (facet, column) in [
+            (Facet::Category, "categories"),
+            (Facet::Technology, "technologies"),
+        ] {
+            let name = || sql::<Text>(&format!("unnest({})", column));
+            let q = search_items_dsl
+                .into_boxed()
+                .select((name(), count()))
+                .group_by(sql::<Text>("1"));
+            let results: Vec<(String, i64)> = apply_filters(q, filter, Some(facet))
+                .order((count().desc(), sql::<Text>("1").asc()))
+                .load(conn)?;
+            names.push(
+                results
+                    .into_iter()
+                    .map(|(name, count)| domain::SearchFacet { name, count })
+                    .collect::<Vec<_>>(),
+            );
+        }
+        let technologies = names.pop().unwrap_or_default();
+        let categories = names.pop().unwrap_or_default();
+
+        Ok(domain::SearchFacets {
+            types: types
+                .into_iter()
+                .map(|(item_type_value, count)| domain::SearchTypeFacet {
+                    item_type: item_type_value,
+                    count,
+                })
+                .collect(),
+            categories,
+            technologies,
+        })
+    }
+
+    /// Titles that start with the text, have a word starting with it, or look like it,
+    /// closest first.
+    pub fn find_completions(
+        &self,
+        search_text: &str,
+        r#type: Option<SearchItemType>,
+        limit: i64,
+    ) -> Result<Vec<domain::SearchCompletion>, diesel::result::Error> {
+        use crate::schema_extra::search_items::dsl::{
+            item_type, link, search_items as search_items_dsl, title,
+        };
+        let search_text = search_text.trim();
+        let starts_with = format!("{}%", escape_like(search_text));
+        let word_starts_with = format!("% {}%", escape_like(search_text));
+        let q = search_items_dsl
+            .select((title, item_type, link))
+            .filter(
+                title
+                    .ilike(starts_with.clone())
+                    .or(title.ilike(word_starts_with))
+                    .or(word_similarity(search_text, title).gt(SIMILARITY_THRESHOLD)),
+            )
+            .order((
+                title.ilike(starts_with).desc(),
+                word_similarity(search_text, title).desc(),
+                title.asc(),
+            ))
+            .limit(limit)
+            .into_boxed();
+
+        let q = if let Some(r#type) = r#type {
+            q.filter(item_type.eq(r#type))
+        } else {
+            q
+        };
+
+        let conn = &self.conn.pg_conn;
+        let results: Vec<(String, SearchItemType, String)> = q.load(conn)?;
+        Ok(results
+            .into_iter()
+            .map(
+                |(title_value, item_type_value, link_value)| domain::SearchCompletion {
+                    title: title_value,
+                    item_type: item_type_value,
+                    link: link_value,
+                },
+            )
+            .collect())
+    }
+
+    /// Published posts and projects most viewed by people in the last `days` days.
+    pub fn find_trending(
+        &self,
+        r#type: Option<SearchItemType>,
+        days: i64,
+        limit: i64,
+    ) -> Result<Vec<domain::TrendingItem>, diesel::result::Error> {
+        use crate::schema_extra::search_items::dsl::{
+            created_at, description, id, image, item_type, language, link,
+            search_items as search_items_dsl, title, updated_at,
+        };
+        let views_sql = recent_view_count_sql("search_items.link", days);
         let q = search_items_dsl
             .select((
                 (
@@ -34,40 +341,74 @@ impl<'a> SearchItemRepo<'a> {
                     description,
                     item_type,
                     link,
+                    language,
                 ),
-                diesel::dsl::sql::<diesel::sql_types::BigInt>("count(*) over()"),
+                sql::<BigInt>(&views_sql),
             ))
+            .filter(item_type.eq_any(vec![SearchItemType::BlogPost, SearchItemType::Project]))
+            .filter(sql::<Bool>(&format!("{} > 0", views_sql)))
+            .order((sql::<BigInt>(&views_sql).desc(), created_at.desc()))
+            .limit(limit)
             .into_boxed();
 
-        let q = if let Some(search_text) = filter.search_text {
-            q.filter(search_vec.matches(plainto_tsquery(search_text)))
-        } else {
-            q
-        };
-
-        let q = if let Some(r#type) = filter.r#type {
+        let q = if let Some(r#type) = r#type {
             q.filter(item_type.eq(r#type))
         } else {
             q
         };
 
-        let q = if let (Some(page), Some(page_size)) = (pagination.page, pagination.page_size) {
-            q.offset((page - 1) * page_size).limit(page_size)
-        } else {
-            q
-        };
-
         let conn = &self.conn.pg_conn;
         let results: Vec<(db_models::SearchItem, i64)> = q.load(conn)?;
-
-        let count = match results.get(0) {
-            Some((_, value)) => *value,
-            None => 0,
-        };
-        let search_item_results = results
+        Ok(results
             .into_iter()
-            .map(|(search_item, _)| domain::SearchItem::from(search_item))
-            .collect::<Vec<_>>();
-        Ok((search_item_results, count))
+            .map(|(search_item, views)| domain::TrendingItem::from(search_item, views))
+            .collect())
+    }
+
+    /// Replaces each word of the text that appears in no title with the most similar
+    /// title word. Returns `None` when nothing needed correcting.
+    pub fn find_correction(
+        &self,
+        search_text: &str,
+        r#type: Option<SearchItemType>,
+    ) -> Result<Option<String>, diesel::result::Error> {
+        use crate::schema_extra::search_title_words::dsl::{item_type, word};
+
+        let conn = &self.conn.pg_conn;
+        let mut corrected = false;
+        let mut words = Vec::new();
+        for original in search_text.split_whitespace() {
+            let cleaned = original
+                .chars()
+                .filter(|c| c.is_alphanumeric())
+                .collect::<String>()
+                .to_lowercase();
+            if cleaned.chars().count() < 3 || original.starts_with('-') {
+                words.push(original.to_string());
+                continue;
+            }
+            let q = search_title_words::table
+                .select(word)
+                .filter(similarity(&cleaned, word).gt(SIMILARITY_THRESHOLD))
+                .order((similarity(&cleaned, word).desc(), word.asc()))
+                .into_boxed();
+            let q = if let Some(r#type) = r#type.clone() {
+                q.filter(item_type.eq(r#type))
+            } else {
+                q
+            };
+            match q.first::<String>(conn).optional()? {
+                Some(candidate) if candidate != cleaned => {
+                    corrected = true;
+                    words.push(candidate);
+                }
+                _ => words.push(original.to_string()),
+            }
+        }
+        Ok(if corrected {
+            Some(words.join(" "))
+        } else {
+            None
+        })
     }
 }
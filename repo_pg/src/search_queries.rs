@@ -0,0 +1,135 @@
+use crate::filters::DateRangeFilter;
+use crate::insertables::NewSearchQuery;
+use crate::models::domain;
+use crate::options::ReportInterval;
+use crate::schema::search_queries;
+use chrono::NaiveDateTime;
+use diesel::dsl::{exists, sql};
+use diesel::pg::Pg;
+use diesel::prelude::*;
+use diesel::query_builder::BoxedSelectStatement;
+use diesel::sql_types::{BigInt, Double, Text, Timestamp};
+use diesel::{GroupByDsl, QueryDsl, RunQueryDsl};
+
+/// Counts the searches of a group that had at least one result followed.
+const CLICKED_SEARCHES: &str = "count(*) FILTER (WHERE EXISTS (\
+    SELECT 1 FROM search_clicks WHERE search_clicks.search_query_id = search_queries.id))";
+
+fn apply_range<'q, ST>(
+    q: BoxedSelectStatement<'q, ST, search_queries::table, Pg>,
+    range: DateRangeFilter,
+) -> BoxedSelectStatement<'q, ST, search_queries::table, Pg> {
+    use crate::schema::search_queries::dsl::created_at;
+
+    let q = if let Some(from) = range.from {
+        q.filter(created_at.ge(from))
+    } else {
+        q
+    };
+    if let Some(to) = range.to {
+        q.filter(created_at.lt(to))
+    } else {
+        q
+    }
+}
+
+pub struct SearchQueryRepo<'a> {
+    conn: &'a crate::pg_util::RepoConnection,
+}
+
+impl<'a> SearchQueryRepo<'a> {
+    pub fn new(conn: &'a crate::pg_util::RepoConnection) -> Self {
+        Self { conn }
+    }
+
+    pub fn insert_one(&self, new_query: NewSearchQuery) -> Result<i32, diesel::result::Error> {
+        let conn = &self.conn.pg_conn;
+        let query = diesel::insert_into(search_queries::table)
+            .values(&new_query)
+            .returning(search_queries::id);
+        query.get_result(conn)
+    }
+
+    pub fn exists(&self, id_value: i32) -> Result<bool, diesel::result::Error> {
+        use crate::schema::search_queries::dsl::{id, search_queries};
+
+        let conn = &self.conn.pg_conn;
+        diesel::select(exists(search_queries.filter(id.eq(id_value)))).get_result(conn)
+    }
+
+    /// The most searched queries in a range, optionally only those that found nothing.
+    pub fn find_top(
+        &self,
+        range: DateRangeFilter,
+        zero_results_only: bool,
+        limit: i64,
+    ) -> Result<Vec<domain::SearchQueryStats>, diesel::result::Error> {
+        use crate::schema::search_queries::dsl::{
+            query, result_count, search_queries as search_queries_dsl,
+        };
+
+        let conn = &self.conn.pg_conn;
+        let searches = || sql::<BigInt>("count(*)");
+        let q = search_queries_dsl
+            .into_boxed()
+            .select((
+                query,
+                searches(),
+                sql::<BigInt>(CLICKED_SEARCHES),
+                sql::<Double>("avg(result_count)::float8"),
+            ))
+            .group_by(query);
+        let q = apply_range(q, range);
+        let q = if zero_results_only {
+            q.filter(result_count.eq(0))
+        } else {
+            q
+        };
+        let results: Vec<(String, i64, i64, f64)> = q
+            .order((searches().desc(), query.asc()))
+            .limit(limit)
+            .load(conn)?;
+        Ok(results
+            .into_iter()
+            .map(
+                |(query_value, searches, clicked_searches, average_results)| {
+                    domain::SearchQueryStats {
+                        query: query_value,
+                        searches,
+                        clicked_searches,
+                        average_results,
+                    }
+                },
+            )
+            .collect())
+    }
+
+    /// Searches and the share of them with a result followed, per period, oldest first.
+    pub fn find_click_through(
+        &self,
+        range: DateRangeFilter,
+        interval: ReportInterval,
+    ) -> Result<Vec<domain::ClickThroughStats>, diesel::result::Error> {
+        use crate::schema::search_queries::dsl::search_queries as search_queries_dsl;
+
+        let conn = &self.conn.pg_conn;
+        let period_sql = format!("date_trunc('{}', created_at)", interval.as_str());
+        let q = search_queries_dsl
+            .into_boxed()
+            .select((
+                sql::<Timestamp>(&period_sql),
+                sql::<BigInt>("count(*)"),
+                sql::<BigInt>(CLICKED_SEARCHES),
+            ))
+            .group_by(sql::<Text>("1"));
+        let results: Vec<(NaiveDateTime, i64, i64)> = apply_range(q, range)
+            .order(sql::<Text>("1").asc())
+            .load(conn)?;
+        Ok(results
+            .into_iter()
+            .map(|(period, searches, clicked_searches)| {
+                domain::ClickThroughStats::from(period, searches, clicked_searches)
+            })
+            .collect())
+    }
+}
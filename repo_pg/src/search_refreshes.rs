@@ -0,0 +1,127 @@
+use crate::models::{db_models, domain};
+use crate::schema::{search_refresh_requests, search_refreshes};
+use chrono::NaiveDateTime;
+use diesel::dsl::{now, sql};
+use diesel::prelude::*;
+use diesel::sql_types::Timestamp;
+use diesel::{QueryDsl, RunQueryDsl};
+
+const SEARCH_REFRESH_ID: i32 = 1;
+
+pub struct SearchRefreshRepo<'a> {
+    conn: &'a crate::pg_util::RepoConnection,
+}
+
+impl<'a> SearchRefreshRepo<'a> {
+    pub fn new(conn: &'a crate::pg_util::RepoConnection) -> Self {
+        Self { conn }
+    }
+
+    pub fn find_one(&self) -> Result<domain::SearchRefresh, diesel::result::Error> {
+        let last_pending = self.find_last_request()?.map(|(_, value)| value);
+        Ok(domain::SearchRefresh::from(
+            self.find_state()?,
+            last_pending,
+        ))
+    }
+
+    /// Rebuilds the search and related items views without blocking searches that run meanwhile.
+    ///
+    /// Meant to run outside a transaction: the requests are read first and only
+    /// those are deleted afterwards. Ids are taken when a write starts, not when it
+    /// commits, so a request committed meanwhile can have a lower id than the ones
+    /// read, and is left for the next refresh.
+    pub fn refresh(&self) -> Result<domain::SearchRefresh, diesel::result::Error> {
+        use crate::schema::search_refresh_requests::dsl::id as request_id;
+        use crate::schema::search_refreshes::dsl::{
+            id, refreshed_at, refreshed_through, requested_at,
+        };
+
+        let conn = &self.conn.pg_conn;
+        let covered = self.find_requests()?;
+        diesel::sql_query("REFRESH MATERIALIZED VIEW CONCURRENTLY search_items").execute(conn)?;
+        diesel::sql_query("REFRESH MATERIALIZED VIEW CONCURRENTLY search_title_words")
+            .execute(conn)?;
+        diesel::sql_query("REFRESH MATERIALIZED VIEW CONCURRENTLY related_items").execute(conn)?;
+        let result = match covered.iter().map(|(_, value)| *value).max() {
+            Some(last_requested_at) => {
+                let covered_ids = covered.iter().map(|(value, _)| *value).collect::<Vec<_>>();
+                diesel::delete(
+                    search_refresh_requests::table.filter(request_id.eq_any(covered_ids)),
+                )
+                .execute(conn)?;
+                diesel::update(search_refreshes::table.filter(id.eq(SEARCH_REFRESH_ID)))
+                    .set((
+                        requested_at.eq(last_requested_at),
+                        refreshed_at.eq(now),
+                        refreshed_through.eq(last_requested_at),
+                    ))
+                    .get_result(conn)?
+            }
+            None => diesel::update(search_refreshes::table.filter(id.eq(SEARCH_REFRESH_ID)))
+                .set((
+                    refreshed_at.eq(now),
+                    refreshed_through.eq(requested_at.nullable()),
+                ))
+                .get_result(conn)?,
+        };
+        let last_pending = self.find_last_request()?.map(|(_, value)| value);
+        Ok(domain::SearchRefresh::from(result, last_pending))
+    }
+
+    /// Refreshes only if content changed and nothing else changed for `quiet_period_ms`,
+    /// so a burst of writes leads to a single refresh. Returns whether it refreshed.
+    pub fn refresh_if_due(&self, quiet_period_ms: u64) -> Result<bool, diesel::result::Error> {
+        use crate::schema::search_refresh_requests::dsl::requested_at;
+
+        let conn = &self.conn.pg_conn;
+        let settled_before = sql::<Timestamp>(&format!(
+            "clock_timestamp()::timestamp - interval '{} milliseconds'",
+            quiet_period_ms
+        ));
+        if self.find_last_request()?.is_none() {
+            return Ok(false);
+        }
+        let recent: Option<NaiveDateTime> = search_refresh_requests::table
+            .select(requested_at)
+            .filter(requested_at.ge(settled_before))
+            .first(conn)
+            .optional()?;
+        if recent.is_some() {
+            return Ok(false);
+        }
+        self.refresh()?;
+        Ok(true)
+    }
+
+    /// The id and time of the newest request that was not refreshed yet.
+    fn find_last_request(&self) -> Result<Option<(i64, NaiveDateTime)>, diesel::result::Error> {
+        use crate::schema::search_refresh_requests::dsl::{id, requested_at};
+
+        let conn = &self.conn.pg_conn;
+        search_refresh_requests::table
+            .select((id, requested_at))
+            .order(id.desc())
+            .first(conn)
+            .optional()
+    }
+
+    fn find_requests(&self) -> Result<Vec<(i64, NaiveDateTime)>, diesel::result::Error> {
+        use crate::schema::search_refresh_requests::dsl::{id, requested_at};
+
+        let conn = &self.conn.pg_conn;
+        search_refresh_requests::table
+            .select((id, requested_at))
+            .load(conn)
+    }
+
+    fn find_state(&self) -> Result<db_models::SearchRefresh, diesel::result::Error> {
+        use crate::schema::search_refreshes::dsl::id;
+
+        let conn = &self.conn.pg_conn;
+        search_refreshes::table
+            .filter(id.eq(SEARCH_REFRESH_ID))
+            .select(search_refreshes::all_columns)
+            .first(conn)
+    }
+}
@@ -0,0 +1,312 @@
+use crate::change_sets::UpdateSeries;
+use crate::filters::GetAllSeriesFilter;
+use crate::insertables::{NewSeries, NewSeriesBlogPost};
+use crate::models::{db_models, domain};
+use crate::options::PaginationOptions;
+use crate::schema::{blog_posts, series, series_blog_posts};
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::BigInt;
+use diesel::{QueryDsl, RunQueryDsl};
+use std::collections::HashMap;
+
+/// A post of a series: the series id, then the post's id, title, slug and whether it
+/// is published.
+type SeriesPostRow = (i32, i32, String, String, bool);
+
+pub struct SeriesRepo<'a> {
+    conn: &'a crate::pg_util::RepoConnection,
+}
+
+impl<'a> SeriesRepo<'a> {
+    pub fn new(conn: &'a crate::pg_util::RepoConnection) -> Self {
+        Self { conn }
+    }
+
+    pub fn insert_one_with_posts(
+        &self,
+        new_series: &NewSeries,
+        blog_post_ids: &[i32],
+    ) -> Result<domain::Series, diesel::result::Error> {
+        let conn = &self.conn.pg_conn;
+        let inserted: db_models::Series = diesel::insert_into(series::table)
+            .values(new_series)
+            .get_result(conn)?;
+        self.update_posts(inserted.id, blog_post_ids)?;
+        let posts = self.find_posts(inserted.id, false)?;
+        Ok(domain::Series::from(inserted, posts))
+    }
+
+    /// Updates a series, and replaces its posts when `blog_post_ids` is given.
+    pub fn update_one(
+        &self,
+        id_value: i32,
+        updated_series: &UpdateSeries,
+        blog_post_ids: Option<&[i32]>,
+    ) -> Result<domain::Series, diesel::result::Error> {
+        use crate::schema::series::dsl::id;
+
+        let conn = &self.conn.pg_conn;
+        let updated: db_models::Series = diesel::update(series::table.filter(id.eq(id_value)))
+            .set(updated_series)
+            .get_result(conn)?;
+        if let Some(blog_post_ids) = blog_post_ids {
+            self.update_posts(id_value, blog_post_ids)?;
+        }
+        let posts = self.find_posts(id_value, false)?;
+        Ok(domain::Series::from(updated, posts))
+    }
+
+    /// Makes these posts, in this order, the only ones in the series. Posts that were in
+    /// another series are taken out of it.
+    fn update_posts(
+        &self,
+        series_id_value: i32,
+        blog_post_ids: &[i32],
+    ) -> Result<(), diesel::result::Error> {
+        use crate::schema::series_blog_posts::dsl::{blog_post_id, series_id};
+
+        let mut unique_ids: Vec<i32> = Vec::with_capacity(blog_post_ids.len());
+        for id_value in blog_post_ids {
+            if !unique_ids.contains(id_value) {
+                unique_ids.push(*id_value);
+            }
+        }
+        let conn = &self.conn.pg_conn;
+        diesel::delete(
+            series_blog_posts::table.filter(
+                series_id
+                    .eq(series_id_value)
+                    .or(blog_post_id.eq_any(&unique_ids)),
+            ),
+        )
+        .execute(conn)?;
+        let new_posts: Vec<NewSeriesBlogPost> = unique_ids
+            .into_iter()
+            .enumerate()
+            .map(|(index, id_value)| NewSeriesBlogPost {
+                series_id: series_id_value,
+                blog_post_id: id_value,
+                position: index as i32 + 1,
+            })
+            .collect();
+        diesel::insert_into(series_blog_posts::table)
+            .values(&new_posts)
+            .execute(conn)?;
+        Ok(())
+    }
+
+    pub fn delete_one(&self, id_value: i32) -> Result<usize, diesel::result::Error> {
+        use crate::schema::series::dsl::id;
+
+        let conn = &self.conn.pg_conn;
+        diesel::delete(series::table.filter(id.eq(id_value))).execute(conn)
+    }
+
+    /// A series with its posts, leaving unpublished posts out if `published_posts_only`.
+    pub fn find_one(
+        &self,
+        id_value: i32,
+        published_posts_only: bool,
+    ) -> Result<Option<domain::Series>, diesel::result::Error> {
+        use crate::schema::series::dsl::id;
+
+        let conn = &self.conn.pg_conn;
+        let found: db_models::Series = match series::table
+            .filter(id.eq(id_value))
+            .first(conn)
+            .optional()?
+        {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+        let posts = self.find_posts(found.id, published_posts_only)?;
+        Ok(Some(domain::Series::from(found, posts)))
+    }
+
+    pub fn find_one_by_slug(
+        &self,
+        slug_value: String,
+        published_posts_only: bool,
+    ) -> Result<Option<domain::Series>, diesel::result::Error> {
+        use crate::schema::series::dsl::slug;
+
+        let conn = &self.conn.pg_conn;
+        let found: db_models::Series = match series::table
+            .filter(slug.eq(slug_value))
+            .first(conn)
+            .optional()?
+        {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+        let posts = self.find_posts(found.id, published_posts_only)?;
+        Ok(Some(domain::Series::from(found, posts)))
+    }
+
+    /// Series newest first, each with its posts.
+    pub fn find(
+        &self,
+        filter: GetAllSeriesFilter,
+        published_posts_only: bool,
+        pagination: PaginationOptions,
+    ) -> Result<(Vec<domain::Series>, i64), diesel::result::Error> {
+        use crate::schema::series::dsl::{created_at, published};
+        use crate::schema::series_blog_posts::dsl::{position, series_id};
+
+        let q = series::table
+            .select((series::all_columns, sql::<BigInt>("count(*) over()")))
+            .order(created_at.desc())
+            .into_boxed();
+        let q = if let Some(published_value) = filter.published {
+            q.filter(published.eq(published_value))
+        } else {
+            q
+        };
+        let q = if let (Some(page), Some(page_size)) = (pagination.page, pagination.page_size) {
+            q.offset((page - 1) * page_size).limit(page_size)
+        } else {
+            q
+        };
+
+        let conn = &self.conn.pg_conn;
+        let results: Vec<(db_models::Series, i64)> = q.load(conn)?;
+        let count = match results.first() {
+            Some((_, value)) => *value,
+            None => 0,
+        };
+
+        let series_ids: Vec<i32> = results.iter().map(|(found, _)| found.id).collect();
+        let q = series_blog_posts::table
+            .inner_join(blog_posts::table)
+            .select((
+                series_id,
+                blog_posts::id,
+                blog_posts::title,
+                blog_posts::slug,
+                blog_posts::published,
+            ))
+            .filter(series_id.eq_any(series_ids))
+            .order((series_id.asc(), position.asc()))
+            .into_boxed();
+        let q = if published_posts_only {
+            q.filter(blog_posts::published.eq(true))
+        } else {
+            q
+        };
+        let mut posts_by_series: HashMap<i32, Vec<domain::SeriesPost>> = HashMap::new();
+        for (series_id_value, post_id, title, slug, post_published) in
+            q.load::<SeriesPostRow>(conn)?
+        {
+            let posts = posts_by_series.entry(series_id_value).or_default();
+            posts.push(domain::SeriesPost {
+                id: post_id,
+                title,
+                slug,
+                position: posts.len() as i32 + 1,
+                published: post_published,
+            });
+        }
+
+        let series_list = results
+            .into_iter()
+            .map(|(found, _)| {
+                let posts = posts_by_series.remove(&found.id).unwrap_or_default();
+                domain::Series::from(found, posts)
+            })
+            .collect();
+        Ok((series_list, count))
+    }
+
+    /// Where a post is in its series, or `None` if it is in none. With
+    /// `published_only`, unpublished series and posts are treated as missing.
+    pub fn find_navigation(
+        &self,
+        blog_post_id_value: i32,
+        published_only: bool,
+    ) -> Result<Option<domain::SeriesNavigation>, diesel::result::Error> {
+        use crate::schema::series_blog_posts::dsl::blog_post_id;
+
+        let conn = &self.conn.pg_conn;
+        let found: db_models::Series = match series_blog_posts::table
+            .inner_join(series::table)
+            .filter(blog_post_id.eq(blog_post_id_value))
+            .select(series::all_columns)
+            .first(conn)
+            .optional()?
+        {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+        if published_only && !found.published {
+            return Ok(None);
+        }
+        let mut posts = self.find_posts(found.id, published_only)?;
+        let index = match posts.iter().position(|post| post.id == blog_post_id_value) {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+        let post_count = posts.len() as i32;
+        let next = if index + 1 < posts.len() {
+            Some(posts.remove(index + 1))
+        } else {
+            None
+        };
+        let previous = if index > 0 {
+            Some(posts.remove(index - 1))
+        } else {
+            None
+        };
+        Ok(Some(domain::SeriesNavigation {
+            id: found.id,
+            title: found.title,
+            slug: found.slug,
+            position: index as i32 + 1,
+            post_count,
+            previous,
+            next,
+        }))
+    }
+
+    /// The posts of a series in order, numbered from 1.
+    fn find_posts(
+        &self,
+        series_id_value: i32,
+        published_only: bool,
+    ) -> Result<Vec<domain::SeriesPost>, diesel::result::Error> {
+        use crate::schema::series_blog_posts::dsl::{position, series_id};
+
+        let conn = &self.conn.pg_conn;
+        let q = series_blog_posts::table
+            .inner_join(blog_posts::table)
+            .select((
+                series_id,
+                blog_posts::id,
+                blog_posts::title,
+                blog_posts::slug,
+                blog_posts::published,
+            ))
+            .filter(series_id.eq(series_id_value))
+            .order(position.asc())
+            .into_boxed();
+        let q = if published_only {
+            q.filter(blog_posts::published.eq(true))
+        } else {
+            q
+        };
+        let rows: Vec<SeriesPostRow> = q.load(conn)?;
+        Ok(rows
+            .into_iter()
+            .enumerate()
+            .map(
+                |(index, (_, id_value, title, slug, published))| domain::SeriesPost {
+                    id: id_value,
+                    title,
+                    slug,
+                    position: index as i32 + 1,
+                    published,
+                },
+            )
+            .collect())
+    }
+}
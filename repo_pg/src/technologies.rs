@@ -1,10 +1,13 @@
+use crate::categories::subtree_sql;
 use crate::errors::PgRepoError;
 use crate::filters::GetAllTechnologiesFilter;
 use crate::models::{db_models, domain};
 use crate::options::{PaginationOptions, TechnologySortType};
 use crate::schema::technologies;
 use crate::{change_sets::UpdateTechnology, insertables::NewTechnology};
+use diesel::dsl::sql;
 use diesel::prelude::*;
+use diesel::sql_types::Bool;
 use diesel::{QueryDsl, RunQueryDsl};
 
 pub struct TechnologyRepo<'a> {
@@ -62,6 +65,50 @@ impl<'a> TechnologyRepo<'a> {
         Ok(Some(domain::Technology::from(technology)))
     }
 
+    pub fn find_one_by_slug(
+        &self,
+        slug_value: String,
+    ) -> Result<Option<domain::Technology>, diesel::result::Error> {
+        use crate::schema::technologies::dsl::{slug, technologies};
+
+        let conn = &self.conn.pg_conn;
+        let query = technologies
+            .filter(slug.eq(slug_value))
+            .select(technologies::all_columns());
+        let technology: db_models::Technology = match query.first(conn).optional()? {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+
+        Ok(Some(domain::Technology::from(technology)))
+    }
+
+    /// The technologies directly under this one, by name.
+    pub fn find_children(
+        &self,
+        id_value: i32,
+    ) -> Result<Vec<domain::Technology>, diesel::result::Error> {
+        use crate::schema::technologies::dsl::{name, parent_id, technologies};
+
+        let conn = &self.conn.pg_conn;
+        let children: Vec<db_models::Technology> = technologies
+            .filter(parent_id.eq(id_value))
+            .order(name.asc())
+            .load(conn)?;
+        Ok(children.into_iter().map(domain::Technology::from).collect())
+    }
+
+    /// The id of this technology and of every technology nested under it.
+    pub fn find_subtree_ids(&self, id_value: i32) -> Result<Vec<i32>, diesel::result::Error> {
+        use crate::schema::technologies::dsl::{id, technologies};
+
+        let conn = &self.conn.pg_conn;
+        technologies
+            .select(id)
+            .filter(sql::<Bool>(&subtree_sql("technologies", id_value)))
+            .load(conn)
+    }
+
     pub fn find(
         &self,
         filter: GetAllTechnologiesFilter,
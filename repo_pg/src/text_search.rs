@@ -0,0 +1,146 @@
+use crate::exports::Content_language;
+use crate::extra::ContentLanguage;
+use diesel::sql_types::{Float, Text};
+use diesel_full_text_search::TsQuery;
+
+/// The `regconfig` type naming a text search configuration, such as `'english'`.
+#[derive(SqlType)]
+#[postgres(type_name = "regconfig")]
+pub struct Regconfig;
+
+sql_function!(fn search_config(language: Content_language) -> Regconfig);
+sql_function!(fn websearch_to_tsquery(config: Regconfig, query: Text) -> TsQuery);
+sql_function!(#[sql_name = "to_tsquery"] fn to_tsquery_with(config: Regconfig, query: Text) -> TsQuery);
+sql_function!(fn ts_headline(config: Regconfig, document: Text, query: TsQuery, options: Text) -> Text);
+sql_function!(fn similarity(left: Text, right: Text) -> Float);
+sql_function!(fn word_similarity(left: Text, right: Text) -> Float);
+
+/// Trigram similarity above which a title or word counts as a likely match.
+pub const SIMILARITY_THRESHOLD: f32 = 0.3;
+
+/// Options for `ts_headline`: up to two short fragments with the matches in `<mark>` tags.
+pub const HEADLINE_OPTIONS: &str =
+    "StartSel=<mark>, StopSel=</mark>, MaxFragments=2, MaxWords=30, MinWords=10";
+
+/// Guesses the language of a text from its script, for when none was asked for.
+pub fn detect_language(text: &str) -> ContentLanguage {
+    let (greek, other) =
+        text.chars()
+            .filter(|c| c.is_alphabetic())
+            .fold((0, 0), |(greek, other), c| match c {
+                '\u{0370}'..='\u{03ff}' | '\u{1f00}'..='\u{1fff}' => (greek + 1, other),
+                _ => (greek, other + 1),
+            });
+    if greek > other {
+        ContentLanguage::Greek
+    } else {
+        ContentLanguage::English
+    }
+}
+
+/// Splits search text into the part parsed with `websearch_to_tsquery` and, when
+/// `prefix` is set, a last word to match as a prefix. The last word is left in the
+/// main query if it is quoted, excluded or the user already typed past it.
+pub fn split_prefix_term(search_text: &str, prefix: bool) -> (String, Option<String>) {
+    if !prefix || search_text.ends_with(char::is_whitespace) {
+        return (search_text.to_string(), None);
+    }
+    let (head, last) = match search_text.rfind(char::is_whitespace) {
+        Some(index) => search_text.split_at(index),
+        None => ("", search_text),
+    };
+    if last.contains('"')
+        || last.trim_start().starts_with('-')
+        || head.matches('"').count() % 2 == 1
+    {
+        return (search_text.to_string(), None);
+    }
+    let term = last
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect::<String>()
+        .to_lowercase();
+    if term.is_empty() || term == "or" {
+        return (search_text.to_string(), None);
+    }
+    (head.trim().to_string(), Some(format!("{}:*", term)))
+}
+
+/// Longest search text kept in the search log.
+pub const MAX_LOGGED_QUERY_LENGTH: usize = 200;
+
+/// Normalizes search text for the search log: lowercased, with single spaces, and with
+/// anything that looks like an email address or a long number masked.
+pub fn normalize_query(search_text: &str) -> String {
+    search_text
+        .split_whitespace()
+        .map(|word| {
+            if word.contains('@') {
+                String::from("[email]")
+            } else if word.chars().filter(|c| c.is_ascii_digit()).count() >= 6 {
+                String::from("[number]")
+            } else {
+                word.to_lowercase()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+        .take(MAX_LOGGED_QUERY_LENGTH)
+        .collect()
+}
+
+/// Escapes the wildcards of a `LIKE` pattern.
+pub fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefix_term_is_split_off() {
+        assert_eq!(
+            split_prefix_term("rust async", true),
+            (String::from("rust"), Some(String::from("async:*")))
+        );
+        assert_eq!(
+            split_prefix_term("Rust", true),
+            (String::new(), Some(String::from("rust:*")))
+        );
+    }
+
+    #[test]
+    fn language_is_detected_from_script() {
+        assert_eq!(detect_language("async rust"), ContentLanguage::English);
+        assert_eq!(detect_language("Καλημέρα κόσμε"), ContentLanguage::Greek);
+        assert_eq!(
+            detect_language("Rust για αρχάριους"),
+            ContentLanguage::Greek
+        );
+        assert_eq!(detect_language("123"), ContentLanguage::English);
+    }
+
+    #[test]
+    fn logged_queries_are_normalized() {
+        assert_eq!(normalize_query("  Async   RUST "), "async rust");
+        assert_eq!(
+            normalize_query("contact me@example.com 6912345678"),
+            "contact [email] [number]"
+        );
+        assert_eq!(normalize_query("rust 2021"), "rust 2021");
+    }
+
+    #[test]
+    fn prefix_term_is_kept_in_query() {
+        assert_eq!(split_prefix_term("rust async", false).1, None);
+        assert_eq!(split_prefix_term("rust ", true).1, None);
+        assert_eq!(split_prefix_term("rust -java", true).1, None);
+        assert_eq!(split_prefix_term("\"async rust\"", true).1, None);
+        assert_eq!(split_prefix_term("\"async rust", true).1, None);
+    }
+}
@@ -6,6 +6,9 @@ use crate::{change_sets::UpdateUser, insertables::NewUser};
 use diesel::prelude::*;
 use diesel::{QueryDsl, RunQueryDsl};
 
+/// Display name of the user that comments of deleted accounts are moved to.
+pub const TOMBSTONE_DISPLAY_NAME: &str = "[deleted]";
+
 pub struct UserRepo<'a> {
     conn: &'a crate::pg_util::RepoConnection,
 }
@@ -41,6 +44,95 @@ impl<'a> UserRepo<'a> {
         Ok(query.execute(conn)?)
     }
 
+    /// Removes a user along with their tokens, notification preferences, ratings and flags. Their comments are
+    /// either moved to the tombstone user or deleted, everything else that must be
+    /// kept (posts, uploads, admin logs, newsletter issues) is moved to the tombstone user.
+    pub fn delete_account(
+        &self,
+        id_value: i32,
+        tombstone_id: i32,
+        delete_comments: bool,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::{
+            admin_logs, api_tokens, blog_post_comment_flags, blog_post_comment_ratings,
+            blog_post_comments, blog_posts, change_password_tokens, contact_message_replies,
+            newsletter_issues, notification_preferences, refresh_tokens, uploaded_images,
+            verify_email_tokens,
+        };
+        let conn = &self.conn.pg_conn;
+
+        diesel::delete(api_tokens::table.filter(api_tokens::user_id.eq(id_value))).execute(conn)?;
+        diesel::delete(
+            notification_preferences::table.filter(notification_preferences::user_id.eq(id_value)),
+        )
+        .execute(conn)?;
+        diesel::delete(refresh_tokens::table.filter(refresh_tokens::user_id.eq(id_value)))
+            .execute(conn)?;
+        diesel::delete(
+            verify_email_tokens::table.filter(verify_email_tokens::user_id.eq(id_value)),
+        )
+        .execute(conn)?;
+        diesel::delete(
+            change_password_tokens::table.filter(change_password_tokens::user_id.eq(id_value)),
+        )
+        .execute(conn)?;
+        diesel::delete(
+            blog_post_comment_ratings::table
+                .filter(blog_post_comment_ratings::user_id.eq(id_value)),
+        )
+        .execute(conn)?;
+        diesel::delete(
+            blog_post_comment_flags::table.filter(blog_post_comment_flags::user_id.eq(id_value)),
+        )
+        .execute(conn)?;
+
+        let user_comments = blog_post_comments::table
+            .filter(blog_post_comments::author_id.eq(id_value))
+            .select(blog_post_comments::id);
+        if delete_comments {
+            diesel::delete(
+                blog_post_comment_ratings::table
+                    .filter(blog_post_comment_ratings::blog_post_comment_id.eq_any(user_comments)),
+            )
+            .execute(conn)?;
+            diesel::delete(
+                blog_post_comment_flags::table
+                    .filter(blog_post_comment_flags::blog_post_comment_id.eq_any(user_comments)),
+            )
+            .execute(conn)?;
+            diesel::delete(
+                blog_post_comments::table.filter(blog_post_comments::author_id.eq(id_value)),
+            )
+            .execute(conn)?;
+        } else {
+            diesel::update(
+                blog_post_comments::table.filter(blog_post_comments::author_id.eq(id_value)),
+            )
+            .set(blog_post_comments::author_id.eq(tombstone_id))
+            .execute(conn)?;
+        }
+
+        diesel::update(blog_posts::table.filter(blog_posts::author_id.eq(id_value)))
+            .set(blog_posts::author_id.eq(tombstone_id))
+            .execute(conn)?;
+        diesel::update(uploaded_images::table.filter(uploaded_images::user_id.eq(id_value)))
+            .set(uploaded_images::user_id.eq(tombstone_id))
+            .execute(conn)?;
+        diesel::update(admin_logs::table.filter(admin_logs::user_id.eq(id_value)))
+            .set(admin_logs::user_id.eq(tombstone_id))
+            .execute(conn)?;
+        diesel::update(newsletter_issues::table.filter(newsletter_issues::user_id.eq(id_value)))
+            .set(newsletter_issues::user_id.eq(tombstone_id))
+            .execute(conn)?;
+        diesel::update(
+            contact_message_replies::table.filter(contact_message_replies::user_id.eq(id_value)),
+        )
+        .set(contact_message_replies::user_id.eq(tombstone_id))
+        .execute(conn)?;
+
+        self.delete_one(id_value)
+    }
+
     pub fn find_one(&self, id_value: i32) -> Result<Option<domain::User>, diesel::result::Error> {
         use crate::schema::users::dsl::{id, users};
 
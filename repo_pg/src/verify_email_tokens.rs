@@ -39,6 +39,27 @@ impl<'a> VerifyEmailTokenRepo<'a> {
         Ok(domain::VerifyEmailToken::from(result))
     }
 
+    /// Invalidates every email change of the user that was not confirmed yet.
+    pub fn invalidate_pending_changes(
+        &self,
+        user_id_value: i32,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::verify_email_tokens::dsl::{
+            invalidated, old_email, undo, used, user_id, verify_email_tokens,
+        };
+        let conn = &self.conn.pg_conn;
+        let query = diesel::update(
+            verify_email_tokens
+                .filter(user_id.eq(user_id_value))
+                .filter(old_email.is_not_null())
+                .filter(undo.eq(false))
+                .filter(used.eq(false))
+                .filter(invalidated.eq(false)),
+        )
+        .set(invalidated.eq(true));
+        Ok(query.execute(conn)?)
+    }
+
     pub fn delete_one(&self, id_value: i32) -> Result<usize, diesel::result::Error> {
         use crate::schema::verify_email_tokens::dsl::{id, verify_email_tokens};
         let conn = &self.conn.pg_conn;
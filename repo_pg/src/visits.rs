@@ -0,0 +1,68 @@
+use crate::insertables::NewVisit;
+use crate::schema::visits;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel::{QueryDsl, RunQueryDsl};
+
+pub struct VisitRepo<'a> {
+    conn: &'a crate::pg_util::RepoConnection,
+}
+
+impl<'a> VisitRepo<'a> {
+    pub fn new(conn: &'a crate::pg_util::RepoConnection) -> Self {
+        Self { conn }
+    }
+
+    pub fn insert_one(&self, new_visit: NewVisit) -> Result<i32, diesel::result::Error> {
+        let conn = &self.conn.pg_conn;
+        let query = diesel::insert_into(visits::table)
+            .values(&new_visit)
+            .returning(visits::id);
+        query.get_result(conn)
+    }
+
+    /// The latest visit of a visitor, if they viewed a page since `since`.
+    pub fn find_current(
+        &self,
+        id_hash_value: &str,
+        since: NaiveDateTime,
+    ) -> Result<Option<i32>, diesel::result::Error> {
+        use crate::schema::visits::dsl::{id, id_hash, last_viewed_at, visits};
+
+        let conn = &self.conn.pg_conn;
+        visits
+            .filter(id_hash.eq(id_hash_value))
+            .filter(last_viewed_at.ge(since))
+            .order(last_viewed_at.desc())
+            .select(id)
+            .first(conn)
+            .optional()
+    }
+
+    /// Makes `page_url` the exit page of a visit.
+    pub fn add_view(
+        &self,
+        id_value: i32,
+        page_url: &str,
+        viewed_at: NaiveDateTime,
+    ) -> Result<usize, diesel::result::Error> {
+        use crate::schema::visits::dsl::{exit_page, id, last_viewed_at, views, visits};
+
+        let conn = &self.conn.pg_conn;
+        diesel::update(visits.filter(id.eq(id_value)))
+            .set((
+                exit_page.eq(page_url),
+                views.eq(views + 1),
+                last_viewed_at.eq(viewed_at),
+            ))
+            .execute(conn)
+    }
+
+    /// Deletes visits that ended before `before`.
+    pub fn delete_before(&self, before: NaiveDateTime) -> Result<usize, diesel::result::Error> {
+        use crate::schema::visits::dsl::{last_viewed_at, visits};
+
+        let conn = &self.conn.pg_conn;
+        diesel::delete(visits.filter(last_viewed_at.lt(before))).execute(conn)
+    }
+}
@@ -66,3 +66,109 @@ mod tests {
         "{\"success\":false,\"errors\":[\"Project not found\"]}"
     );
 }
+
+#[cfg(test)]
+mod email_worker {
+    use backend_api::{
+        email_worker::deliver_due,
+        emails::{EmailMessage, EmailTransport, InMemoryEmailTransport},
+        errors::EmailError,
+    };
+    use backend_repo_pg::{
+        email_outbox::OutboxEmailRepo,
+        extra::EmailStatus,
+        insertables::NewOutboxEmail,
+        pg_util::{get_pg_pool, pg_transaction, DynRepo},
+    };
+    use pretty_assertions::assert_eq;
+    use std::{sync::Arc, time::SystemTime};
+
+    struct FailingEmailTransport;
+
+    impl EmailTransport for FailingEmailTransport {
+        fn send(&self, _: &EmailMessage) -> Result<(), EmailError> {
+            Err(EmailError::new(String::from("Connection refused")))
+        }
+    }
+
+    fn repo() -> DynRepo {
+        dotenv::dotenv().ok();
+        let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL is not set");
+        Arc::new(get_pg_pool(database_url, 2))
+    }
+
+    async fn queue(repo: &DynRepo) -> (i32, String) {
+        let nanos = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .expect("Clock before 1970")
+            .as_nanos();
+        let to_address = format!("outbox-{}@example.com", nanos);
+        let new_email = NewOutboxEmail {
+            to_address: to_address.clone(),
+            to_name: None,
+            subject: String::from("Subject"),
+            body_html: String::from("<p>Body</p>"),
+            body_text: String::from("Body"),
+            list_unsubscribe: None,
+        };
+        let email = pg_transaction(repo.clone(), |conn| {
+            OutboxEmailRepo::new(conn).insert_one(new_email)
+        })
+        .await
+        .expect("Failed to queue email");
+        (email.id, to_address)
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn outbox_sends_each_message_once_and_retries_failures() {
+        let repo = repo();
+        let (id, to_address) = queue(&repo).await;
+        let find = |repo: DynRepo| async move {
+            pg_transaction(repo, move |conn| OutboxEmailRepo::new(conn).find_one(id))
+                .await
+                .expect("Failed to load email")
+                .expect("Email is gone")
+        };
+
+        deliver_due(repo.clone(), Arc::new(FailingEmailTransport))
+            .await
+            .expect("Delivery failed");
+        let email = find(repo.clone()).await;
+        assert_eq!(email.status, EmailStatus::Pending);
+        assert_eq!(email.attempts, 1);
+        assert_eq!(email.last_error.as_deref(), Some("Connection refused"));
+
+        // Backing off, so not due again yet
+        let transport = Arc::new(InMemoryEmailTransport::default());
+        deliver_due(repo.clone(), transport.clone())
+            .await
+            .expect("Delivery failed");
+        assert!(transport
+            .messages()
+            .iter()
+            .all(|m| m.to_address != to_address));
+
+        pg_transaction(repo.clone(), move |conn| {
+            OutboxEmailRepo::new(conn).retry(id)
+        })
+        .await
+        .expect("Failed to retry email");
+        deliver_due(repo.clone(), transport.clone())
+            .await
+            .expect("Delivery failed");
+        deliver_due(repo.clone(), transport.clone())
+            .await
+            .expect("Delivery failed");
+        let sent = transport
+            .messages()
+            .into_iter()
+            .filter(|m| m.to_address == to_address)
+            .collect::<Vec<_>>();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].subject, "Subject");
+        let email = find(repo.clone()).await;
+        assert_eq!(email.status, EmailStatus::Sent);
+        assert_eq!(email.last_error, None);
+        assert!(email.sent_at.is_some());
+    }
+}